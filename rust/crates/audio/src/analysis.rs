@@ -0,0 +1,157 @@
+// Musical analysis helpers for Conway's Steinway
+// Derives simple melodic information from the keys played each generation
+
+use crate::key_mapping::PIANO_KEY_COUNT;
+
+/// Which key of a generation is treated as its principal (melodic) note
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrincipalNote {
+    Highest,
+    Lowest,
+}
+
+impl PrincipalNote {
+    // Pick the principal key out of a generation's keys
+    pub fn select(&self, keys: &[usize]) -> Option<usize> {
+        match self {
+            PrincipalNote::Highest => keys.iter().copied().max(),
+            PrincipalNote::Lowest => keys.iter().copied().min(),
+        }
+    }
+}
+
+/// Tracks the melodic interval between the principal notes of successive generations
+pub struct IntervalTracker {
+    principal: PrincipalNote,
+    previous: Option<usize>,
+}
+
+impl Default for IntervalTracker {
+    fn default() -> Self {
+        Self::new(PrincipalNote::Highest)
+    }
+}
+
+impl IntervalTracker {
+    pub fn new(principal: PrincipalNote) -> Self {
+        IntervalTracker {
+            principal,
+            previous: None,
+        }
+    }
+
+    /// Record a generation's keys and return the interval (in semitones) from the
+    /// previous sounding generation. Silent generations are skipped and don't reset
+    /// the contour, so the next note is compared with the last one actually heard.
+    pub fn observe(&mut self, keys: &[usize]) -> Option<i32> {
+        let current = self.principal.select(keys)?;
+        let interval = self.previous.map(|previous| current as i32 - previous as i32);
+        self.previous = Some(current);
+        interval
+    }
+}
+
+/// Tallies how often each of the 88 columns (piano keys) sounded over a run,
+/// showing which notes a board favors
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnActivity {
+    counts: [u64; PIANO_KEY_COUNT],
+}
+
+impl Default for ColumnActivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColumnActivity {
+    pub fn new() -> Self {
+        ColumnActivity {
+            counts: [0; PIANO_KEY_COUNT],
+        }
+    }
+
+    /// Record the columns of one generation's bottom row; anything off the
+    /// keyboard is ignored
+    pub fn observe(&mut self, columns: &[usize]) {
+        for &column in columns {
+            if let Some(count) = self.counts.get_mut(column) {
+                *count += 1;
+            }
+        }
+    }
+
+    pub fn counts(&self) -> [u64; PIANO_KEY_COUNT] {
+        self.counts
+    }
+
+    /// The `n` most-played columns with their counts, busiest first. Ties go
+    /// to the lower column and columns that never sounded are left out.
+    pub fn top(&self, n: usize) -> Vec<(usize, u64)> {
+        let mut played: Vec<(usize, u64)> = self.counts.iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        played.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        played.truncate(n);
+        played
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_sequence() {
+        let mut tracker = IntervalTracker::new(PrincipalNote::Highest);
+
+        // Scripted generations, including a silent one
+        let generations: Vec<Vec<usize>> = vec![
+            vec![40, 48],   // principal 48, nothing to compare with yet
+            vec![30, 52],   // up a major third
+            vec![],         // silence is skipped
+            vec![45],       // down a perfect fourth from 52
+            vec![45, 10],   // unison
+        ];
+
+        let intervals: Vec<Option<i32>> = generations.iter()
+            .map(|keys| tracker.observe(keys))
+            .collect();
+
+        assert_eq!(intervals, vec![None, Some(4), None, Some(-7), Some(0)]);
+    }
+
+    #[test]
+    fn test_lowest_principal_note() {
+        let mut tracker = IntervalTracker::new(PrincipalNote::Lowest);
+
+        assert_eq!(tracker.observe(&[40, 48]), None);
+        assert_eq!(tracker.observe(&[30, 52]), Some(-10));
+        assert_eq!(tracker.observe(&[42]), Some(12));
+    }
+
+    #[test]
+    fn test_column_activity_tallies() {
+        let mut activity = ColumnActivity::new();
+        let bottom_rows: Vec<Vec<usize>> = vec![
+            vec![0, 40, 87],
+            vec![40],
+            vec![],
+            vec![40, 87, 88], // 88 is off the keyboard
+        ];
+        for row in &bottom_rows {
+            activity.observe(row);
+        }
+
+        let counts = activity.counts();
+        assert_eq!(counts[0], 1);
+        assert_eq!(counts[40], 3);
+        assert_eq!(counts[87], 2);
+        assert_eq!(counts.iter().sum::<u64>(), 6);
+
+        assert_eq!(activity.top(2), vec![(40, 3), (87, 2)]);
+        assert_eq!(activity.top(10), vec![(40, 3), (87, 2), (0, 1)]);
+    }
+}
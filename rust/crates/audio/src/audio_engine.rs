@@ -1,28 +1,105 @@
 use std::thread;
 use std::time::Duration;
 use rodio::{Decoder, OutputStream, Sink, Source};
+use rodio::buffer::SamplesBuffer;
+use rodio::source::ChannelVolume;
 use std::io::Cursor;
 use std::collections::HashMap;
-use std::fs::File;
-use log::{info, warn, error, debug};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use log::{info, warn, debug};
 // Keep RepoStructure import as it's used in load_samples() and tests
 use common::RepoStructure;
+use config::{AdsrConfig, Config};
+use crate::dynamics::{edge_key_gain, key_pan, limit_polyphony, pan_gains, velocity_gain, FULL_VELOCITY, KEYBOARD_CENTER};
+use crate::reverb::apply_reverb_interleaved;
+use crate::synth::{generate_piano_wave_with_envelope, SYNTH_NOTE_DURATION_MS, SYNTH_SAMPLE_RATE};
+use crate::notes::{key_to_frequency, key_to_note_name, note_name_to_key};
+use crate::pitch::{semitone_ratio, shift_pitch, tuning_offset, STANDARD_TUNING_HZ};
 
 // We no longer need hardcoded paths since we're using the repo structure utility
 
 pub trait AudioPlayer {
     fn play_piano_keys(&self, keys: &[usize]);
     fn play_chord(&self, keys: &[usize], duration_ms: u64);
+
+    // Play (key, velocity) pairs; players without dynamics ignore the velocities
+    fn play_notes(&self, notes: &[(usize, u8)]) {
+        let keys: Vec<usize> = notes.iter().map(|&(key, _)| key).collect();
+        self.play_piano_keys(&keys);
+    }
+
+    // Pause between generations; recorders move their clock instead of sleeping
+    fn wait(&self, duration_ms: u64) {
+        thread::sleep(Duration::from_millis(duration_ms));
+    }
 }
 
 pub struct AudioEngine {
     _stream: OutputStream,
     sink: Sink,
     sample_cache: HashMap<usize, Vec<u8>>, // Cache for piano samples
+    volume: f32,                 // Master volume (0.0-1.0)
+    edge_key_band: usize,        // Number of keys at each end that get softened
+    edge_key_attenuation: f64,   // How much those keys are softened (0.0-1.0)
+    stereo_width: f32,           // Stereo spread (0.0 = mono)
+    stereo_center: f32,          // Key that pans dead-center
+    pitch_shift: bool,           // Retune samples to the exact key (false = play the nearest sample as-is)
+    max_polyphony: usize,        // Most keys sounded at once (0 = unlimited)
+    sustain: bool,               // Don't wait for notes to finish before returning
+    timing: NoteTiming,          // Note, gap and chord durations
+    tuning_hz: f64,              // Concert pitch (A4) the keys are tuned to
+    reverb_mix: f64,             // Reverb wet/dry balance (0.0 = dry)
+    envelope: Option<AdsrConfig>, // Envelope for synthesized notes (None = per-register curves)
+    shifted_cache: Mutex<HashMap<usize, ShiftedSample>>, // Pitch-shifted or reverberated samples by key
+    chord_detector: ChordDetector,
+    #[cfg(test)]
+    samples_played: std::sync::atomic::AtomicUsize, // play_sample calls, for tests
+}
+
+// A decoded sample retuned (and reverberated) for one key
+struct ShiftedSample {
+    channels: u16,
+    sample_rate: u32,
+    samples: Vec<f32>,
 }
 
 pub struct NullAudioEngine;
 
+// Master volume used when none is configured
+pub const DEFAULT_VOLUME: f32 = 0.6;
+
+// How long the players hold each generation's notes, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoteTiming {
+    pub note_duration_ms: u64,  // Time the row rings after its last note
+    pub gap_ms: u64,            // Stagger between individually played notes
+    pub chord_duration_ms: u64, // How long a detected chord is held
+}
+
+impl Default for NoteTiming {
+    fn default() -> Self {
+        NoteTiming {
+            note_duration_ms: 300,
+            gap_ms: 50,
+            chord_duration_ms: 300,
+        }
+    }
+}
+
+impl NoteTiming {
+    pub fn from_config(config: &Config) -> Self {
+        NoteTiming {
+            note_duration_ms: config.note_duration_ms,
+            gap_ms: config.gap_ms,
+            chord_duration_ms: config.chord_duration_ms,
+        }
+    }
+}
+
 impl Default for AudioEngine {
     fn default() -> Self {
         Self::new()
@@ -31,6 +108,16 @@ impl Default for AudioEngine {
 
 impl AudioEngine {
     pub fn new() -> Self {
+        Self::with_samples(load_sample_data())
+    }
+
+    // An engine playing the samples in `dir` instead of the repository's
+    // static/audio; fails if the directory doesn't exist
+    pub fn with_samples_dir(dir: PathBuf) -> io::Result<Self> {
+        Ok(Self::with_samples(load_sample_data_in(&dir)?))
+    }
+
+    fn with_samples(sample_cache: HashMap<usize, Vec<u8>>) -> Self {
         let (_stream, stream_handle) = OutputStream::try_default().unwrap_or_else(|_| {
             warn!("Warning: Could not initialize audio stream");
             OutputStream::try_default().expect("Failed to create fallback audio stream")
@@ -41,84 +128,151 @@ impl AudioEngine {
             Sink::try_new(&stream_handle).expect("Failed to create fallback audio sink")
         });
         
-        let mut engine = AudioEngine { 
+        let engine = AudioEngine { 
             _stream, 
             sink, 
-            sample_cache: HashMap::new()
+            sample_cache,
+            volume: DEFAULT_VOLUME,
+            edge_key_band: 0,
+            edge_key_attenuation: 0.0,
+            stereo_width: 0.0,
+            stereo_center: KEYBOARD_CENTER,
+            pitch_shift: true,
+            max_polyphony: 0,
+            sustain: false,
+            timing: NoteTiming::default(),
+            tuning_hz: STANDARD_TUNING_HZ,
+            reverb_mix: 0.0,
+            envelope: None,
+            shifted_cache: Mutex::new(HashMap::new()),
+            chord_detector: ChordDetector::default(),
+            #[cfg(test)]
+            samples_played: std::sync::atomic::AtomicUsize::new(0),
         };
         
-        // Load piano samples
-        engine.load_samples();
+        engine.print_coverage_analysis();
         engine
     }
 
-    fn load_samples(&mut self) {
-        // Load available piano samples with comprehensive chromatic coverage
-        // Piano key mapping: A0=0, A#0=1, B0=2, C1=3, C#1=4, D1=5, D#1=6, E1=7, F1=8, F#1=9, G1=10, G#1=11, A1=12...
-        let sample_files = [
-            // Low range (Octave 1 & 2)
-            (9, "piano_a1.wav"),     // A1 (key 9)
-            (21, "piano_a2.wav"),    // A2 (key 21)
-            (24, "piano_c2.wav"),    // C2 (key 24)
-            
-            // Mid-low range (Octave 3) - Better chromatic coverage
-            (36, "piano_c3.wav"),    // C3 (key 36)
-            (38, "piano_d3.wav"),    // D3 (key 38)
-            (41, "piano_f3.wav"),    // F3 (key 41)
-            (43, "piano_g3.wav"),    // G3 (key 43)
-            
-            // Mid range (Octave 4) - Even better coverage
-            (48, "piano_c4_ivory.wav"), // C4 - Middle C (key 48) - Best quality
-            (50, "piano_d4.wav"),    // D4 (key 50)
-            (53, "piano_f4.wav"),    // F4 (key 53)
-            (55, "piano_g4.wav"),    // G4 (key 55)
-            
-            // Alternative samples for comparison/backup
-            (36, "piano_c3_kawai.wav"), // Alternative C3
-            (48, "piano_c4.wav"),    // Alternative C4
-            (48, "piano_c4_kawai.wav"), // Another C4 option
-            
-            // Upper-mid range (Octave 5)
-            (60, "piano_c5.wav"),    // C5 (key 60)
-            
-            // High range (Octave 6 & 7)
-            (72, "piano_c6.wav"),    // C6 (key 72)
-            (84, "piano_c7.wav"),    // C7 (key 84)
-        ];
-        
-        // Get audio samples directory from repository structure
-        let repo = RepoStructure::new();
-        let audio_dir = repo.audio_samples_dir();
-        
-        // Log the audio path being used
-        info!("Loading audio samples from path: {}", audio_dir.display());
-
-        for (key, file_name) in sample_files.iter() {
-            // Construct the full path
-            let full_path = audio_dir.join(file_name);
-            if let Ok(mut file) = File::open(&full_path) {
-                let mut buffer = Vec::new();
-                if std::io::Read::read_to_end(&mut file, &mut buffer).is_ok() {
-                    self.sample_cache.insert(*key, buffer);
-                    let note_name = self.key_to_note_name(*key);
-                    info!("Loaded sample for key {} ({}): {}", key, note_name, full_path.display());
-                } else {
-                    warn!("Failed to read sample file: {}", full_path.display());
-                }
-            } else {
-                warn!("Could not find sample file: {}", full_path.display());
-            }
-        }
-        
-        info!("Loaded {} piano samples covering chromatic range", self.sample_cache.len());
-        self.print_coverage_analysis();
+    // An engine with every audio setting taken from the configuration
+    pub fn from_config(config: &Config) -> Self {
+        let engine = match &config.samples_dir {
+            // Config validation has already checked the directory exists
+            Some(dir) => Self::with_samples_dir(dir.clone()).unwrap_or_else(|e| {
+                warn!("{}; using the repository samples", e);
+                Self::new()
+            }),
+            None => Self::new(),
+        };
+        engine
+            .with_edge_attenuation(config.edge_key_band, config.edge_key_attenuation)
+            .with_stereo(config.effective_stereo_width(), config.stereo_center_key)
+            .with_pitch_shift(config.pitch_shift)
+            .with_max_polyphony(config.max_polyphony)
+            .with_sustain(config.sustain)
+            .with_volume(config.volume)
+            .with_timing(NoteTiming::from_config(config))
+            .with_tuning(config.tuning_hz)
+            .with_reverb(config.reverb_mix)
+            .with_envelope(config.envelope)
+            .with_chord_detector(ChordDetector::new(config.chord_cluster_size, config.chord_cluster_gap))
+    }
+
+    // Master volume, clamped to 0.0-1.0
+    pub fn with_volume(mut self, volume: f64) -> Self {
+        self.volume = volume.clamp(0.0, 1.0) as f32;
+        self
+    }
+
+    // Tune A4 to `tuning_hz` instead of 440 Hz. Samples are retuned to match,
+    // so this only takes effect with pitch shifting on.
+    pub fn with_tuning(mut self, tuning_hz: f64) -> Self {
+        self.tuning_hz = tuning_hz;
+        self
+    }
+
+    // Add a reverb tail to every note; `mix` runs from 0.0 (dry) to 1.0 (all reverb)
+    pub fn with_reverb(mut self, mix: f64) -> Self {
+        self.reverb_mix = mix.clamp(0.0, 1.0);
+        self
+    }
+
+    // Shape notes synthesized for keys without a sample with this envelope
+    // instead of the built-in register curves
+    pub fn with_envelope(mut self, envelope: Option<AdsrConfig>) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    pub fn tuning_hz(&self) -> f64 {
+        self.tuning_hz
+    }
+
+    // Frequency of `key` at the engine's tuning
+    pub fn key_frequency(&self, key: usize) -> f64 {
+        key_to_frequency(key, self.tuning_hz)
+    }
+
+    // Overall gain for one note: master volume, velocity, pitch-shift
+    // compensation and edge-key softening
+    fn playback_gain(&self, key: usize, velocity: u8, volume_compensation: f32) -> f32 {
+        self.volume * velocity_gain(velocity) * volume_compensation
+            * edge_key_gain(key, self.edge_key_band, self.edge_key_attenuation)
+    }
+
+    // Soften the lowest and highest `band` keys by `attenuation` (0.0-1.0)
+    pub fn with_edge_attenuation(mut self, band: usize, attenuation: f64) -> Self {
+        self.edge_key_band = band;
+        self.edge_key_attenuation = attenuation;
+        self
+    }
+
+    // Pan keys across the stereo image; width 0.0 keeps mono output
+    pub fn with_stereo(mut self, width: f64, center_key: Option<usize>) -> Self {
+        self.stereo_width = width as f32;
+        self.stereo_center = center_key.map_or(KEYBOARD_CENTER, |key| key as f32);
+        self
+    }
+
+    // Retune samples to each key's exact pitch (keeping their length), or play
+    // the nearest sample untouched when disabled
+    pub fn with_pitch_shift(mut self, enabled: bool) -> Self {
+        self.pitch_shift = enabled;
+        self
+    }
+
+    // Sound at most `max` keys at once, spread across the range (0 = unlimited)
+    pub fn with_max_polyphony(mut self, max: usize) -> Self {
+        self.max_polyphony = max;
+        self
+    }
+
+    // Hold the sustain pedal: return as soon as a row's notes are queued and
+    // let them ring into the next generation, instead of sleeping through the
+    // note or chord duration
+    pub fn with_sustain(mut self, sustain: bool) -> Self {
+        self.sustain = sustain;
+        self
+    }
+
+    // Use these note, gap and chord durations instead of the defaults
+    pub fn with_timing(mut self, timing: NoteTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    pub fn timing(&self) -> NoteTiming {
+        self.timing
+    }
+
+    // Use custom cluster thresholds for chord detection
+    pub fn with_chord_detector(mut self, chord_detector: ChordDetector) -> Self {
+        self.chord_detector = chord_detector;
+        self
     }
 
     fn key_to_note_name(&self, key: usize) -> String {
-        let note_names = ["A", "A#", "B", "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#"];
-        let octave = key / 12;
-        let note_in_octave = key % 12;
-        format!("{}{}", note_names[note_in_octave], octave)
+        key_to_note_name(key)
     }
 
     fn print_coverage_analysis(&self) {
@@ -154,170 +308,187 @@ impl AudioEngine {
     }
 
     fn get_sample_for_key(&self, key: usize) -> Option<&Vec<u8>> {
-        // Find the closest available sample with intelligent chromatic selection
-        let available_keys: Vec<usize> = self.sample_cache.keys().copied().collect();
-        if available_keys.is_empty() {
-            return None;
+        self.closest_sample_key(key)
+            .and_then(|sample_key| self.sample_cache.get(&sample_key))
+    }
+
+    // Key of the sample used to play `key`
+    fn closest_sample_key(&self, key: usize) -> Option<usize> {
+        nearest_sample_key(self.sample_cache.keys().copied(), key)
+    }
+
+    // The sample for `sample_key` moved by `semitones` to play `key`, with the
+    // reverb added, decoded and processed on first use and cached afterwards
+    fn shifted_sample(&self, key: usize, sample_key: usize, semitones: f32) -> Option<SamplesBuffer<f32>> {
+        let mut cache = self.shifted_cache.lock().unwrap_or_else(|e| e.into_inner());
+        let to_buffer = |shifted: &ShiftedSample| {
+            SamplesBuffer::new(shifted.channels, shifted.sample_rate, shifted.samples.clone())
+        };
+        if let Some(shifted) = cache.get(&key) {
+            return Some(to_buffer(shifted));
         }
 
-        // Advanced sample selection algorithm for better chromatic coverage
-        let closest_key = available_keys.iter()
-            .min_by_key(|&&sample_key| {
-                let distance = (sample_key as i32 - key as i32).abs();
-                
-                // Chromatic optimization: prefer samples that result in better pitch shifts
-                if distance == 0 {
-                    0 // Perfect match
-                } else if distance <= 2 {
-                    distance // Minimal shift penalty (within major second)
-                } else if distance <= 6 {
-                    distance + 1 // Slight penalty for larger shifts (up to tritone)
-                } else if distance <= 12 {
-                    distance * 2 // Higher penalty for shifts over an octave
-                } else {
-                    distance * 3 // Very high penalty for extreme shifts
-                }
-            })
-            .copied()?;
+        let sample_data = self.sample_cache.get(&sample_key)?;
+        let source = Decoder::new(Cursor::new(sample_data.clone())).ok()?;
+        let (channels, sample_rate) = (source.channels(), source.sample_rate());
+        let samples: Vec<f32> = source.convert_samples::<f32>().collect();
+        let mut samples = if semitones != 0.0 {
+            shift_pitch(&samples, channels as usize, semitone_ratio(semitones))
+        } else {
+            samples
+        };
+        if self.reverb_mix > 0.0 {
+            samples = apply_reverb_interleaved(&samples, channels as usize, self.reverb_mix, sample_rate);
+        }
 
-        self.sample_cache.get(&closest_key)
+        let shifted = ShiftedSample {
+            channels,
+            sample_rate,
+            samples,
+        };
+        let buffer = to_buffer(&shifted);
+        cache.insert(key, shifted);
+        Some(buffer)
+    }
+
+    // Apply the gain and stereo placement for `key` and queue the sound
+    fn append_source<S>(&self, source: S, key: usize, gain: f32)
+    where
+        S: Source<Item = f32> + Send + 'static,
+    {
+        let adjusted_source = source.amplify(gain);
+        if self.stereo_width > 0.0 {
+            let pan = key_pan(key, self.stereo_center, self.stereo_width);
+            let (left, right) = pan_gains(pan);
+            self.sink.append(ChannelVolume::new(adjusted_source, vec![left, right]));
+        } else {
+            self.sink.append(adjusted_source);
+        }
     }
 
-    fn play_sample(&self, key: usize) {
-        if let Some(sample_data) = self.get_sample_for_key(key) {
-            let cursor = Cursor::new(sample_data.clone());
-            if let Ok(source) = Decoder::new(cursor) {
-                // Calculate pitch adjustment if needed
-                let closest_sample_key = self.sample_cache.keys()
-                    .min_by_key(|&&sample_key| (sample_key as i32 - key as i32).abs())
-                    .copied()
-                    .unwrap_or(48);
+    // Play one key, scaled by its velocity (FULL_VELOCITY plays it unchanged)
+    fn play_sample(&self, key: usize, velocity: u8) {
+        #[cfg(test)]
+        self.samples_played.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
 
-                let semitone_difference = key as f32 - closest_sample_key as f32;
-                let pitch_ratio = 2.0_f32.powf(semitone_difference / 12.0);
-                
-                // Advanced volume compensation for chromatic intervals
-                let volume_compensation = if semitone_difference > 0.0 {
-                    // Pitching up: reduce volume progressively for higher pitches
-                    let reduction_factor = 1.0 - (semitone_difference * 0.03).min(0.3);
-                    reduction_factor.max(0.6) // Don't reduce below 60% volume
-                } else if semitone_difference < 0.0 {
-                    // Pitching down: increase volume progressively for lower pitches  
-                    let boost_factor = 1.0 + (-semitone_difference * 0.04).min(0.4);
-                    boost_factor.min(1.5) // Don't boost above 150% volume
-                } else {
-                    1.0 // No adjustment for perfect match
-                };
-                
-                // Apply pitch shift, volume compensation, and play
-                let adjusted_source = source
-                    .speed(pitch_ratio)
-                    .amplify(0.6 * volume_compensation);
-                    
-                self.sink.append(adjusted_source);
-                
-                // Debug info
-                if (semitone_difference).abs() > 0.1 {
-                    debug!("Key {}: using sample {} (shift: {:.1} semitones, vol: {:.2})", 
-                        key, closest_sample_key, semitone_difference, volume_compensation);
+        let Some(sample_key) = self.closest_sample_key(key) else {
+            // Only happens when no samples were loaded at all; synthesize the note instead
+            warn!("No sample available for key {}; playing a synthesized note", key);
+            let wave = generate_piano_wave_with_envelope(self.key_frequency(key), SYNTH_NOTE_DURATION_MS, self.envelope.as_ref());
+            self.append_source(SamplesBuffer::new(1, SYNTH_SAMPLE_RATE, wave), key, self.playback_gain(key, velocity, 1.0));
+            return;
+        };
+
+        // Samples are recorded at standard pitch, so other tunings move every key
+        let semitone_difference = key as f32 - sample_key as f32 + tuning_offset(self.tuning_hz);
+
+        let shifted = self.pitch_shift && semitone_difference != 0.0;
+        if !shifted && self.reverb_mix == 0.0 {
+            // Play the nearest sample exactly as recorded
+            if let Some(sample_data) = self.get_sample_for_key(key) {
+                if let Ok(source) = Decoder::new(Cursor::new(sample_data.clone())) {
+                    self.append_source(source.convert_samples::<f32>(), key, self.playback_gain(key, velocity, 1.0));
                 }
             }
+            return;
+        }
+
+        let (semitones, volume_compensation) = if shifted {
+            (semitone_difference, shift_compensation(semitone_difference))
         } else {
-            // This should never happen with our comprehensive sample coverage
-            error!("Critical error: No sample available for key {} - this indicates a problem with sample loading", key);
+            (0.0, 1.0)
+        };
+
+        // Retune without changing the note's length, then apply volume compensation and play
+        if let Some(shifted) = self.shifted_sample(key, sample_key, semitones) {
+            self.append_source(shifted, key, self.playback_gain(key, velocity, volume_compensation));
+            debug!("Key {}: using sample {} (shift: {:.1} semitones, vol: {:.2})",
+                key, sample_key, semitone_difference, volume_compensation);
         }
     }
 
     // Made public to be used by piano_player
     pub fn is_chord_pattern(&self, keys: &[usize]) -> bool {
-        if keys.len() < 3 {
-            return false;
-        }
-        
-        // Check for common chord patterns
-        let mut sorted_keys: Vec<usize> = keys.to_vec();
-        sorted_keys.sort();
-        
-        // Check for triads (3 notes)
-        if sorted_keys.len() >= 3 {
-            for i in 0..=sorted_keys.len()-3 {
-                let root = sorted_keys[i];
-                let third = sorted_keys[i+1];
-                let fifth = sorted_keys[i+2];
-                
-                let interval1 = third.saturating_sub(root);
-                let interval2 = fifth.saturating_sub(root);
-                
-                // Major chord: 4 and 7 semitones
-                // Minor chord: 3 and 7 semitones
-                // Diminished chord: 3 and 6 semitones
-                // Augmented chord: 4 and 8 semitones
-                if (interval1 == 3 || interval1 == 4) && 
-                   (6..=8).contains(&interval2) {
-                    return true;
-                }
-            }
-        }
-        
-        // Check for dense clusters (many consecutive notes)
-        if sorted_keys.len() >= 5 {
-            let mut consecutive_count = 1;
-            for i in 1..sorted_keys.len() {
-                if sorted_keys[i] - sorted_keys[i-1] <= 2 {
-                    consecutive_count += 1;
-                    if consecutive_count >= 5 {
-                        return true;
-                    }
-                } else {
-                    consecutive_count = 1;
-                }
-            }
-        }
-        
-        false
+        self.chord_detector.is_chord_pattern(keys)
+    }
+
+    // Which kind of chord the keys form, if any
+    pub fn classify_chord(&self, keys: &[usize]) -> Option<ChordQuality> {
+        self.chord_detector.classify(keys)
+    }
+
+    pub fn chord_detector(&self) -> ChordDetector {
+        self.chord_detector
     }
 }
 
 // Implement AudioPlayer trait for AudioEngine
 impl AudioPlayer for AudioEngine {
     fn play_piano_keys(&self, keys: &[usize]) {
-        if keys.is_empty() {
+        self.play_notes(&with_full_velocity(keys));
+    }
+
+    fn play_chord(&self, keys: &[usize], duration_ms: u64) {
+        self.play_chord_notes(&with_full_velocity(keys), duration_ms);
+    }
+
+    fn play_notes(&self, notes: &[(usize, u8)]) {
+        if notes.is_empty() {
             return;
         }
 
-        // Detect chord patterns and play accordingly
-        if self.is_chord_pattern(keys) {
-            self.play_chord(keys, 300);
+        // Detect chord patterns on the full row, then thin out dense rows
+        let keys: Vec<usize> = notes.iter().map(|&(key, _)| key).collect();
+        let chord = self.classify_chord(&keys);
+        let notes = &limit_polyphony(notes, self.max_polyphony);
+        if notes.len() < keys.len() {
+            debug!("Polyphony cap of {}: dropped {} of {} keys", self.max_polyphony, keys.len() - notes.len(), keys.len());
+        }
+
+        if let Some(quality) = chord {
+            debug!("Chord detected: {:?} {:?}", quality, keys);
+            self.play_chord_notes(notes, self.timing.chord_duration_ms);
         } else {
             // Play individual keys with slight delay using samples
-            for &key in keys {
-                self.play_sample(key);
-                thread::sleep(Duration::from_millis(50));
+            for &(key, velocity) in notes {
+                self.play_sample(key, velocity);
+                thread::sleep(Duration::from_millis(self.timing.gap_ms));
             }
         }
         
-        // Wait for audio to finish
-        thread::sleep(Duration::from_millis(300));
+        // Wait for audio to finish, unless it is meant to ring on
+        if !self.sustain {
+            thread::sleep(Duration::from_millis(self.timing.note_duration_ms));
+        }
     }
+}
 
-    fn play_chord(&self, keys: &[usize], duration_ms: u64) {
-        if keys.is_empty() {
+impl AudioEngine {
+    fn play_chord_notes(&self, notes: &[(usize, u8)], duration_ms: u64) {
+        if notes.is_empty() {
             return;
         }
 
         // Play chord using samples with slight timing offset for natural feel
-        for (i, &key) in keys.iter().enumerate() {
+        for (i, &(key, velocity)) in notes.iter().enumerate() {
             // Add slight delay between notes for natural chord attack
             if i > 0 {
                 thread::sleep(Duration::from_millis(10));
             }
-            self.play_sample(key);
+            self.play_sample(key, velocity);
         }
         
-        thread::sleep(Duration::from_millis(duration_ms));
+        if !self.sustain {
+            thread::sleep(Duration::from_millis(duration_ms));
+        }
     }
 }
 
+// Keys played without dynamics
+pub(crate) fn with_full_velocity(keys: &[usize]) -> Vec<(usize, u8)> {
+    keys.iter().map(|&key| (key, FULL_VELOCITY)).collect()
+}
+
 // Additional methods for AudioEngine are implemented in this block
 // This ensures the trait implementation remains clean
 impl AudioEngine {
@@ -330,7 +501,7 @@ impl AudioEngine {
         }
 
         for &key in keys {
-            self.play_sample(key);
+            self.play_sample(key, FULL_VELOCITY);
             thread::sleep(Duration::from_millis(note_duration_ms));
             
             if gap_ms > 0 {
@@ -340,6 +511,219 @@ impl AudioEngine {
     }
 }
 
+// Sample file extensions the loader picks up
+const SAMPLE_EXTENSIONS: [&str; 3] = ["wav", "ogg", "flac"];
+
+// Read every available piano sample, keyed by the piano key it was recorded at
+pub(crate) fn load_sample_data() -> HashMap<usize, Vec<u8>> {
+    // Get audio samples directory from repository structure
+    let repo = RepoStructure::new();
+    load_sample_data_from(&repo.audio_samples_dir())
+}
+
+// Read the samples in a directory given explicitly, where a missing
+// directory is a mistake rather than a checkout without samples
+pub(crate) fn load_sample_data_in(audio_dir: &Path) -> io::Result<HashMap<usize, Vec<u8>>> {
+    if !audio_dir.is_dir() {
+        return Err(io::Error::new(io::ErrorKind::NotFound,
+            format!("Audio samples directory {} does not exist", audio_dir.display())));
+    }
+    Ok(load_sample_data_from(audio_dir))
+}
+
+// Load every piano_<note><octave>[_variant].{wav,ogg,flac} file in `audio_dir`.
+// Files are taken in name order and the first one for a key wins, so
+// piano_c4.wav is preferred over piano_c4_kawai.wav.
+pub(crate) fn load_sample_data_from(audio_dir: &Path) -> HashMap<usize, Vec<u8>> {
+    // Log the audio path being used
+    info!("Loading audio samples from path: {}", audio_dir.display());
+
+    let mut paths: Vec<PathBuf> = match fs::read_dir(audio_dir) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect(),
+        Err(e) => {
+            warn!("Could not read sample directory {}: {}", audio_dir.display(), e);
+            return HashMap::new();
+        }
+    };
+    paths.sort();
+
+    let mut samples = HashMap::new();
+    for full_path in paths {
+        let Some(file_name) = full_path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let is_sample = file_name.starts_with("piano_")
+            && full_path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| SAMPLE_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+        if !is_sample {
+            continue;
+        }
+
+        let Some(key) = sample_key_from_file_name(file_name) else {
+            warn!("Skipping sample with an unrecognized note name: {}", full_path.display());
+            continue;
+        };
+        if samples.contains_key(&key) {
+            debug!("Key {} already has a sample; skipping {}", key, full_path.display());
+            continue;
+        }
+
+        match fs::read(&full_path) {
+            Ok(buffer) => {
+                samples.insert(key, buffer);
+                info!("Loaded sample for key {} ({}): {}", key, key_to_note_name(key), full_path.display());
+            }
+            Err(e) => warn!("Failed to read sample file {}: {}", full_path.display(), e),
+        }
+    }
+    
+    info!("Loaded {} piano samples covering chromatic range", samples.len());
+    samples
+}
+
+// Piano key for a sample file name such as piano_c4.wav, piano_f#3_soft.ogg
+// or piano_eb5.flac. The note is parsed like any other note name, so
+// piano_c4 is middle C, key 39.
+pub(crate) fn sample_key_from_file_name(file_name: &str) -> Option<usize> {
+    let stem = file_name.strip_prefix("piano_")?.split('.').next()?;
+    note_name_to_key(stem.split('_').next()?)
+}
+
+// The sample key to play `key` from, preferring small pitch shifts
+pub(crate) fn nearest_sample_key(available_keys: impl Iterator<Item = usize>, key: usize) -> Option<usize> {
+    // Advanced sample selection algorithm for better chromatic coverage.
+    // Ties go to the lower sample so the choice doesn't depend on the
+    // (hash map) order the keys arrive in.
+    available_keys
+        .min_by_key(|&sample_key| {
+            let distance = (sample_key as i32 - key as i32).abs();
+
+            // Chromatic optimization: prefer samples that result in better pitch shifts
+            let penalty = if distance == 0 {
+                0 // Perfect match
+            } else if distance <= 2 {
+                distance // Minimal shift penalty (within major second)
+            } else if distance <= 6 {
+                distance + 1 // Slight penalty for larger shifts (up to tritone)
+            } else if distance <= 12 {
+                distance * 2 // Higher penalty for shifts over an octave
+            } else {
+                distance * 3 // Very high penalty for extreme shifts
+            };
+            (penalty, sample_key)
+        })
+}
+
+// Advanced volume compensation for chromatic intervals
+pub(crate) fn shift_compensation(semitone_difference: f32) -> f32 {
+    if semitone_difference > 0.0 {
+        // Pitching up: reduce volume progressively for higher pitches
+        let reduction_factor = 1.0 - (semitone_difference * 0.03).min(0.3);
+        reduction_factor.max(0.6) // Don't reduce below 60% volume
+    } else {
+        // Pitching down: increase volume progressively for lower pitches
+        let boost_factor = 1.0 + (-semitone_difference * 0.04).min(0.4);
+        boost_factor.min(1.5) // Don't boost above 150% volume
+    }
+}
+
+// Kind of chord recognized by the ChordDetector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChordQuality {
+    Major,
+    Minor,
+    Diminished,
+    Augmented,
+    Cluster, // Dense run of near-adjacent keys rather than a triad
+}
+
+// Chord detection shared by the engine, the piano front-end and the timeline.
+// Recognizes triads plus dense clusters of near-adjacent keys; the cluster
+// thresholds are configurable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChordDetector {
+    pub cluster_size: usize, // Minimum number of keys in a cluster
+    pub cluster_gap: usize,  // Maximum distance between neighbouring cluster keys
+}
+
+impl Default for ChordDetector {
+    fn default() -> Self {
+        ChordDetector {
+            cluster_size: 5,
+            cluster_gap: 2,
+        }
+    }
+}
+
+impl ChordDetector {
+    pub fn new(cluster_size: usize, cluster_gap: usize) -> Self {
+        ChordDetector { cluster_size, cluster_gap }
+    }
+
+    pub fn is_chord_pattern(&self, keys: &[usize]) -> bool {
+        self.classify(keys).is_some()
+    }
+
+    // What kind of chord the keys form, if any. The lowest matching triad wins;
+    // dense clusters are only reported when there is no triad.
+    pub fn classify(&self, keys: &[usize]) -> Option<ChordQuality> {
+        if keys.len() < 3 {
+            return None;
+        }
+        
+        // Check for common chord patterns
+        let mut sorted_keys: Vec<usize> = keys.to_vec();
+        sorted_keys.sort();
+        
+        // Check for triads (3 notes)
+        if sorted_keys.len() >= 3 {
+            for i in 0..=sorted_keys.len()-3 {
+                let root = sorted_keys[i];
+                let third = sorted_keys[i+1];
+                let fifth = sorted_keys[i+2];
+                
+                let interval1 = third.saturating_sub(root);
+                let interval2 = fifth.saturating_sub(root);
+                
+                // Major chord: 4 and 7 semitones
+                // Minor chord: 3 and 7 semitones
+                // Diminished chord: 3 and 6 semitones
+                // Augmented chord: 4 and 8 semitones
+                // 3 and 8 is a major triad in first inversion
+                let quality = match (interval1, interval2) {
+                    (4, 7) | (3, 8) => Some(ChordQuality::Major),
+                    (3, 7) => Some(ChordQuality::Minor),
+                    (3, 6) => Some(ChordQuality::Diminished),
+                    (4, 8) => Some(ChordQuality::Augmented),
+                    _ => None,
+                };
+                if quality.is_some() {
+                    return quality;
+                }
+            }
+        }
+        
+        // Check for dense clusters (many consecutive notes)
+        if sorted_keys.len() >= self.cluster_size {
+            let mut consecutive_count = 1;
+            for i in 1..sorted_keys.len() {
+                if sorted_keys[i] - sorted_keys[i-1] <= self.cluster_gap {
+                    consecutive_count += 1;
+                    if consecutive_count >= self.cluster_size {
+                        return Some(ChordQuality::Cluster);
+                    }
+                } else {
+                    consecutive_count = 1;
+                }
+            }
+        }
+        
+        None
+    }
+}
+
 impl Default for NullAudioEngine {
     fn default() -> Self {
         Self::new()
@@ -377,6 +761,14 @@ mod tests {
         engine.play_chord(&[48, 52, 55], 500); // C Major
     }
 
+    #[test]
+    fn test_nearest_sample_ties_pick_the_lower_key() {
+        // Key 42 is as far from 40 as from 44, whichever order they arrive in
+        assert_eq!(nearest_sample_key([40, 44].into_iter(), 42), Some(40));
+        assert_eq!(nearest_sample_key([44, 40].into_iter(), 42), Some(40));
+        assert_eq!(nearest_sample_key([44, 40].into_iter(), 43), Some(44));
+    }
+
     #[test]
     fn test_null_audio_engine() {
         let engine = NullAudioEngine::new();
@@ -411,6 +803,64 @@ mod tests {
         println!("Successfully tested sample selection with {} samples", engine.sample_cache.len());
     }
 
+    #[test]
+    fn test_samples_are_found_by_file_name() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "piano_c4.wav", "piano_c4_kawai.wav", "piano_a2.ogg", "piano_f#3.flac",
+            "piano_eb5.ogg", "piano_x9.wav", "piano_c.wav", "piano_c3.mp3", "notes.txt",
+        ] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+
+        let samples = load_sample_data_from(dir.path());
+        let mut keys: Vec<usize> = samples.keys().copied().collect();
+        keys.sort();
+        assert_eq!(keys, vec![24, 33, 39, 54]);
+
+        // The plain file wins over the variant for the same note
+        assert_eq!(samples[&39], b"piano_c4.wav");
+    }
+
+    #[test]
+    fn test_engine_loads_an_explicit_samples_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["piano_a2.wav", "piano_c4.wav", "piano_c6.ogg"] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+
+        let engine = AudioEngine::with_samples_dir(dir.path().to_path_buf()).unwrap();
+        let mut keys: Vec<usize> = engine.sample_cache.keys().copied().collect();
+        keys.sort();
+        assert_eq!(keys, vec![24, 39, 63]);
+        assert_eq!(engine.get_sample_for_key(41), Some(&b"piano_c4.wav".to_vec()));
+
+        let missing = dir.path().join("no-samples-here");
+        let error = AudioEngine::with_samples_dir(missing.clone()).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().contains(&missing.display().to_string()), "{}", error);
+    }
+
+    #[test]
+    fn test_sample_file_names_use_scientific_pitch() {
+        for (name, key) in [
+            ("piano_a1.wav", 12), ("piano_a2.wav", 24), ("piano_c2.wav", 15),
+            ("piano_c3.wav", 27), ("piano_d3.wav", 29), ("piano_f3.wav", 32),
+            ("piano_g3.wav", 34), ("piano_c4_ivory.wav", 39), ("piano_d4.wav", 41),
+            ("piano_f4.wav", 44), ("piano_g4.wav", 46), ("piano_c5.wav", 51),
+            ("piano_c6.wav", 63), ("piano_c7.wav", 75), ("piano_C8.WAV", 87),
+        ] {
+            assert_eq!(sample_key_from_file_name(name), Some(key), "{}", name);
+            // Same key as the note name on its own
+            let note = name.trim_start_matches("piano_").split(['_', '.']).next().unwrap();
+            assert_eq!(note_name_to_key(note), Some(key), "{}", name);
+        }
+        assert_eq!(sample_key_from_file_name("piano_cs4.wav"), Some(40));
+        assert_eq!(sample_key_from_file_name("piano_eb5.flac"), Some(54));
+        assert_eq!(sample_key_from_file_name("piano_c9.wav"), None);
+        assert_eq!(sample_key_from_file_name("piano_h4.wav"), None);
+    }
+
     #[test]
     fn test_key_to_note_name_conversion() {
         let engine = AudioEngine::new();
@@ -418,7 +868,8 @@ mod tests {
         // Test specific known conversions
         assert_eq!(engine.key_to_note_name(0), "A0");
         assert_eq!(engine.key_to_note_name(48), "A4"); // Key 48 should be A4
-        assert_eq!(engine.key_to_note_name(87), "C7");  // Key 87 should be C7
+        assert_eq!(engine.key_to_note_name(39), "C4"); // Middle C
+        assert_eq!(engine.key_to_note_name(87), "C8");  // Key 87 should be C8
         
         // Test that all keys produce valid note names
         for key in 0..88 {
@@ -443,4 +894,136 @@ mod tests {
         let dense_cluster = vec![48, 49, 50, 51, 52]; // 5 consecutive semitones
         assert!(engine.is_chord_pattern(&dense_cluster));
     }
+
+    #[test]
+    fn test_chord_quality_classification() {
+        let engine = AudioEngine::new();
+
+        // Triads built on C3 (key 27)
+        assert_eq!(engine.classify_chord(&[27, 31, 34]), Some(ChordQuality::Major));
+        assert_eq!(engine.classify_chord(&[27, 30, 34]), Some(ChordQuality::Minor));
+        assert_eq!(engine.classify_chord(&[27, 30, 33]), Some(ChordQuality::Diminished));
+        assert_eq!(engine.classify_chord(&[27, 31, 35]), Some(ChordQuality::Augmented));
+
+        // Key order doesn't matter
+        assert_eq!(engine.classify_chord(&[34, 27, 31]), Some(ChordQuality::Major));
+
+        // A major third with a tritone is no major triad
+        assert_ne!(engine.classify_chord(&[27, 31, 33]), Some(ChordQuality::Major));
+
+        assert_eq!(engine.classify_chord(&[48, 49, 50, 51, 52]), Some(ChordQuality::Cluster));
+        assert_eq!(engine.classify_chord(&[27, 28]), None);
+        assert_eq!(engine.classify_chord(&[27, 29, 40]), None);
+    }
+
+    #[test]
+    fn test_engine_without_samples_synthesizes_notes() {
+        // A fresh checkout without static/audio still makes sound
+        let dir = tempfile::tempdir().unwrap();
+        let engine = AudioEngine::with_samples_dir(dir.path().to_path_buf()).unwrap()
+            .with_timing(NoteTiming { note_duration_ms: 0, gap_ms: 0, chord_duration_ms: 0 });
+        assert!(engine.sample_cache.is_empty());
+        assert_eq!(engine.closest_sample_key(48), None);
+
+        engine.play_piano_keys(&[48]);
+        engine.play_chord(&[27, 31, 34], 0);
+        assert_eq!(engine.samples_played.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_sustain_skips_the_note_tail() {
+        // Default timing: 50ms between notes and a 300ms tail after the row
+        let sustained = AudioEngine::new().with_sustain(true);
+        let started = std::time::Instant::now();
+        sustained.play_piano_keys(&[40]);
+        sustained.play_chord(&[27, 31, 34], 1000);
+        assert!(started.elapsed() < Duration::from_millis(250), "took {:?}", started.elapsed());
+        assert_eq!(sustained.samples_played.load(std::sync::atomic::Ordering::SeqCst), 4);
+
+        let gated = AudioEngine::new();
+        let started = std::time::Instant::now();
+        gated.play_piano_keys(&[40]);
+        assert!(started.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_polyphony_cap_limits_samples_played() {
+        let engine = AudioEngine::new().with_max_polyphony(8);
+        let keys: Vec<usize> = (20..60).collect();
+        engine.play_piano_keys(&keys);
+        assert_eq!(engine.samples_played.load(std::sync::atomic::Ordering::SeqCst), 8);
+
+        // Rows within the cap are played in full
+        let unlimited = AudioEngine::new();
+        unlimited.play_piano_keys(&[20, 40, 60]);
+        assert_eq!(unlimited.samples_played.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_engine_uses_configured_durations() {
+        // Without a configuration the engine keeps its original timing
+        assert_eq!(AudioEngine::new().timing(), NoteTiming::default());
+
+        let config = Config {
+            note_duration_ms: 180,
+            gap_ms: 25,
+            chord_duration_ms: 450,
+            ..Config::default()
+        };
+        let timing = AudioEngine::from_config(&config).timing();
+        assert_eq!(timing.note_duration_ms, 180);
+        assert_eq!(timing.gap_ms, 25);
+        assert_eq!(timing.chord_duration_ms, 450);
+    }
+
+    #[test]
+    fn test_tuning_reference_moves_key_frequency() {
+        // A4 is 440 Hz unless configured otherwise
+        let engine = AudioEngine::new();
+        assert_eq!(engine.tuning_hz(), 440.0);
+        assert_eq!(engine.key_frequency(48), 440.0);
+
+        let config = Config { tuning_hz: 432.0, ..Config::default() };
+        let retuned = AudioEngine::from_config(&config);
+        assert_eq!(retuned.key_frequency(48), 432.0);
+        assert!(retuned.key_frequency(60) < engine.key_frequency(60));
+    }
+
+    #[test]
+    fn test_configured_volume_scales_gain() {
+        // Unconfigured engines keep the original 0.6
+        assert!((AudioEngine::new().playback_gain(48, FULL_VELOCITY, 1.0) - 0.6).abs() < 1e-6);
+
+        let muted = AudioEngine::new().with_volume(0.0);
+        assert_eq!(muted.playback_gain(48, FULL_VELOCITY, 1.0), 0.0);
+        assert_eq!(muted.playback_gain(48, FULL_VELOCITY, 1.5), 0.0);
+
+        // Out-of-range volumes are clamped
+        let loud = AudioEngine::new().with_volume(3.0);
+        assert!((loud.playback_gain(48, FULL_VELOCITY, 1.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_velocity_scales_gain() {
+        let engine = AudioEngine::new().with_volume(1.0);
+        let soft = engine.playback_gain(48, 40, 1.0);
+        let loud = engine.playback_gain(48, 100, 1.0);
+        assert!(soft < loud);
+        assert!((loud - 100.0 / 127.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_configurable_cluster_threshold() {
+        let run = [40, 41, 42]; // Three-note chromatic run, not a triad
+
+        // Default thresholds (size 5, gap 2) treat it as individual notes
+        assert!(!ChordDetector::default().is_chord_pattern(&run));
+
+        // Lowering the cluster size makes it a chord
+        assert!(ChordDetector::new(3, 2).is_chord_pattern(&run));
+
+        // A tighter gap rejects runs spaced by whole tones
+        assert!(ChordDetector::new(3, 2).is_chord_pattern(&[40, 42, 44]));
+        assert!(!ChordDetector::new(3, 1).is_chord_pattern(&[40, 42, 44]));
+    }
 }
@@ -0,0 +1,150 @@
+// Dynamics helpers for Conway's Steinway
+// Pure gain/velocity shaping applied before samples reach the sink
+
+use crate::key_mapping::PIANO_KEY_COUNT;
+
+/// Gain for a key given the edge band width (in keys at each end of the keyboard)
+/// and the attenuation amount (0.0 = unchanged, 1.0 = silent). Keys inside the
+/// band get the configured attenuation; everything else plays at full gain.
+pub fn edge_key_gain(key: usize, band: usize, attenuation: f64) -> f32 {
+    let in_low_band = key < band;
+    let in_high_band = key + band >= PIANO_KEY_COUNT;
+
+    if in_low_band || in_high_band {
+        (1.0 - attenuation.clamp(0.0, 1.0)) as f32
+    } else {
+        1.0
+    }
+}
+
+// Velocity of a note played without dynamics
+pub const FULL_VELOCITY: u8 = 127;
+
+/// Amplitude scale for a note velocity (1-127, like MIDI); FULL_VELOCITY is unchanged
+pub fn velocity_gain(velocity: u8) -> f32 {
+    velocity.min(FULL_VELOCITY) as f32 / FULL_VELOCITY as f32
+}
+
+/// At most `max` of the notes, spread evenly from the lowest key to the
+/// highest so a dense row keeps its overall shape. 0 means no limit.
+pub fn limit_polyphony(notes: &[(usize, u8)], max: usize) -> Vec<(usize, u8)> {
+    if max == 0 || notes.len() <= max {
+        return notes.to_vec();
+    }
+
+    let mut sorted = notes.to_vec();
+    sorted.sort_by_key(|&(key, _)| key);
+    if max == 1 {
+        return vec![sorted[sorted.len() / 2]];
+    }
+
+    let last = sorted.len() - 1;
+    (0..max)
+        .map(|i| sorted[(i * last + (max - 1) / 2) / (max - 1)])
+        .collect()
+}
+
+// Geometric middle of the keyboard, used when no center key is configured
+pub const KEYBOARD_CENTER: f32 = (PIANO_KEY_COUNT - 1) as f32 / 2.0;
+
+/// Stereo position for a key, from -1.0 (hard left) to 1.0 (hard right).
+/// `center_key` pans dead-center; each side of it is spread evenly so the
+/// lowest and highest keys reach the full `width` (0.0 = mono, 1.0 = full).
+pub fn key_pan(key: usize, center_key: f32, width: f32) -> f32 {
+    let offset = key as f32 - center_key;
+    let span = if offset < 0.0 {
+        center_key
+    } else {
+        (PIANO_KEY_COUNT - 1) as f32 - center_key
+    };
+    if span <= 0.0 {
+        return 0.0;
+    }
+
+    (offset / span).clamp(-1.0, 1.0) * width.clamp(0.0, 1.0)
+}
+
+/// Constant-power (left, right) gains for a pan position
+pub fn pan_gains(pan: f32) -> (f32, f32) {
+    let angle = (pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+    (angle.cos(), angle.sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_band_is_attenuated() {
+        let band = 3;
+        let attenuation = 0.4;
+
+        // Lowest and highest three keys are softened
+        for key in [0, 1, 2, 85, 86, 87] {
+            assert!((edge_key_gain(key, band, attenuation) - 0.6).abs() < 1e-6,
+                    "Key {} should be attenuated", key);
+        }
+
+        // Everything in between is untouched
+        for key in 3..85 {
+            assert_eq!(edge_key_gain(key, band, attenuation), 1.0,
+                       "Key {} should not be attenuated", key);
+        }
+    }
+
+    #[test]
+    fn test_no_attenuation_by_default() {
+        assert_eq!(edge_key_gain(0, 3, 0.0), 1.0);
+        assert_eq!(edge_key_gain(87, 0, 0.5), 1.0); // Empty band
+        assert_eq!(edge_key_gain(0, 3, 2.0), 0.0); // Amount is capped
+    }
+
+    #[test]
+    fn test_zero_width_is_mono() {
+        for key in 0..PIANO_KEY_COUNT {
+            assert_eq!(key_pan(key, KEYBOARD_CENTER, 0.0), 0.0);
+            assert_eq!(key_pan(key, 20.0, 0.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_center_key_pans_dead_center() {
+        for width in [0.0, 0.25, 0.5, 1.0] {
+            assert_eq!(key_pan(30, 30.0, width), 0.0);
+            assert_eq!(key_pan(0, 0.0, width), 0.0);
+        }
+
+        // The ends of the keyboard reach the full width on either side
+        assert_eq!(key_pan(0, 30.0, 1.0), -1.0);
+        assert_eq!(key_pan(87, 30.0, 0.5), 0.5);
+
+        let (left, right) = pan_gains(0.0);
+        assert!((left - right).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_limit_polyphony_spreads_across_range() {
+        let notes: Vec<(usize, u8)> = (10..50).map(|key| (key, FULL_VELOCITY)).collect();
+
+        let limited = limit_polyphony(&notes, 8);
+        let keys: Vec<usize> = limited.iter().map(|&(key, _)| key).collect();
+        assert_eq!(keys.len(), 8);
+        // Both ends survive and the rest are evenly spaced
+        assert_eq!(keys, vec![10, 16, 21, 27, 32, 38, 43, 49]);
+
+        // 0 and caps above the note count leave the row alone
+        assert_eq!(limit_polyphony(&notes, 0), notes);
+        assert_eq!(limit_polyphony(&notes[..5], 8), notes[..5].to_vec());
+        assert_eq!(limit_polyphony(&notes, 1), vec![(30, FULL_VELOCITY)]);
+    }
+
+    #[test]
+    fn test_keyboard_ends_pan_to_opposite_sides() {
+        let (low_left, low_right) = pan_gains(key_pan(0, KEYBOARD_CENTER, 1.0));
+        let (high_left, high_right) = pan_gains(key_pan(87, KEYBOARD_CENTER, 1.0));
+
+        // Key 0 is hard left and key 87 hard right
+        assert!((low_left - 1.0).abs() < 1e-6 && low_right.abs() < 1e-6);
+        assert!(high_left.abs() < 1e-6 && (high_right - 1.0).abs() < 1e-6);
+    }
+}
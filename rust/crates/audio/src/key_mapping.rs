@@ -0,0 +1,85 @@
+// Key mapping helpers for Conway's Steinway
+// Every transform that moves keys around (transpose, offset, output mapping)
+// goes through here so out-of-range keys are handled the same way everywhere
+
+use config::KeyRangePolicy;
+
+/// Number of keys on the piano (keys are numbered 0..=87)
+pub const PIANO_KEY_COUNT: usize = 88;
+
+/// Bring a possibly out-of-range key back onto the keyboard according to the policy.
+/// Returns None when the key should be discarded.
+pub fn apply_range_policy(key: i32, policy: KeyRangePolicy) -> Option<usize> {
+    let key_count = PIANO_KEY_COUNT as i32;
+    if (0..key_count).contains(&key) {
+        return Some(key as usize);
+    }
+
+    match policy {
+        KeyRangePolicy::Drop => None,
+        KeyRangePolicy::Clamp => Some(key.clamp(0, key_count - 1) as usize),
+        KeyRangePolicy::Wrap => Some(key.rem_euclid(key_count) as usize),
+    }
+}
+
+/// Remap every key with the given function, applying the out-of-range policy.
+/// The result is sorted and free of duplicates, since clamping or wrapping can
+/// fold several keys onto the same one.
+pub fn remap_keys<F>(keys: &[usize], policy: KeyRangePolicy, mapping: F) -> Vec<usize>
+where
+    F: Fn(usize) -> i32,
+{
+    let mut mapped: Vec<usize> = keys.iter()
+        .filter_map(|&key| apply_range_policy(mapping(key), policy))
+        .collect();
+    mapped.sort_unstable();
+    mapped.dedup();
+    mapped
+}
+
+/// Shift every key by a number of semitones (used for both transpose and offset)
+pub fn transpose_keys(keys: &[usize], semitones: i32, policy: KeyRangePolicy) -> Vec<usize> {
+    remap_keys(keys, policy, |key| key as i32 + semitones)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_policy() {
+        assert_eq!(apply_range_policy(-3, KeyRangePolicy::Drop), None);
+        assert_eq!(apply_range_policy(90, KeyRangePolicy::Drop), None);
+        assert_eq!(apply_range_policy(40, KeyRangePolicy::Drop), Some(40));
+
+        assert_eq!(transpose_keys(&[0, 40, 87], 2, KeyRangePolicy::Drop), vec![2, 42]);
+        assert_eq!(transpose_keys(&[0, 40, 87], -2, KeyRangePolicy::Drop), vec![38, 85]);
+    }
+
+    #[test]
+    fn test_clamp_policy() {
+        assert_eq!(apply_range_policy(-3, KeyRangePolicy::Clamp), Some(0));
+        assert_eq!(apply_range_policy(90, KeyRangePolicy::Clamp), Some(87));
+
+        // Keys pinned to the same end collapse into one
+        assert_eq!(transpose_keys(&[85, 86, 87], 5, KeyRangePolicy::Clamp), vec![87]);
+        assert_eq!(transpose_keys(&[0, 1, 40], -5, KeyRangePolicy::Clamp), vec![0, 35]);
+    }
+
+    #[test]
+    fn test_wrap_policy() {
+        assert_eq!(apply_range_policy(-1, KeyRangePolicy::Wrap), Some(87));
+        assert_eq!(apply_range_policy(-3, KeyRangePolicy::Wrap), Some(85));
+        assert_eq!(apply_range_policy(88, KeyRangePolicy::Wrap), Some(0));
+        assert_eq!(apply_range_policy(90, KeyRangePolicy::Wrap), Some(2));
+
+        assert_eq!(transpose_keys(&[1, 86], 3, KeyRangePolicy::Wrap), vec![1, 4]);
+    }
+
+    #[test]
+    fn test_remap_with_custom_mapping() {
+        // Mirror the keyboard: low keys become high keys
+        let mirrored = remap_keys(&[0, 10, 87], KeyRangePolicy::Drop, |key| 87 - key as i32);
+        assert_eq!(mirrored, vec![0, 77, 87]);
+    }
+}
@@ -1,9 +1,30 @@
 // Audio module library for Conway's Steinway
 // Provides piano audio synthesis and playback functionality
 
+pub mod analysis;
 pub mod audio_engine;
+pub mod dynamics;
+pub mod key_mapping;
+pub mod midi;
+pub mod notes;
+pub mod osc;
 pub mod piano_player;
+pub mod pitch;
+pub mod preview;
+pub mod recorder;
+pub mod reverb;
+pub mod scale;
+pub mod streaming;
+pub mod synth;
+pub mod timeline;
 
 // Re-export commonly used types for convenience
-pub use audio_engine::{AudioEngine, NullAudioEngine, AudioPlayer};
+pub use analysis::{ColumnActivity, IntervalTracker, PrincipalNote};
+pub use audio_engine::{AudioEngine, NullAudioEngine, AudioPlayer, ChordDetector, ChordQuality, NoteTiming};
+pub use midi::MidiWriter;
+pub use notes::{key_to_frequency, key_to_note_name, note_name_to_key};
+pub use osc::OscPlayer;
 pub use piano_player::PlayerPiano;
+pub use recorder::AudioRecorder;
+pub use scale::ScaleMapper;
+pub use streaming::StreamingPlayer;
@@ -0,0 +1,260 @@
+// MIDI export for Conway's Steinway
+// Writes the keys played by each generation as a Standard MIDI File, so a run
+// can be edited in a DAW instead of being rendered to audio. Every generation
+// lasts one step: its notes start together and end when the next step begins.
+// Timed notes from timeline::build_timeline can be added instead, so exports
+// keep the configured note and chord durations.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::key_mapping::PIANO_KEY_COUNT;
+use crate::timeline::NoteEvent;
+
+// MIDI note of the lowest piano key (A0)
+pub const MIDI_NOTE_OFFSET: u8 = 21;
+// Velocity used for every note
+pub const DEFAULT_VELOCITY: u8 = 96;
+
+// 500 ticks per quarter note at 120 BPM makes one tick one millisecond
+const TICKS_PER_QUARTER: u16 = 500;
+const MICROSECONDS_PER_QUARTER: u32 = 500_000;
+
+/// MIDI note number for a piano key (0-87 maps to 21-108)
+pub fn key_to_midi_note(key: usize) -> Option<u8> {
+    (key < PIANO_KEY_COUNT).then(|| MIDI_NOTE_OFFSET + key as u8)
+}
+
+pub struct MidiWriter {
+    step_ms: u64,
+    velocity: u8,
+    notes: Vec<NoteEvent>,
+    length_ms: u64, // End of the performance, including trailing silence
+}
+
+impl MidiWriter {
+    // One generation per `step_ms` milliseconds
+    pub fn new(step_ms: u64) -> Self {
+        MidiWriter {
+            step_ms,
+            velocity: DEFAULT_VELOCITY,
+            notes: Vec::new(),
+            length_ms: 0,
+        }
+    }
+
+    // Note-on velocity (1-127)
+    pub fn with_velocity(mut self, velocity: u8) -> Self {
+        self.velocity = velocity.clamp(1, 127);
+        self
+    }
+
+    // Append the keys played by the next generation (empty for a silent step)
+    pub fn add_generation(&mut self, keys: &[usize]) {
+        let start_ms = self.length_ms;
+        for &key in keys {
+            self.add_note(NoteEvent { key, start_ms, duration_ms: self.step_ms });
+        }
+        self.length_ms = start_ms + self.step_ms;
+    }
+
+    // Add a note at its own start time and length
+    pub fn add_note(&mut self, note: NoteEvent) {
+        self.length_ms = self.length_ms.max(note.end_ms());
+        self.notes.push(note);
+    }
+
+    // Keep the track running until `length_ms` even if the last notes end sooner
+    pub fn extend_to(&mut self, length_ms: u64) {
+        self.length_ms = self.length_ms.max(length_ms);
+    }
+
+    pub fn note_count(&self) -> usize {
+        self.notes.iter()
+            .filter(|note| key_to_midi_note(note.key).is_some())
+            .count()
+    }
+
+    // (millisecond, status, note) for every note-on and note-off, in track order.
+    // A key struck again before it has ended is cut short, so its note-off can't
+    // silence the new note.
+    fn channel_events(&self) -> Vec<(u64, u8, u8)> {
+        let mut notes: Vec<(u8, NoteEvent)> = self.notes.iter()
+            .filter_map(|&note| key_to_midi_note(note.key).map(|midi| (midi, note)))
+            .collect();
+        notes.sort_by_key(|&(midi, note)| (midi, note.start_ms));
+
+        let mut events = Vec::with_capacity(notes.len() * 2);
+        for (i, &(midi, note)) in notes.iter().enumerate() {
+            let mut end_ms = note.end_ms();
+            if let Some(&(next_midi, next)) = notes.get(i + 1) {
+                if next_midi == midi {
+                    if next.start_ms == note.start_ms {
+                        continue;
+                    }
+                    end_ms = end_ms.min(next.start_ms);
+                }
+            }
+            events.push((note.start_ms, 0x90, midi));
+            events.push((end_ms, 0x80, midi));
+        }
+
+        // Note-offs go first at a shared tick so a restruck key sounds again
+        events.sort_by_key(|&(ms, status, midi)| (ms, status == 0x90, midi));
+        events
+    }
+
+    /// The whole performance as a format 0 Standard MIDI File
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut track = Vec::new();
+        // Tempo meta event so ticks are milliseconds
+        track.extend_from_slice(&[0x00, 0xFF, 0x51, 0x03]);
+        track.extend_from_slice(&MICROSECONDS_PER_QUARTER.to_be_bytes()[1..]);
+
+        let mut last_ms = 0;
+        for (ms, status, note) in self.channel_events() {
+            write_var_len(&mut track, (ms - last_ms) as u32);
+            let velocity = if status == 0x90 { self.velocity } else { 0 };
+            track.extend_from_slice(&[status, note, velocity]);
+            last_ms = ms;
+        }
+
+        // End of track, after any trailing silent steps
+        write_var_len(&mut track, self.length_ms.saturating_sub(last_ms) as u32);
+        track.extend_from_slice(&[0xFF, 0x2F, 0x00]);
+
+        let mut bytes = Vec::with_capacity(track.len() + 22);
+        bytes.extend_from_slice(b"MThd");
+        bytes.extend_from_slice(&6u32.to_be_bytes());
+        bytes.extend_from_slice(&0u16.to_be_bytes()); // Format 0
+        bytes.extend_from_slice(&1u16.to_be_bytes()); // One track
+        bytes.extend_from_slice(&TICKS_PER_QUARTER.to_be_bytes());
+        bytes.extend_from_slice(b"MTrk");
+        bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&track);
+        bytes
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+}
+
+// MIDI variable-length quantity: 7 bits per byte, high bit set on all but the last
+fn write_var_len(out: &mut Vec<u8>, mut value: u32) {
+    let mut bytes = vec![(value & 0x7F) as u8];
+    value >>= 7;
+    while value > 0 {
+        bytes.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+    out.extend(bytes.iter().rev());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (absolute tick, status, note) for every channel event in a format 0 file
+    fn parse_smf(bytes: &[u8]) -> (u16, Vec<(u32, u8, u8)>) {
+        assert_eq!(&bytes[0..4], b"MThd");
+        assert_eq!(u32::from_be_bytes(bytes[4..8].try_into().unwrap()), 6);
+        assert_eq!(u16::from_be_bytes(bytes[8..10].try_into().unwrap()), 0);
+        assert_eq!(u16::from_be_bytes(bytes[10..12].try_into().unwrap()), 1);
+        let division = u16::from_be_bytes(bytes[12..14].try_into().unwrap());
+        assert_eq!(&bytes[14..18], b"MTrk");
+        let length = u32::from_be_bytes(bytes[18..22].try_into().unwrap()) as usize;
+        let track = &bytes[22..];
+        assert_eq!(track.len(), length);
+
+        let mut events = Vec::new();
+        let (mut pos, mut tick) = (0, 0u32);
+        loop {
+            let mut delta = 0u32;
+            loop {
+                let byte = track[pos];
+                pos += 1;
+                delta = (delta << 7) | (byte & 0x7F) as u32;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            tick += delta;
+            match track[pos] {
+                0xFF => {
+                    let kind = track[pos + 1];
+                    pos += 3 + track[pos + 2] as usize;
+                    if kind == 0x2F {
+                        assert_eq!(pos, track.len(), "end of track must be the last event");
+                        return (division, events);
+                    }
+                }
+                status => {
+                    events.push((tick, status, track[pos + 1]));
+                    pos += 3;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_to_midi_note() {
+        assert_eq!(key_to_midi_note(0), Some(21));
+        assert_eq!(key_to_midi_note(48), Some(69)); // A4
+        assert_eq!(key_to_midi_note(87), Some(108));
+        assert_eq!(key_to_midi_note(88), None);
+    }
+
+    #[test]
+    fn test_known_sequence_round_trips() {
+        let mut writer = MidiWriter::new(250);
+        writer.add_generation(&[0, 48]);
+        writer.add_generation(&[]);
+        writer.add_generation(&[87]);
+        writer.add_generation(&[]);
+        assert_eq!(writer.note_count(), 3);
+
+        let (division, events) = parse_smf(&writer.to_bytes());
+        assert_eq!(division, 500);
+
+        let note_ons: Vec<_> = events.iter().filter(|e| e.1 == 0x90).collect();
+        let note_offs: Vec<_> = events.iter().filter(|e| e.1 == 0x80).collect();
+        assert_eq!(note_ons.len(), 3);
+        assert_eq!(note_offs.len(), 3);
+
+        // One tick per millisecond: generation N starts at N * 250
+        assert_eq!(events, vec![
+            (0, 0x90, 21), (0, 0x90, 69),
+            (250, 0x80, 21), (250, 0x80, 69),
+            (500, 0x90, 108),
+            (750, 0x80, 108),
+        ]);
+    }
+
+    #[test]
+    fn test_repeated_notes_restart() {
+        let mut writer = MidiWriter::new(100);
+        writer.add_generation(&[40]);
+        writer.add_generation(&[40]);
+
+        let (_, events) = parse_smf(&writer.to_bytes());
+        // The first note ends before the second one starts at the same tick
+        assert_eq!(events, vec![
+            (0, 0x90, 61), (100, 0x80, 61), (100, 0x90, 61), (200, 0x80, 61),
+        ]);
+    }
+
+    #[test]
+    fn test_var_len_encoding() {
+        let encode = |value| {
+            let mut out = Vec::new();
+            write_var_len(&mut out, value);
+            out
+        };
+        assert_eq!(encode(0), vec![0x00]);
+        assert_eq!(encode(0x7F), vec![0x7F]);
+        assert_eq!(encode(0x80), vec![0x81, 0x00]);
+        assert_eq!(encode(0x3FFF), vec![0xFF, 0x7F]);
+    }
+}
@@ -0,0 +1,97 @@
+// Note names and frequencies for Conway's Steinway
+// Keys are numbered 0..=87 from A0. Names use scientific pitch, where octave
+// numbers change at C: A4 (the tuning reference) is key 48, middle C (C4) is
+// key 39 and the top key is C8.
+
+use crate::key_mapping::PIANO_KEY_COUNT;
+
+// Piano key of A4, the tuning reference
+pub const A4_KEY: usize = 48;
+
+// Note names within an octave, starting from C
+const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+// Semitones from C0, which is below the keyboard, up to A0 (key 0)
+const A0_FROM_C0: usize = 9;
+
+/// Frequency of a piano key in Hz with A4 tuned to `tuning_hz`
+pub fn key_to_frequency(key: usize, tuning_hz: f64) -> f64 {
+    tuning_hz * 2.0_f64.powf((key as f64 - A4_KEY as f64) / 12.0)
+}
+
+/// Note name for a piano key, e.g. "A0" for key 0, "C4" for key 39 and "A#4"
+/// for key 49
+pub fn key_to_note_name(key: usize) -> String {
+    let semitone = key + A0_FROM_C0;
+    format!("{}{}", NOTE_NAMES[semitone % 12], semitone / 12)
+}
+
+/// Piano key for a note name such as "C4", "A#3" or "Bb2" (the inverse of
+/// key_to_note_name). Flats are accepted too, and sharps may be written "s"
+/// as in sample file names ("cs4"). Returns None for names that don't parse
+/// or fall off the keyboard.
+pub fn note_name_to_key(name: &str) -> Option<usize> {
+    let name = name.trim();
+    let mut chars = name.chars();
+    let letter = chars.next()?.to_ascii_uppercase();
+    let rest = chars.as_str();
+
+    let (accidental, octave) = if let Some(octave) = rest.strip_prefix(['#', 's']) {
+        (1, octave)
+    } else if let Some(octave) = rest.strip_prefix('b') {
+        (-1, octave)
+    } else {
+        (0, rest)
+    };
+
+    let natural = NOTE_NAMES.iter().position(|&note| note == letter.to_string())? as i32;
+    let octave: i32 = octave.parse().ok()?;
+    let key = octave * 12 + natural + accidental - A0_FROM_C0 as i32;
+    (0..PIANO_KEY_COUNT as i32).contains(&key).then_some(key as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pitch::STANDARD_TUNING_HZ;
+
+    #[test]
+    fn test_a4_is_the_tuning_reference() {
+        let a4 = note_name_to_key("A4").unwrap();
+        assert_eq!(a4, A4_KEY);
+        assert_eq!(key_to_frequency(a4, STANDARD_TUNING_HZ), 440.0);
+        assert_eq!(key_to_frequency(a4, 432.0), 432.0);
+        assert!((key_to_frequency(a4 + 12, 415.0) - 830.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_note_names_round_trip() {
+        for name in ["A0", "C4", "A#3", "G#6", "C8"] {
+            let key = note_name_to_key(name).unwrap();
+            assert_eq!(key_to_note_name(key), name);
+        }
+        for key in 0..PIANO_KEY_COUNT {
+            assert_eq!(note_name_to_key(&key_to_note_name(key)), Some(key));
+        }
+    }
+
+    #[test]
+    fn test_flats_and_bad_names() {
+        assert_eq!(note_name_to_key("Bb3"), note_name_to_key("A#3"));
+        assert_eq!(note_name_to_key("Cs4"), note_name_to_key("C#4"));
+        assert_eq!(note_name_to_key("H2"), None);
+        assert_eq!(note_name_to_key("A"), None);
+        // Below A0 or above C8, the top key
+        assert_eq!(note_name_to_key("Ab0"), None);
+        assert_eq!(note_name_to_key("C#8"), None);
+    }
+
+    #[test]
+    fn test_scientific_pitch_landmarks() {
+        assert_eq!(note_name_to_key("A0"), Some(0));
+        assert_eq!(note_name_to_key("c4"), Some(39));
+        assert_eq!(note_name_to_key("C8"), Some(87));
+        assert_eq!(key_to_note_name(38), "B3");
+        assert_eq!(key_to_note_name(39), "C4");
+    }
+}
@@ -0,0 +1,168 @@
+// OSC output for Conway's Steinway
+// Sends every note as an Open Sound Control `/note` message over UDP, so a
+// run can drive SuperCollider, Max or a hardware bridge instead of the local
+// samples. Each message carries two int32 arguments: the piano key (0-87)
+// and its velocity. Only int32 arguments are needed, so packets are encoded
+// by hand rather than through an OSC library.
+
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+
+use log::warn;
+
+use crate::audio_engine::AudioPlayer;
+use crate::midi::DEFAULT_VELOCITY;
+
+// OSC address every note is sent to
+pub const NOTE_ADDRESS: &str = "/note";
+
+pub struct OscPlayer {
+    socket: UdpSocket,
+    target: SocketAddr,
+}
+
+impl OscPlayer {
+    // Bind an ephemeral local port and send every note to `target`
+    pub fn connect(target: SocketAddr) -> io::Result<Self> {
+        let local: SocketAddr = match target {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(target)?;
+        Ok(OscPlayer { socket, target })
+    }
+
+    pub fn target(&self) -> SocketAddr {
+        self.target
+    }
+
+    fn send_note(&self, key: usize, velocity: u8) {
+        // UDP is fire-and-forget: a missing listener shouldn't stop the run
+        if let Err(e) = self.socket.send(&encode_note(key, velocity)) {
+            warn!("Unable to send OSC note {} to {}: {}", key, self.target, e);
+        }
+    }
+}
+
+impl AudioPlayer for OscPlayer {
+    fn play_piano_keys(&self, keys: &[usize]) {
+        for &key in keys {
+            self.send_note(key, DEFAULT_VELOCITY);
+        }
+    }
+
+    fn play_chord(&self, keys: &[usize], _duration_ms: u64) {
+        self.play_piano_keys(keys);
+    }
+
+    fn play_notes(&self, notes: &[(usize, u8)]) {
+        for &(key, velocity) in notes {
+            self.send_note(key, velocity);
+        }
+    }
+}
+
+/// OSC packet for a `/note` message with the key and velocity as int32s
+pub fn encode_note(key: usize, velocity: u8) -> Vec<u8> {
+    encode_message(NOTE_ADDRESS, &[key as i32, velocity as i32])
+}
+
+/// OSC packet for `address` with int32 `args`
+pub fn encode_message(address: &str, args: &[i32]) -> Vec<u8> {
+    let mut packet = Vec::new();
+    push_padded_string(&mut packet, address);
+
+    let mut type_tags = String::from(",");
+    type_tags.extend(std::iter::repeat_n('i', args.len()));
+    push_padded_string(&mut packet, &type_tags);
+
+    for arg in args {
+        packet.extend_from_slice(&arg.to_be_bytes());
+    }
+    packet
+}
+
+/// Address and int32 arguments of an OSC message; None if the packet is
+/// malformed or carries anything other than int32s
+pub fn decode_message(packet: &[u8]) -> Option<(String, Vec<i32>)> {
+    let (address, rest) = read_padded_string(packet)?;
+    let (type_tags, mut rest) = read_padded_string(rest)?;
+    let tags = type_tags.strip_prefix(',')?;
+
+    let mut args = Vec::with_capacity(tags.len());
+    for tag in tags.chars() {
+        if tag != 'i' || rest.len() < 4 {
+            return None;
+        }
+        let (arg, tail) = rest.split_at(4);
+        args.push(i32::from_be_bytes(arg.try_into().ok()?));
+        rest = tail;
+    }
+
+    rest.is_empty().then_some((address, args))
+}
+
+// OSC strings are NUL-terminated and padded to a multiple of four bytes
+fn push_padded_string(packet: &mut Vec<u8>, s: &str) {
+    packet.extend_from_slice(s.as_bytes());
+    let padding = 4 - s.len() % 4;
+    packet.extend(std::iter::repeat_n(0, padding));
+}
+
+fn read_padded_string(bytes: &[u8]) -> Option<(String, &[u8])> {
+    let len = bytes.iter().position(|&b| b == 0)?;
+    let padded = (len / 4 + 1) * 4;
+    if padded > bytes.len() {
+        return None;
+    }
+    let s = String::from_utf8(bytes[..len].to_vec()).ok()?;
+    Some((s, &bytes[padded..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_note_packet_layout() {
+        let packet = encode_note(39, 96);
+        let mut expected = b"/note\0\0\0,ii\0".to_vec();
+        expected.extend_from_slice(&[0, 0, 0, 39, 0, 0, 0, 96]);
+        assert_eq!(packet, expected);
+        assert_eq!(decode_message(&packet), Some(("/note".to_string(), vec![39, 96])));
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_packets() {
+        let packet = encode_note(39, 96);
+        assert_eq!(decode_message(&packet[..packet.len() - 1]), None);
+        assert_eq!(decode_message(b"/note"), None);
+    }
+
+    #[test]
+    fn test_osc_player_sends_each_key() {
+        let listener = UdpSocket::bind("127.0.0.1:0").unwrap();
+        listener.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let player = OscPlayer::connect(listener.local_addr().unwrap()).unwrap();
+
+        player.play_piano_keys(&[0, 39, 87]);
+        player.play_notes(&[(48, 127)]);
+
+        let mut buffer = [0u8; 64];
+        let mut received = Vec::new();
+        for _ in 0..4 {
+            let len = listener.recv(&mut buffer).unwrap();
+            received.push(decode_message(&buffer[..len]).unwrap());
+        }
+
+        let note = |key, velocity| ("/note".to_string(), vec![key, velocity]);
+        assert_eq!(received, vec![
+            note(0, DEFAULT_VELOCITY as i32),
+            note(39, DEFAULT_VELOCITY as i32),
+            note(87, DEFAULT_VELOCITY as i32),
+            note(48, 127),
+        ]);
+    }
+}
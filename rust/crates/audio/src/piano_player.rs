@@ -1,8 +1,15 @@
-use super::audio_engine::{AudioPlayer, AudioEngine, NullAudioEngine};
+use super::audio_engine::{AudioPlayer, AudioEngine, NullAudioEngine, ChordDetector};
+use super::osc::OscPlayer;
+use super::recorder::AudioRecorder;
+use super::streaming::StreamingPlayer;
+use config::Config;
+use std::io;
+use std::path::PathBuf;
 use log::info;
 
 pub struct PlayerPiano {
     audio_engine: Box<dyn AudioPlayer>,
+    chord_detector: ChordDetector,
 }
 
 impl Default for PlayerPiano {
@@ -15,21 +22,104 @@ impl PlayerPiano {
     pub fn new() -> Self {
         PlayerPiano {
             audio_engine: Box::new(AudioEngine::new()),
+            chord_detector: ChordDetector::default(),
+        }
+    }
+
+    // Audio engine, timing and chord detection taken from the configuration;
+    // silent configurations get the no-op engine
+    pub fn from_config(config: &Config) -> Self {
+        if config.silent {
+            let chord_detector = ChordDetector::new(config.chord_cluster_size, config.chord_cluster_gap);
+            return Self::new_silent().with_chord_detector(chord_detector);
+        }
+        Self::with_engine(AudioEngine::from_config(config))
+    }
+
+    // Play the samples in `dir` instead of the repository's static/audio;
+    // fails if the directory doesn't exist
+    pub fn with_samples_dir(dir: PathBuf) -> io::Result<Self> {
+        Ok(Self::with_engine(AudioEngine::with_samples_dir(dir)?))
+    }
+
+    // Use a preconfigured audio engine
+    pub fn with_engine(engine: AudioEngine) -> Self {
+        PlayerPiano {
+            chord_detector: engine.chord_detector(),
+            audio_engine: Box::new(engine),
+        }
+    }
+
+    // Render into a recording instead of playing live
+    pub fn with_recorder(recorder: AudioRecorder) -> Self {
+        PlayerPiano {
+            chord_detector: recorder.chord_detector(),
+            audio_engine: Box::new(recorder),
+        }
+    }
+
+    // Send each note as an OSC message instead of playing it locally
+    pub fn with_osc(player: OscPlayer) -> Self {
+        PlayerPiano {
+            audio_engine: Box::new(player),
+            chord_detector: ChordDetector::default(),
+        }
+    }
+
+    // Play on a background thread: play_keys and wait return at once while
+    // the audio catches up from a bounded queue. Dropping the piano plays
+    // whatever is still queued before returning.
+    pub fn new_streaming() -> Self {
+        Self::with_streaming(ChordDetector::default(), || Box::new(AudioEngine::new()))
+    }
+
+    // Stream to the player built by `make_player` on the audio thread
+    pub fn with_streaming<F>(chord_detector: ChordDetector, make_player: F) -> Self
+    where
+        F: FnOnce() -> Box<dyn AudioPlayer> + Send + 'static,
+    {
+        PlayerPiano {
+            audio_engine: Box::new(StreamingPlayer::spawn(make_player)),
+            chord_detector,
         }
     }
 
     pub fn new_silent() -> Self {
         PlayerPiano {
             audio_engine: Box::new(NullAudioEngine::new()),
+            chord_detector: ChordDetector::default(),
         }
     }
 
+    // Use custom cluster thresholds when classifying keys as chords
+    pub fn with_chord_detector(mut self, chord_detector: ChordDetector) -> Self {
+        self.chord_detector = chord_detector;
+        self
+    }
+
     pub fn play_keys(&self, keys: &[usize]) {
         if keys.is_empty() {
             info!("♪ Silence");
             return;
         }
 
+        self.log_keys(keys);
+        self.audio_engine.play_piano_keys(keys);
+    }
+
+    // Play (key, velocity) pairs, scaling each note's amplitude by its velocity
+    pub fn play_notes(&self, notes: &[(usize, u8)]) {
+        if notes.is_empty() {
+            info!("♪ Silence");
+            return;
+        }
+
+        let keys: Vec<usize> = notes.iter().map(|&(key, _)| key).collect();
+        self.log_keys(&keys);
+        self.audio_engine.play_notes(notes);
+    }
+
+    fn log_keys(&self, keys: &[usize]) {
         // Check if this looks like a chord pattern
         let is_chord = self.is_chord_pattern(keys);
         
@@ -44,58 +134,15 @@ impl PlayerPiano {
         } else {
             info!("♪ Playing piano keys: {}", key_str);
         }
+    }
 
-        self.audio_engine.play_piano_keys(keys);
+    // Wait between generations (instant when recording)
+    pub fn wait(&self, duration_ms: u64) {
+        self.audio_engine.wait(duration_ms);
     }
 
     fn is_chord_pattern(&self, keys: &[usize]) -> bool {
-        // Simplified implementation that doesn't rely on downcasting
-        // Basic chord detection algorithm
-        if keys.len() < 3 {
-            return false;
-        }
-        
-        // Check for common chord patterns
-        let mut sorted_keys: Vec<usize> = keys.to_vec();
-        sorted_keys.sort();
-        
-        // Check for triads (3 notes)
-        if sorted_keys.len() >= 3 {
-            for i in 0..=sorted_keys.len()-3 {
-                let root = sorted_keys[i];
-                let third = sorted_keys[i+1];
-                let fifth = sorted_keys[i+2];
-                
-                let interval1 = third.saturating_sub(root);
-                let interval2 = fifth.saturating_sub(root);
-                
-                // Major chord: 4 and 7 semitones
-                // Minor chord: 3 and 7 semitones
-                // Diminished chord: 3 and 6 semitones
-                // Augmented chord: 4 and 8 semitones
-                if (interval1 == 3 || interval1 == 4) && 
-                   (6..=8).contains(&interval2) {
-                    return true;
-                }
-            }
-        }
-        
-        // Check for dense clusters (many consecutive notes)
-        if sorted_keys.len() >= 5 {
-            let mut consecutive_count = 1;
-            for i in 1..sorted_keys.len() {
-                if sorted_keys[i] - sorted_keys[i-1] <= 2 {
-                    consecutive_count += 1;
-                    if consecutive_count >= 5 {
-                        return true;
-                    }
-                } else {
-                    consecutive_count = 1;
-                }
-            }
-        }
-        
-        false
+        self.chord_detector.is_chord_pattern(keys)
     }
 
     // These methods are only used in tests but marked public
@@ -114,6 +161,46 @@ impl PlayerPiano {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    // Takes a while per note, like a real engine, and counts what it played
+    struct SlowCountingPlayer {
+        played: Arc<AtomicUsize>,
+    }
+
+    impl AudioPlayer for SlowCountingPlayer {
+        fn play_piano_keys(&self, keys: &[usize]) {
+            thread::sleep(Duration::from_millis(20));
+            self.played.fetch_add(keys.len(), Ordering::SeqCst);
+        }
+
+        fn play_chord(&self, keys: &[usize], _duration_ms: u64) {
+            self.play_piano_keys(keys);
+        }
+    }
+
+    #[test]
+    fn test_streaming_returns_before_playback() {
+        let played = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&played);
+        let piano = PlayerPiano::with_streaming(ChordDetector::default(), move || {
+            Box::new(SlowCountingPlayer { played: counter })
+        });
+
+        let start = Instant::now();
+        for key in 0..10 {
+            piano.play_keys(&[key]);
+        }
+        // Ten notes take 200 ms to play but only moments to queue
+        assert!(start.elapsed() < Duration::from_millis(100), "queueing took {:?}", start.elapsed());
+
+        // Dropping the piano plays out the queue
+        drop(piano);
+        assert_eq!(played.load(Ordering::SeqCst), 10);
+    }
 
     #[test]
     fn test_player_piano_creation() {
@@ -126,6 +213,21 @@ mod tests {
         piano_silent.play_keys(&[48]);
     }
 
+    #[test]
+    fn test_from_config_silent_is_a_no_op() {
+        let config = Config {
+            silent: true,
+            chord_cluster_size: 3,
+            ..Config::default()
+        };
+        let piano = PlayerPiano::from_config(&config);
+
+        // Cluster settings still come from the configuration
+        assert!(piano.is_chord_pattern(&[40, 41, 42]));
+        piano.play_keys(&[40, 41, 42]);
+        piano.play_notes(&[(48, 100)]);
+    }
+
     #[test]
     fn test_chord_detection() {
         let piano = PlayerPiano::new_silent();
@@ -0,0 +1,171 @@
+// Pitch shifting for Conway's Steinway
+// Playing a sample faster raises its pitch but also shortens it, so a note an
+// octave above its sample would last half as long. shift_pitch instead cuts
+// the sample into overlapping windowed grains that stay at their original
+// time position, and resamples inside each grain with linear interpolation.
+// The pitch moves by the requested ratio while the length is unchanged.
+// Each grain's read position is nudged (WSOLA-style) to line up with the
+// waveform of the grain before it, so the crossfades don't smear the pitch.
+
+use std::f32::consts::PI;
+
+// Grain length in frames (about 46 ms at 44.1 kHz); grains overlap by half
+const GRAIN_FRAMES: usize = 2048;
+// How far a grain's read position may move to line up with the previous grain
+const SEARCH_FRAMES: isize = 512;
+// Compare every Nth frame when lining grains up
+const SEARCH_STEP: usize = 4;
+
+// Concert pitch the piano samples were recorded at (A4 in Hz)
+pub const STANDARD_TUNING_HZ: f64 = 440.0;
+/// How far a tuning reference sits from standard pitch, in semitones
+/// (432 Hz is about a third of a semitone flat)
+pub fn tuning_offset(tuning_hz: f64) -> f32 {
+    (12.0 * (tuning_hz / STANDARD_TUNING_HZ).log2()) as f32
+}
+
+/// Frequency ratio for a shift of `semitones` (12 = one octave up)
+pub fn semitone_ratio(semitones: f32) -> f32 {
+    2.0_f32.powf(semitones / 12.0)
+}
+
+// Periodic Hann window; at 50% overlap neighbouring windows sum to 1
+fn hann(i: usize, length: usize) -> f32 {
+    0.5 - 0.5 * (2.0 * PI * i as f32 / length as f32).cos()
+}
+
+// Linearly interpolated sample of one channel at a fractional frame position
+fn sample_at(samples: &[f32], channels: usize, channel: usize, position: f32) -> f32 {
+    let frames = samples.len() / channels;
+    let frame = position.floor() as usize;
+    if frame + 1 >= frames {
+        return 0.0;
+    }
+    let fraction = position - frame as f32;
+    let a = samples[frame * channels + channel];
+    let b = samples[(frame + 1) * channels + channel];
+    a + (b - a) * fraction
+}
+
+/// Shift the pitch of interleaved samples by `ratio` (2.0 = one octave up)
+/// while keeping the same number of samples, so the note's duration is preserved
+pub fn shift_pitch(samples: &[f32], channels: usize, ratio: f32) -> Vec<f32> {
+    if channels == 0 || samples.is_empty() || (ratio - 1.0).abs() < f32::EPSILON || ratio <= 0.0 {
+        return samples.to_vec();
+    }
+
+    let frames = samples.len() / channels;
+    let hop = GRAIN_FRAMES / 2;
+    let mut output = vec![0.0; frames * channels];
+    // Where the previous grain started on the output and in the source
+    let mut previous: Option<(usize, f32)> = None;
+
+    // Start half a grain early so every frame is covered by two windows
+    let mut start = -(hop as isize);
+    while start < frames as isize {
+        let anchor = start.max(0) as usize;
+        let end = ((start + GRAIN_FRAMES as isize) as usize).min(frames);
+        let read = match previous {
+            Some((previous_anchor, previous_read)) => {
+                let continuation = previous_read + (anchor - previous_anchor) as f32 * ratio;
+                best_alignment(samples, channels, anchor, end.min(anchor + hop), continuation, ratio)
+            }
+            None => anchor as f32,
+        };
+
+        for frame in anchor..end {
+            let weight = hann((frame as isize - start) as usize, GRAIN_FRAMES);
+            let position = read + (frame - anchor) as f32 * ratio;
+            for channel in 0..channels {
+                output[frame * channels + channel] += weight * sample_at(samples, channels, channel, position);
+            }
+        }
+
+        previous = Some((anchor, read));
+        start += hop as isize;
+    }
+
+    output
+}
+
+// Source position near `anchor` whose waveform over the overlap best matches
+// the natural continuation of the previous grain (compared on the first channel)
+fn best_alignment(samples: &[f32], channels: usize, anchor: usize, overlap_end: usize,
+                  continuation: f32, ratio: f32) -> f32 {
+    let correlation = |read: f32| -> f32 {
+        (anchor..overlap_end).step_by(SEARCH_STEP)
+            .map(|frame| {
+                let offset = (frame - anchor) as f32 * ratio;
+                sample_at(samples, channels, 0, read + offset) * sample_at(samples, channels, 0, continuation + offset)
+            })
+            .sum()
+    };
+
+    (-SEARCH_FRAMES..=SEARCH_FRAMES)
+        .map(|delta| anchor as f32 + delta as f32)
+        .filter(|&read| read >= 0.0)
+        .map(|read| (read, correlation(read)))
+        .fold((anchor as f32, f32::MIN), |best, candidate| if candidate.1 > best.1 { candidate } else { best })
+        .0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    fn sine(frequency: f32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .map(|i| (2.0 * PI * frequency * i as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    // Estimate the frequency of a mono signal from its rising zero crossings
+    fn estimate_frequency(samples: &[f32]) -> f32 {
+        let crossings = samples.windows(2).filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0).count();
+        crossings as f32 * SAMPLE_RATE / samples.len() as f32
+    }
+
+    #[test]
+    fn test_shift_preserves_length_and_moves_pitch() {
+        let input = sine(440.0, SAMPLE_RATE as usize);
+
+        for semitones in [7.0, -5.0, 12.0] {
+            let ratio = semitone_ratio(semitones);
+            let output = shift_pitch(&input, 1, ratio);
+            assert_eq!(output.len(), input.len(), "Duration changed for {} semitones", semitones);
+
+            // Skip the edges, where the highest shifts run out of source material
+            let middle = &output[GRAIN_FRAMES..output.len() / 2];
+            let frequency = estimate_frequency(middle);
+            let expected = 440.0 * ratio;
+            assert!((frequency - expected).abs() / expected < 0.05,
+                    "Expected about {:.0} Hz for {} semitones, got {:.0} Hz", expected, semitones, frequency);
+        }
+    }
+
+    #[test]
+    fn test_stereo_channels_stay_separate() {
+        let left = sine(440.0, 8192);
+        let interleaved: Vec<f32> = left.iter().flat_map(|&sample| [sample, 0.0]).collect();
+
+        let output = shift_pitch(&interleaved, 2, semitone_ratio(3.0));
+        assert_eq!(output.len(), interleaved.len());
+        assert!(output.iter().skip(1).step_by(2).all(|&sample| sample == 0.0));
+        assert!(output.iter().step_by(2).any(|&sample| sample.abs() > 0.5));
+    }
+
+    #[test]
+    fn test_tuning_reference() {
+        assert_eq!(tuning_offset(STANDARD_TUNING_HZ), 0.0);
+        // Baroque pitch is a semitone below standard
+        assert!((tuning_offset(415.0) + 1.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_unit_ratio_is_untouched() {
+        let input = sine(220.0, 1000);
+        assert_eq!(shift_pitch(&input, 1, 1.0), input);
+    }
+}
@@ -0,0 +1,125 @@
+// Waveform preview helpers for Conway's Steinway
+// Summarizes a rendered sample buffer as per-bucket peaks so loud and quiet
+// sections can be eyeballed without opening an audio editor
+
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use hound::WavReader;
+
+// Sparkline glyphs from quietest to loudest
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// Buckets in the preview written after a recording
+pub const PREVIEW_BUCKETS: usize = 60;
+
+/// Split the buffer into `buckets` equal slices and return each slice's peak
+/// amplitude, normalized to 0.0-1.0
+pub fn compute_peaks(samples: &[i16], buckets: usize) -> Vec<f32> {
+    if samples.is_empty() || buckets == 0 {
+        return Vec::new();
+    }
+
+    let bucket_size = samples.len().div_ceil(buckets);
+    samples.chunks(bucket_size)
+        .map(|chunk| {
+            let peak = chunk.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+            peak as f32 / i16::MAX as f32
+        })
+        .map(|peak| peak.min(1.0))
+        .collect()
+}
+
+/// Render peaks as a unicode sparkline, one glyph per bucket
+pub fn peaks_to_sparkline(peaks: &[f32]) -> String {
+    peaks.iter()
+        .map(|&peak| {
+            let level = (peak.clamp(0.0, 1.0) * (SPARK_LEVELS.len() - 1) as f32).round() as usize;
+            SPARK_LEVELS[level]
+        })
+        .collect()
+}
+
+/// Write the peaks next to a rendered file as a compact JSON array
+pub fn write_peaks_file(path: &Path, peaks: &[f32]) -> Result<(), Box<dyn std::error::Error>> {
+    let values: Vec<String> = peaks.iter().map(|peak| format!("{:.3}", peak)).collect();
+    fs::write(path, format!("[{}]\n", values.join(",")))?;
+    Ok(())
+}
+
+/// Where the peaks of `wav_path` are written: the same name with `.peaks` appended
+pub fn peaks_path(wav_path: &Path) -> PathBuf {
+    let mut path = OsString::from(wav_path.as_os_str());
+    path.push(".peaks");
+    PathBuf::from(path)
+}
+
+/// Read a 16-bit WAV file, write its peaks next to it (see `peaks_path`) and
+/// return them as a sparkline
+pub fn write_preview(wav_path: &Path, buckets: usize) -> Result<String, Box<dyn std::error::Error>> {
+    let mut reader = WavReader::open(wav_path)?;
+    let samples = reader.samples::<i16>().collect::<Result<Vec<_>, _>>()?;
+    let peaks = compute_peaks(&samples, buckets);
+    write_peaks_file(&peaks_path(wav_path), &peaks)?;
+    Ok(peaks_to_sparkline(&peaks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::{SampleFormat, WavSpec, WavWriter};
+
+    #[test]
+    fn test_compute_peaks_bucket_maxima() {
+        // Three buckets of four samples each
+        let samples: Vec<i16> = vec![
+            0, 100, -200, 50,
+            i16::MAX, 0, 0, -10,
+            -16384, 10, 20, 30,
+        ];
+
+        let peaks = compute_peaks(&samples, 3);
+        assert_eq!(peaks.len(), 3);
+        assert!((peaks[0] - 200.0 / i16::MAX as f32).abs() < 1e-6);
+        assert!((peaks[1] - 1.0).abs() < 1e-6);
+        assert!((peaks[2] - 16384.0 / i16::MAX as f32).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_compute_peaks_handles_edge_cases() {
+        assert!(compute_peaks(&[], 4).is_empty());
+        assert!(compute_peaks(&[1, 2, 3], 0).is_empty());
+
+        // The negative extreme doesn't overflow and is capped at 1.0
+        assert_eq!(compute_peaks(&[i16::MIN], 1), vec![1.0]);
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(peaks_to_sparkline(&[0.0, 0.5, 1.0]), "▁▅█");
+    }
+
+    #[test]
+    fn test_preview_of_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let wav_path = dir.path().join("run.wav");
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+
+        // Silence, then a full-scale burst
+        let mut writer = WavWriter::create(&wav_path, spec).unwrap();
+        for i in 0..8 {
+            writer.write_sample(if i < 4 { 0i16 } else { i16::MAX }).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        assert_eq!(write_preview(&wav_path, 2).unwrap(), "▁█");
+        assert_eq!(peaks_path(&wav_path), dir.path().join("run.wav.peaks"));
+        assert_eq!(fs::read_to_string(peaks_path(&wav_path)).unwrap(), "[0.000,1.000]\n");
+    }
+}
@@ -0,0 +1,541 @@
+// WAV recording for Conway's Steinway
+// AudioRecorder plays the same notes as the AudioEngine, but instead of
+// queueing them on a sink it mixes each one into an in-memory stereo buffer
+// at its time offset. Time only moves on a virtual clock, so a whole run is
+// rendered as fast as the board can be stepped and written out on finalize.
+
+use std::collections::HashMap;
+use std::io::{self, Cursor};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use config::{AdsrConfig, Config};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use log::{error, info, warn};
+
+use crate::audio_engine::{
+    load_sample_data, load_sample_data_in, nearest_sample_key, shift_compensation, with_full_velocity, AudioPlayer, ChordDetector,
+    NoteTiming, DEFAULT_VOLUME,
+};
+use crate::dynamics::{edge_key_gain, key_pan, limit_polyphony, pan_gains, velocity_gain, KEYBOARD_CENTER};
+use crate::reverb::apply_reverb_interleaved;
+use crate::synth::{generate_piano_wave_with_envelope, SYNTH_NOTE_DURATION_MS};
+use crate::notes::key_to_frequency;
+use crate::pitch::{semitone_ratio, shift_pitch, tuning_offset, STANDARD_TUNING_HZ};
+
+// Sample rate of recordings; every sample is resampled to it before mixing
+pub const RECORDING_SAMPLE_RATE: u32 = 44100;
+// Recordings are always stereo
+const RECORDING_CHANNELS: usize = 2;
+
+// Cloning shares the recording, so a caller can keep one handle while the
+// piano plays through another
+#[derive(Clone)]
+pub struct AudioRecorder {
+    sample_cache: Arc<HashMap<usize, Vec<u8>>>, // Piano samples as WAV bytes
+    volume: f32,
+    edge_key_band: usize,
+    edge_key_attenuation: f64,
+    stereo_width: f32,
+    stereo_center: f32,
+    pitch_shift: bool,
+    max_polyphony: usize,
+    sustain: bool,
+    timing: NoteTiming,
+    tuning_hz: f64,
+    reverb_mix: f64,
+    envelope: Option<AdsrConfig>,
+    chord_detector: ChordDetector,
+    state: Arc<Mutex<RecordingState>>,
+}
+
+#[derive(Default)]
+struct RecordingState {
+    mix: Vec<f32>,                            // Interleaved stereo mix, unclipped
+    cursor: usize,                            // Current position on the virtual clock, in frames
+    rendered: HashMap<usize, Arc<Vec<f32>>>, // Stereo samples retuned for each key
+}
+
+impl Default for AudioRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioRecorder {
+    pub fn new() -> Self {
+        Self::with_samples(load_sample_data())
+    }
+
+    // A recorder mixing the samples in `dir` instead of the repository's
+    // static/audio; fails if the directory doesn't exist
+    pub fn with_samples_dir(dir: PathBuf) -> io::Result<Self> {
+        Ok(Self::with_samples(load_sample_data_in(&dir)?))
+    }
+
+    fn with_samples(sample_cache: HashMap<usize, Vec<u8>>) -> Self {
+        AudioRecorder {
+            sample_cache: Arc::new(sample_cache),
+            volume: DEFAULT_VOLUME,
+            edge_key_band: 0,
+            edge_key_attenuation: 0.0,
+            stereo_width: 0.0,
+            stereo_center: KEYBOARD_CENTER,
+            pitch_shift: true,
+            max_polyphony: 0,
+            sustain: false,
+            timing: NoteTiming::default(),
+            tuning_hz: STANDARD_TUNING_HZ,
+            reverb_mix: 0.0,
+            envelope: None,
+            chord_detector: ChordDetector::default(),
+            state: Arc::new(Mutex::new(RecordingState::default())),
+        }
+    }
+
+    // A recorder with every audio setting taken from the configuration
+    pub fn from_config(config: &Config) -> Self {
+        let recorder = match &config.samples_dir {
+            // Config validation has already checked the directory exists
+            Some(dir) => Self::with_samples_dir(dir.clone()).unwrap_or_else(|e| {
+                warn!("{}; using the repository samples", e);
+                Self::new()
+            }),
+            None => Self::new(),
+        };
+        recorder
+            .with_edge_attenuation(config.edge_key_band, config.edge_key_attenuation)
+            .with_stereo(config.effective_stereo_width(), config.stereo_center_key)
+            .with_pitch_shift(config.pitch_shift)
+            .with_max_polyphony(config.max_polyphony)
+            .with_sustain(config.sustain)
+            .with_volume(config.volume)
+            .with_timing(NoteTiming::from_config(config))
+            .with_tuning(config.tuning_hz)
+            .with_reverb(config.reverb_mix)
+            .with_envelope(config.envelope)
+            .with_chord_detector(ChordDetector::new(config.chord_cluster_size, config.chord_cluster_gap))
+    }
+
+    // Master volume, clamped to 0.0-1.0
+    pub fn with_volume(mut self, volume: f64) -> Self {
+        self.volume = volume.clamp(0.0, 1.0) as f32;
+        self
+    }
+
+    // Soften the lowest and highest `band` keys by `attenuation` (0.0-1.0)
+    pub fn with_edge_attenuation(mut self, band: usize, attenuation: f64) -> Self {
+        self.edge_key_band = band;
+        self.edge_key_attenuation = attenuation;
+        self
+    }
+
+    // Pan keys across the stereo image; width 0.0 keeps the samples as recorded
+    pub fn with_stereo(mut self, width: f64, center_key: Option<usize>) -> Self {
+        self.stereo_width = width as f32;
+        self.stereo_center = center_key.map_or(KEYBOARD_CENTER, |key| key as f32);
+        self
+    }
+
+    // Retune samples to each key's exact pitch, or use the nearest sample untouched
+    pub fn with_pitch_shift(mut self, enabled: bool) -> Self {
+        self.pitch_shift = enabled;
+        self
+    }
+
+    // Sound at most `max` keys at once, spread across the range (0 = unlimited)
+    pub fn with_max_polyphony(mut self, max: usize) -> Self {
+        self.max_polyphony = max;
+        self
+    }
+
+    // Let notes ring into the next generation instead of moving the clock
+    // past their duration
+    pub fn with_sustain(mut self, sustain: bool) -> Self {
+        self.sustain = sustain;
+        self
+    }
+
+    // Tune A4 to `tuning_hz` instead of 440 Hz (with pitch shifting on)
+    pub fn with_tuning(mut self, tuning_hz: f64) -> Self {
+        self.tuning_hz = tuning_hz;
+        self
+    }
+
+    // Add a reverb tail to every note; `mix` runs from 0.0 (dry) to 1.0 (all reverb)
+    pub fn with_reverb(mut self, mix: f64) -> Self {
+        self.reverb_mix = mix.clamp(0.0, 1.0);
+        self
+    }
+
+    // Shape notes synthesized for keys without a sample with this envelope
+    // instead of the built-in register curves
+    pub fn with_envelope(mut self, envelope: Option<AdsrConfig>) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    // Use these note, gap and chord durations instead of the defaults
+    pub fn with_timing(mut self, timing: NoteTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    // Use custom cluster thresholds for chord detection
+    pub fn with_chord_detector(mut self, chord_detector: ChordDetector) -> Self {
+        self.chord_detector = chord_detector;
+        self
+    }
+
+    pub fn chord_detector(&self) -> ChordDetector {
+        self.chord_detector
+    }
+
+    // Length of the recording so far in milliseconds, including trailing silence
+    pub fn duration_ms(&self) -> u64 {
+        let state = self.lock_state();
+        frames_to_ms(total_frames(&state))
+    }
+
+    // Write the recording to a 16-bit stereo WAV file. Gaps with no notes are
+    // written as silence and the file runs until the last note has died away.
+    pub fn finalize(&self, path: &Path) -> Result<(), hound::Error> {
+        let spec = WavSpec {
+            channels: RECORDING_CHANNELS as u16,
+            sample_rate: RECORDING_SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+
+        let state = self.lock_state();
+        let frames = total_frames(&state);
+        let mut writer = WavWriter::create(path, spec)?;
+        for &sample in &state.mix {
+            writer.write_sample(to_pcm(sample))?;
+        }
+        for _ in state.mix.len()..frames * RECORDING_CHANNELS {
+            writer.write_sample(0i16)?;
+        }
+        writer.finalize()?;
+
+        info!("Recorded {:.1} seconds to {}", frames_to_ms(frames) as f64 / 1000.0, path.display());
+        Ok(())
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, RecordingState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Move the virtual clock forward
+    fn advance(&self, duration_ms: u64) {
+        self.lock_state().cursor += ms_to_frames(duration_ms);
+    }
+
+    // The stereo samples for `key`, decoded (and retuned when enabled) on first
+    // use, plus the pitch-shift volume compensation that goes with them
+    fn rendered_sample(&self, key: usize) -> Option<(Arc<Vec<f32>>, f32)> {
+        let Some(sample_key) = nearest_sample_key(self.sample_cache.keys().copied(), key) else {
+            return Some((self.synthesized_sample(key), 1.0));
+        };
+        let semitone_difference = key as f32 - sample_key as f32 + tuning_offset(self.tuning_hz);
+        let shifted = self.pitch_shift && semitone_difference != 0.0;
+        let compensation = if shifted { shift_compensation(semitone_difference) } else { 1.0 };
+
+        if let Some(rendered) = self.lock_state().rendered.get(&key) {
+            return Some((Arc::clone(rendered), compensation));
+        }
+
+        let stereo = match decode_stereo(&self.sample_cache[&sample_key]) {
+            Ok(stereo) => stereo,
+            Err(e) => {
+                error!("Unable to decode the sample for key {}: {}", sample_key, e);
+                return None;
+            }
+        };
+        let mut samples = if shifted {
+            shift_pitch(&stereo, RECORDING_CHANNELS, semitone_ratio(semitone_difference))
+        } else {
+            stereo
+        };
+        if self.reverb_mix > 0.0 {
+            samples = apply_reverb_interleaved(&samples, RECORDING_CHANNELS, self.reverb_mix, RECORDING_SAMPLE_RATE);
+        }
+
+        let rendered = Arc::new(samples);
+        self.lock_state().rendered.insert(key, Arc::clone(&rendered));
+        Some((rendered, compensation))
+    }
+
+    // A synthesized note for `key`, for when no samples were loaded at all
+    fn synthesized_sample(&self, key: usize) -> Arc<Vec<f32>> {
+        if let Some(rendered) = self.lock_state().rendered.get(&key) {
+            return Arc::clone(rendered);
+        }
+
+        let frequency = key_to_frequency(key, self.tuning_hz);
+        let mono = generate_piano_wave_with_envelope(frequency, SYNTH_NOTE_DURATION_MS, self.envelope.as_ref());
+        let mut samples: Vec<f32> = mono.iter().flat_map(|&s| [s, s]).collect();
+        if self.reverb_mix > 0.0 {
+            samples = apply_reverb_interleaved(&samples, RECORDING_CHANNELS, self.reverb_mix, RECORDING_SAMPLE_RATE);
+        }
+
+        let rendered = Arc::new(samples);
+        self.lock_state().rendered.insert(key, Arc::clone(&rendered));
+        rendered
+    }
+
+    // Mix `key` into the recording at the current clock position; overlapping
+    // notes add together and are clipped when the file is written
+    fn record_sample(&self, key: usize, velocity: u8) {
+        let Some((samples, compensation)) = self.rendered_sample(key) else {
+            error!("No sample available to record key {}", key);
+            return;
+        };
+
+        let gain = self.volume * velocity_gain(velocity) * compensation
+            * edge_key_gain(key, self.edge_key_band, self.edge_key_attenuation);
+        let (left, right) = if self.stereo_width > 0.0 {
+            pan_gains(key_pan(key, self.stereo_center, self.stereo_width))
+        } else {
+            (1.0, 1.0)
+        };
+
+        let mut state = self.lock_state();
+        let start = state.cursor * RECORDING_CHANNELS;
+        let end = start + samples.len();
+        if state.mix.len() < end {
+            state.mix.resize(end, 0.0);
+        }
+        for (i, frame) in samples.chunks_exact(RECORDING_CHANNELS).enumerate() {
+            let at = start + i * RECORDING_CHANNELS;
+            state.mix[at] += frame[0] * gain * left;
+            state.mix[at + 1] += frame[1] * gain * right;
+        }
+    }
+
+    pub fn is_chord_pattern(&self, keys: &[usize]) -> bool {
+        self.chord_detector.is_chord_pattern(keys)
+    }
+}
+
+// Same timing as the AudioEngine, with sleeps replaced by moving the clock
+impl AudioPlayer for AudioRecorder {
+    fn play_piano_keys(&self, keys: &[usize]) {
+        self.play_notes(&with_full_velocity(keys));
+    }
+
+    fn play_chord(&self, keys: &[usize], duration_ms: u64) {
+        self.record_chord(&with_full_velocity(keys), duration_ms);
+    }
+
+    fn play_notes(&self, notes: &[(usize, u8)]) {
+        if notes.is_empty() {
+            return;
+        }
+
+        let keys: Vec<usize> = notes.iter().map(|&(key, _)| key).collect();
+        let notes = &limit_polyphony(notes, self.max_polyphony);
+        if self.is_chord_pattern(&keys) {
+            self.record_chord(notes, self.timing.chord_duration_ms);
+        } else {
+            for &(key, velocity) in notes {
+                self.record_sample(key, velocity);
+                self.advance(self.timing.gap_ms);
+            }
+        }
+
+        if !self.sustain {
+            self.advance(self.timing.note_duration_ms);
+        }
+    }
+
+    fn wait(&self, duration_ms: u64) {
+        self.advance(duration_ms);
+    }
+}
+
+impl AudioRecorder {
+    fn record_chord(&self, notes: &[(usize, u8)], duration_ms: u64) {
+        if notes.is_empty() {
+            return;
+        }
+
+        for (i, &(key, velocity)) in notes.iter().enumerate() {
+            if i > 0 {
+                self.advance(10);
+            }
+            self.record_sample(key, velocity);
+        }
+
+        if !self.sustain {
+            self.advance(duration_ms);
+        }
+    }
+}
+
+// Frames covered by the recording: up to the clock, or to the end of the last
+// note if it rings on past it
+fn total_frames(state: &RecordingState) -> usize {
+    state.cursor.max(state.mix.len() / RECORDING_CHANNELS)
+}
+
+fn ms_to_frames(duration_ms: u64) -> usize {
+    (duration_ms * RECORDING_SAMPLE_RATE as u64 / 1000) as usize
+}
+
+fn frames_to_ms(frames: usize) -> u64 {
+    frames as u64 * 1000 / RECORDING_SAMPLE_RATE as u64
+}
+
+// Clip a mixed sample to full scale and convert it to 16-bit PCM
+fn to_pcm(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16
+}
+
+// Decode WAV bytes to interleaved stereo at the recording sample rate
+fn decode_stereo(data: &[u8]) -> Result<Vec<f32>, hound::Error> {
+    let mut reader = WavReader::new(Cursor::new(data))?;
+    let spec = reader.spec();
+    let samples: Vec<f32> = match spec.sample_format {
+        SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader.samples::<i32>()
+                .map(|sample| sample.map(|value| value as f32 / scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    // Mono samples play on both sides; extra channels are dropped
+    let channels = spec.channels.max(1) as usize;
+    let stereo: Vec<f32> = samples.chunks_exact(channels)
+        .flat_map(|frame| [frame[0], frame[channels.min(2) - 1]])
+        .collect();
+
+    Ok(resample(&stereo, spec.sample_rate))
+}
+
+// Linear-interpolation resampling of interleaved stereo to the recording rate
+fn resample(stereo: &[f32], sample_rate: u32) -> Vec<f32> {
+    if sample_rate == RECORDING_SAMPLE_RATE || sample_rate == 0 {
+        return stereo.to_vec();
+    }
+
+    let frames = stereo.len() / RECORDING_CHANNELS;
+    if frames == 0 {
+        return Vec::new();
+    }
+    let step = sample_rate as f64 / RECORDING_SAMPLE_RATE as f64;
+    let out_frames = (frames as f64 / step) as usize;
+
+    let mut out = Vec::with_capacity(out_frames * RECORDING_CHANNELS);
+    for i in 0..out_frames {
+        let position = i as f64 * step;
+        let index = position as usize;
+        let next = (index + 1).min(frames - 1);
+        let fraction = (position - index as f64) as f32;
+        for channel in 0..RECORDING_CHANNELS {
+            let a = stereo[index * RECORDING_CHANNELS + channel];
+            let b = stereo[next * RECORDING_CHANNELS + channel];
+            out.push(a + (b - a) * fraction);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames_at(ms: u64) -> usize {
+        ms_to_frames(ms)
+    }
+
+    #[test]
+    fn test_records_two_note_sequence() {
+        let recorder = AudioRecorder::new();
+        recorder.play_piano_keys(&[48]);
+        recorder.wait(500);
+        recorder.play_piano_keys(&[60]);
+        recorder.wait(500);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("two_notes.wav");
+        recorder.finalize(&path).unwrap();
+
+        let mut reader = WavReader::open(&path).unwrap();
+        let spec = reader.spec();
+        assert_eq!(spec.channels, 2);
+        assert_eq!(spec.sample_rate, RECORDING_SAMPLE_RATE);
+        assert_eq!(spec.bits_per_sample, 16);
+
+        // Each note takes 50 + 300 ms and each wait 500 ms, so the second note
+        // starts at 850 ms and the file lasts until its sample has finished
+        let (second, _) = recorder.rendered_sample(60).unwrap();
+        let expected = (frames_at(850) + second.len() / 2).max(frames_at(1700));
+        assert_eq!(reader.duration() as usize, expected);
+
+        let samples: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+        let window = |from_ms: u64| &samples[frames_at(from_ms) * 2..frames_at(from_ms + 100) * 2];
+        assert!(window(0).iter().any(|&s| s != 0), "first note should be audible");
+        assert!(window(850).iter().any(|&s| s != 0), "second note should be audible");
+    }
+
+    #[test]
+    fn test_gaps_are_written_as_silence() {
+        let recorder = AudioRecorder::new();
+        recorder.play_piano_keys(&[48]);
+        // Wait well past the end of the note
+        recorder.wait(20_000);
+        assert_eq!(recorder.duration_ms(), 20_350);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gap.wav");
+        recorder.finalize(&path).unwrap();
+
+        let mut reader = WavReader::open(&path).unwrap();
+        assert_eq!(reader.duration() as usize, frames_at(20_350));
+        let samples: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+        let tail = &samples[frames_at(19_000) * 2..];
+        assert!(tail.iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_sustain_skips_the_note_tail() {
+        let recorder = AudioRecorder::new().with_sustain(true);
+        recorder.play_piano_keys(&[48]);
+        recorder.wait(20_000);
+        // Only the 50 ms gap is added before the wait; the 300 ms note tail is not
+        assert_eq!(recorder.duration_ms(), 20_050);
+    }
+
+    #[test]
+    fn test_keys_without_samples_are_synthesized() {
+        let recorder = AudioRecorder {
+            sample_cache: Arc::new(HashMap::new()),
+            ..AudioRecorder::new()
+        };
+        let (samples, compensation) = recorder.rendered_sample(48).unwrap();
+        assert_eq!(compensation, 1.0);
+        assert_eq!(samples.len(), frames_at(SYNTH_NOTE_DURATION_MS) * RECORDING_CHANNELS);
+        assert!(samples.iter().any(|&s| s.abs() > 0.1));
+    }
+
+    #[test]
+    fn test_overlapping_notes_are_clipped() {
+        assert_eq!(to_pcm(0.0), 0);
+        assert_eq!(to_pcm(0.5), 16384);
+        // Sums beyond full scale clip instead of wrapping around
+        assert_eq!(to_pcm(2.5), i16::MAX);
+        assert_eq!(to_pcm(-2.5), -i16::MAX);
+    }
+
+    #[test]
+    fn test_resample_to_recording_rate() {
+        // One second at 22.05 kHz becomes one second at 44.1 kHz
+        let half_rate = vec![0.25; 22050 * 2];
+        let resampled = resample(&half_rate, 22050);
+        assert_eq!(resampled.len(), 44100 * 2);
+        assert!(resampled.iter().all(|&s| (s - 0.25).abs() < 1e-6));
+    }
+}
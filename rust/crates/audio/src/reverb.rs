@@ -0,0 +1,129 @@
+// Reverb for Conway's Steinway
+// A small Schroeder reverb: four feedback comb filters in parallel build up
+// the decaying reflections, then two all-pass filters in series smear them
+// into a smooth tail. The buffer is lengthened so the tail can ring out.
+
+// Comb filter delays in milliseconds; mutually prime-ish so echoes don't line up
+const COMB_DELAYS_MS: [f64; 4] = [29.7, 37.1, 41.1, 43.7];
+// All-pass delays in milliseconds and their gain
+const ALLPASS_DELAYS_MS: [f64; 2] = [5.0, 1.7];
+const ALLPASS_GAIN: f64 = 0.7;
+// Time for the reflections to fall by 60 dB
+const DECAY_SECONDS: f64 = 1.2;
+// Silence appended to the buffer for the tail to ring into
+pub const REVERB_TAIL_SECONDS: f64 = 1.0;
+
+/// Mix a reverb tail into a mono buffer of 16-bit samples. `mix` (0.0-1.0)
+/// crossfades from the dry sound to the fully reverberated one; at 0.0 the
+/// buffer is left exactly as it was. Otherwise it grows by REVERB_TAIL_SECONDS.
+pub fn apply_reverb(samples: &mut Vec<i16>, mix: f64, sample_rate: u32) {
+    let mix = mix.clamp(0.0, 1.0);
+    if mix == 0.0 || samples.is_empty() || sample_rate == 0 {
+        return;
+    }
+
+    let tail = (REVERB_TAIL_SECONDS * sample_rate as f64) as usize;
+    let dry: Vec<f64> = samples.iter()
+        .map(|&s| s as f64)
+        .chain(std::iter::repeat_n(0.0, tail))
+        .collect();
+
+    let delay_samples = |ms: f64| ((ms / 1000.0 * sample_rate as f64) as usize).max(1);
+
+    // Parallel feedback combs, each decaying at the same overall rate
+    let mut wet = vec![0.0; dry.len()];
+    for &delay_ms in &COMB_DELAYS_MS {
+        let delay = delay_samples(delay_ms);
+        let feedback = 10f64.powf(-3.0 * delay_ms / 1000.0 / DECAY_SECONDS);
+        let mut buffer = vec![0.0; dry.len()];
+        for i in 0..dry.len() {
+            let echo = if i >= delay { buffer[i - delay] } else { 0.0 };
+            buffer[i] = dry[i] + feedback * echo;
+            wet[i] += buffer[i] / COMB_DELAYS_MS.len() as f64;
+        }
+    }
+
+    // Series all-pass filters diffuse the comb echoes
+    for &delay_ms in &ALLPASS_DELAYS_MS {
+        let delay = delay_samples(delay_ms);
+        let input = wet.clone();
+        for i in 0..input.len() {
+            let delayed_in = if i >= delay { input[i - delay] } else { 0.0 };
+            let delayed_out = if i >= delay { wet[i - delay] } else { 0.0 };
+            wet[i] = -ALLPASS_GAIN * input[i] + delayed_in + ALLPASS_GAIN * delayed_out;
+        }
+    }
+
+    *samples = dry.iter().zip(&wet)
+        .map(|(&d, &w)| (d * (1.0 - mix) + w * mix).round().clamp(i16::MIN as f64, i16::MAX as f64) as i16)
+        .collect();
+}
+
+// apply_reverb for interleaved f32 samples, run on each channel separately so
+// the delays are measured in frames
+pub(crate) fn apply_reverb_interleaved(samples: &[f32], channels: usize, mix: f64, sample_rate: u32) -> Vec<f32> {
+    let channels = channels.max(1);
+    let frames = samples.len() / channels;
+    let mut out = Vec::new();
+
+    for channel in 0..channels {
+        let mut pcm: Vec<i16> = (0..frames)
+            .map(|frame| (samples[frame * channels + channel].clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            .collect();
+        apply_reverb(&mut pcm, mix, sample_rate);
+
+        if out.is_empty() {
+            out = vec![0.0; pcm.len() * channels];
+        }
+        for (frame, &value) in pcm.iter().enumerate() {
+            out[frame * channels + channel] = value as f32 / i16::MAX as f32;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click() -> Vec<i16> {
+        let mut samples = vec![0i16; 4410];
+        samples[0] = 20000;
+        samples[1] = -15000;
+        samples
+    }
+
+    #[test]
+    fn test_zero_mix_leaves_buffer_unchanged() {
+        let original = click();
+        let mut samples = original.clone();
+        apply_reverb(&mut samples, 0.0, 44100);
+        assert_eq!(samples, original);
+    }
+
+    #[test]
+    fn test_interleaved_channels_stay_separate() {
+        // A click on the left only
+        let mut stereo = vec![0.0f32; 2000];
+        stereo[0] = 0.8;
+        let wet = apply_reverb_interleaved(&stereo, 2, 0.5, 8000);
+
+        assert_eq!(wet.len(), 2000 + 2 * 8000);
+        assert!(wet.iter().skip(1).step_by(2).all(|&s| s == 0.0));
+        assert!(wet.iter().step_by(2).skip(1000).any(|&s| s != 0.0));
+    }
+
+    #[test]
+    fn test_reverb_adds_a_tail() {
+        let original = click();
+        let mut samples = original.clone();
+        apply_reverb(&mut samples, 0.5, 44100);
+
+        assert_eq!(samples.len(), original.len() + 44100);
+        // Reflections keep sounding after the dry sound has ended
+        assert!(samples[original.len()..].iter().any(|&s| s != 0));
+        // ... and die away by the end of the tail
+        let last = &samples[samples.len() - 100..];
+        assert!(last.iter().all(|&s| s.unsigned_abs() < 200));
+    }
+}
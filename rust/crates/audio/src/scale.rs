@@ -0,0 +1,125 @@
+// Scale mapping for Conway's Steinway
+// Board columns map straight onto chromatic keys, so random boards sound
+// atonal. ScaleMapper snaps each key to the nearest key of a scale, keeping
+// it in the same register; columns that land on the same key merge.
+
+use std::collections::BTreeMap;
+
+use config::{Config, Scale};
+use log::warn;
+
+use crate::key_mapping::PIANO_KEY_COUNT;
+use crate::notes::note_name_to_key;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleMapper {
+    in_scale: [bool; 12], // Which pitch classes (0 = A, as in key numbers) belong to the scale
+}
+
+impl ScaleMapper {
+    // A mapper for `scale` rooted at a note name without octave ("C", "F#",
+    // "Bb"). Returns None if the root doesn't parse.
+    pub fn new(scale: Scale, root: &str) -> Option<Self> {
+        // Octave 1 holds every pitch class, so any valid root name is on the keyboard
+        let root_class = note_name_to_key(&format!("{}1", root.trim()))? % 12;
+        let mut in_scale = [false; 12];
+        for &interval in scale.intervals() {
+            in_scale[(root_class + interval) % 12] = true;
+        }
+        Some(ScaleMapper { in_scale })
+    }
+
+    // The configured mapper, or None when the scale is chromatic. A root that
+    // slipped past validation falls back to C.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if config.scale == Scale::Chromatic {
+            return None;
+        }
+        ScaleMapper::new(config.scale, &config.scale_root).or_else(|| {
+            warn!("Invalid scale root '{}'. Using C", config.scale_root);
+            ScaleMapper::new(config.scale, "C")
+        })
+    }
+
+    pub fn contains(&self, key: usize) -> bool {
+        self.in_scale[key % 12]
+    }
+
+    // The nearest scale key to `key`, preferring the one below on a tie
+    pub fn map_key(&self, key: usize) -> usize {
+        (0..12)
+            .flat_map(|distance| [key.checked_sub(distance), Some(key + distance)])
+            .flatten()
+            .find(|&candidate| candidate < PIANO_KEY_COUNT && self.contains(candidate))
+            .unwrap_or(key)
+    }
+
+    // Snap every key onto the scale; the result is sorted and free of duplicates
+    pub fn map_keys(&self, keys: &[usize]) -> Vec<usize> {
+        let mut mapped: Vec<usize> = keys.iter().map(|&key| self.map_key(key)).collect();
+        mapped.sort_unstable();
+        mapped.dedup();
+        mapped
+    }
+
+    // Snap keys with velocities onto the scale; merged keys keep the loudest velocity
+    pub fn map_notes(&self, notes: &[(usize, u8)]) -> Vec<(usize, u8)> {
+        let mut mapped = BTreeMap::new();
+        for &(key, velocity) in notes {
+            let loudest = mapped.entry(self.map_key(key)).or_insert(velocity);
+            *loudest = (*loudest).max(velocity);
+        }
+        mapped.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pentatonic_stays_in_scale() {
+        // C major pentatonic: C D E G A
+        let mapper = ScaleMapper::new(Scale::Pentatonic, "C").unwrap();
+        let allowed: Vec<usize> = ["C", "D", "E", "G", "A"].iter()
+            .map(|name| note_name_to_key(&format!("{}1", name)).unwrap() % 12)
+            .collect();
+
+        let all_keys: Vec<usize> = (0..PIANO_KEY_COUNT).collect();
+        let mapped = mapper.map_keys(&all_keys);
+        assert!(mapped.iter().all(|key| allowed.contains(&(key % 12))), "{:?}", mapped);
+        // Every scale key on the keyboard is still reachable
+        assert_eq!(mapped.len(), all_keys.iter().filter(|&&key| allowed.contains(&(key % 12))).count());
+    }
+
+    #[test]
+    fn test_keys_snap_to_nearest_scale_key() {
+        let mapper = ScaleMapper::new(Scale::Major, "C").unwrap();
+        let c4 = note_name_to_key("C4").unwrap();
+        let c_sharp4 = note_name_to_key("C#4").unwrap();
+        let f_sharp4 = note_name_to_key("F#4").unwrap();
+
+        assert_eq!(mapper.map_key(c4), c4);
+        // Ties go down
+        assert_eq!(mapper.map_key(c_sharp4), c4);
+        assert_eq!(mapper.map_key(f_sharp4), note_name_to_key("F4").unwrap());
+        // The top and bottom keys stay on the keyboard
+        assert!(mapper.map_key(0) < PIANO_KEY_COUNT);
+        assert!(mapper.map_key(PIANO_KEY_COUNT - 1) < PIANO_KEY_COUNT);
+    }
+
+    #[test]
+    fn test_merged_notes_keep_loudest_velocity() {
+        let mapper = ScaleMapper::new(Scale::Major, "C").unwrap();
+        let c4 = note_name_to_key("C4").unwrap();
+        assert_eq!(mapper.map_notes(&[(c4, 60), (c4 + 1, 100)]), vec![(c4, 100)]);
+    }
+
+    #[test]
+    fn test_roots_and_chromatic_config() {
+        assert_eq!(ScaleMapper::new(Scale::Minor, "A"), ScaleMapper::new(Scale::Major, "C"));
+        assert_eq!(ScaleMapper::new(Scale::Major, "Bb"), ScaleMapper::new(Scale::Major, "A#"));
+        assert_eq!(ScaleMapper::new(Scale::Major, "X"), None);
+        assert_eq!(ScaleMapper::from_config(&Config::default()), None);
+    }
+}
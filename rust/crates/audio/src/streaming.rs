@@ -0,0 +1,92 @@
+// Non-blocking playback for Conway's Steinway
+// StreamingPlayer hands every call to a background thread over a bounded
+// channel and returns at once, so the board can be simulated ahead of what is
+// being heard. The queue bound keeps it from running away: once it is full,
+// callers wait for the audio thread to catch up.
+
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::thread::{self, JoinHandle};
+
+use log::warn;
+
+use crate::audio_engine::AudioPlayer;
+
+// Calls that may be waiting to be played before callers block
+pub const STREAM_QUEUE_CAPACITY: usize = 64;
+
+enum PlayerEvent {
+    Keys(Vec<usize>),
+    Chord(Vec<usize>, u64),
+    Notes(Vec<(usize, u8)>),
+    Wait(u64),
+}
+
+pub struct StreamingPlayer {
+    sender: Option<SyncSender<PlayerEvent>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl StreamingPlayer {
+    // Start the audio thread. The player is built on that thread, since audio
+    // output streams generally can't be moved between threads.
+    pub fn spawn<F>(make_player: F) -> Self
+    where
+        F: FnOnce() -> Box<dyn AudioPlayer> + Send + 'static,
+    {
+        let (sender, receiver) = sync_channel(STREAM_QUEUE_CAPACITY);
+        let worker = thread::spawn(move || {
+            let player = make_player();
+            for event in receiver {
+                match event {
+                    PlayerEvent::Keys(keys) => player.play_piano_keys(&keys),
+                    PlayerEvent::Chord(keys, duration_ms) => player.play_chord(&keys, duration_ms),
+                    PlayerEvent::Notes(notes) => player.play_notes(&notes),
+                    PlayerEvent::Wait(duration_ms) => player.wait(duration_ms),
+                }
+            }
+        });
+
+        StreamingPlayer {
+            sender: Some(sender),
+            worker: Some(worker),
+        }
+    }
+
+    fn send(&self, event: PlayerEvent) {
+        let sent = self.sender.as_ref().map(|sender| sender.send(event).is_ok());
+        if sent != Some(true) {
+            warn!("Audio thread has stopped; dropping playback event");
+        }
+    }
+}
+
+impl AudioPlayer for StreamingPlayer {
+    fn play_piano_keys(&self, keys: &[usize]) {
+        self.send(PlayerEvent::Keys(keys.to_vec()));
+    }
+
+    fn play_chord(&self, keys: &[usize], duration_ms: u64) {
+        self.send(PlayerEvent::Chord(keys.to_vec(), duration_ms));
+    }
+
+    fn play_notes(&self, notes: &[(usize, u8)]) {
+        self.send(PlayerEvent::Notes(notes.to_vec()));
+    }
+
+    // Pauses are queued too, so the audio thread keeps the original pacing
+    fn wait(&self, duration_ms: u64) {
+        self.send(PlayerEvent::Wait(duration_ms));
+    }
+}
+
+// Play everything still queued, then stop the audio thread
+impl Drop for StreamingPlayer {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        if let Some(worker) = self.worker.take() {
+            if worker.join().is_err() {
+                warn!("Audio thread panicked");
+            }
+        }
+    }
+}
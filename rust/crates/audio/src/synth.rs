@@ -0,0 +1,188 @@
+// Synthesized piano tones for Conway's Steinway
+// Used when no recorded sample covers a key. A note is a sum of harmonics
+// under an amplitude envelope. By default the envelope and the harmonic
+// content follow the register: bass notes swell in slowly and ring for a long
+// time with plenty of overtones, treble notes strike fast and die away
+// quickly with a purer tone. A user-supplied ADSR envelope replaces those
+// curves with a single predictable shape for every key.
+
+use std::f64::consts::PI;
+
+use config::AdsrConfig;
+
+// Sample rate of synthesized notes (mono)
+pub const SYNTH_SAMPLE_RATE: u32 = 44100;
+// Peak level of a synthesized note, leaving headroom for chords
+const SYNTH_PEAK: f64 = 0.5;
+// Length of a synthesized note played in place of a sample
+pub const SYNTH_NOTE_DURATION_MS: u64 = 1500;
+// Register boundaries in Hz
+const BASS_LIMIT_HZ: f64 = 130.0;
+const TREBLE_LIMIT_HZ: f64 = 1000.0;
+
+// Built-in envelope for one register: attack and release in ms, and the time
+// constant of the exponential decay that follows the attack
+struct RegisterCurve {
+    attack_ms: f64,
+    decay_seconds: f64,
+    release_ms: f64,
+    harmonics: &'static [f64], // Relative level of each harmonic, fundamental first
+}
+
+const BASS_CURVE: RegisterCurve = RegisterCurve {
+    attack_ms: 8.0,
+    decay_seconds: 1.5,
+    release_ms: 200.0,
+    harmonics: &[1.0, 0.7, 0.5, 0.35, 0.25, 0.15],
+};
+const MIDDLE_CURVE: RegisterCurve = RegisterCurve {
+    attack_ms: 4.0,
+    decay_seconds: 0.8,
+    release_ms: 150.0,
+    harmonics: &[1.0, 0.5, 0.25, 0.12],
+};
+const TREBLE_CURVE: RegisterCurve = RegisterCurve {
+    attack_ms: 2.0,
+    decay_seconds: 0.35,
+    release_ms: 80.0,
+    harmonics: &[1.0, 0.3, 0.1],
+};
+
+fn register_curve(frequency: f64) -> &'static RegisterCurve {
+    if frequency < BASS_LIMIT_HZ {
+        &BASS_CURVE
+    } else if frequency < TREBLE_LIMIT_HZ {
+        &MIDDLE_CURVE
+    } else {
+        &TREBLE_CURVE
+    }
+}
+
+/// Synthesize a piano-like note of `duration_ms` at `frequency` Hz, shaped by
+/// the built-in curves for its register
+pub fn generate_piano_wave(frequency: f64, duration_ms: u64) -> Vec<f32> {
+    generate_piano_wave_with_envelope(frequency, duration_ms, None)
+}
+
+/// Synthesize a note like generate_piano_wave, but with `envelope` driving its
+/// level when given. The release fades out over the end of the note, so the
+/// result is always exactly `duration_ms` long.
+pub fn generate_piano_wave_with_envelope(frequency: f64, duration_ms: u64, envelope: Option<&AdsrConfig>) -> Vec<f32> {
+    let curve = register_curve(frequency);
+    let frames = (duration_ms * SYNTH_SAMPLE_RATE as u64 / 1000) as usize;
+    let level_sum: f64 = curve.harmonics.iter().sum();
+
+    (0..frames)
+        .map(|frame| {
+            let t = frame as f64 / SYNTH_SAMPLE_RATE as f64;
+            let t_ms = t * 1000.0;
+
+            let tone: f64 = curve.harmonics.iter().enumerate()
+                .filter(|(n, _)| frequency * (*n as f64 + 1.0) < SYNTH_SAMPLE_RATE as f64 / 2.0)
+                .map(|(n, &level)| {
+                    let harmonic = n as f64 + 1.0;
+                    // Without a user envelope, overtones fade faster than the fundamental
+                    let fade = match envelope {
+                        Some(_) => 1.0,
+                        None => (-t * harmonic / curve.decay_seconds).exp(),
+                    };
+                    level * fade * (2.0 * PI * frequency * harmonic * t).sin()
+                })
+                .sum();
+
+            let level = match envelope {
+                Some(adsr) => adsr_level(adsr, t_ms, duration_ms as f64),
+                None => register_level(curve, t_ms, duration_ms as f64),
+            };
+            (SYNTH_PEAK * level * tone / level_sum) as f32
+        })
+        .collect()
+}
+
+// Level (0.0-1.0) of a user envelope `t_ms` into a note of `duration_ms`
+fn adsr_level(adsr: &AdsrConfig, t_ms: f64, duration_ms: f64) -> f64 {
+    let attack = adsr.attack_ms as f64;
+    let decay = adsr.decay_ms as f64;
+    let sustain = adsr.sustain_level.clamp(0.0, 1.0);
+    let release = (adsr.release_ms as f64).min(duration_ms);
+
+    let held = if t_ms < attack {
+        t_ms / attack
+    } else if t_ms < attack + decay {
+        1.0 - (1.0 - sustain) * (t_ms - attack) / decay
+    } else {
+        sustain
+    };
+
+    let release_start = duration_ms - release;
+    if t_ms > release_start && release > 0.0 {
+        held * (duration_ms - t_ms) / release
+    } else {
+        held
+    }
+}
+
+// Level (0.0-1.0) of a register's built-in curve `t_ms` into a note of `duration_ms`
+fn register_level(curve: &RegisterCurve, t_ms: f64, duration_ms: f64) -> f64 {
+    let attack = (t_ms / curve.attack_ms).min(1.0);
+    let release = curve.release_ms.min(duration_ms);
+    let release_gain = if release > 0.0 {
+        ((duration_ms - t_ms) / release).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    attack * release_gain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Loudest sample in each `window_ms` slice of a note
+    fn window_peaks(wave: &[f32], window_ms: u64) -> Vec<f32> {
+        let window = (window_ms * SYNTH_SAMPLE_RATE as u64 / 1000) as usize;
+        wave.chunks(window)
+            .filter(|chunk| chunk.len() == window)
+            .map(|chunk| chunk.iter().fold(0.0f32, |peak, s| peak.max(s.abs())))
+            .collect()
+    }
+
+    #[test]
+    fn test_wave_has_the_requested_length() {
+        assert_eq!(generate_piano_wave(440.0, 500).len(), 22050);
+        assert!(generate_piano_wave(440.0, 500).iter().all(|s| s.abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_pure_attack_ramps_up() {
+        let attack_only = AdsrConfig { attack_ms: 200, decay_ms: 0, sustain_level: 1.0, release_ms: 0 };
+        let wave = generate_piano_wave_with_envelope(440.0, 200, Some(&attack_only));
+
+        // 10 ms windows hold several whole cycles, so each window's peak
+        // tracks the envelope
+        let peaks = window_peaks(&wave, 10);
+        assert_eq!(peaks.len(), 20);
+        assert!(peaks.windows(2).all(|pair| pair[1] >= pair[0]), "attack should only rise: {:?}", peaks);
+        assert!(peaks[0] < peaks[19] * 0.2);
+    }
+
+    #[test]
+    fn test_envelope_sustains_and_releases() {
+        let adsr = AdsrConfig { attack_ms: 10, decay_ms: 50, sustain_level: 0.5, release_ms: 100 };
+        assert_eq!(adsr_level(&adsr, 5.0, 500.0), 0.5);
+        assert_eq!(adsr_level(&adsr, 10.0, 500.0), 1.0);
+        assert_eq!(adsr_level(&adsr, 200.0, 500.0), 0.5);
+        assert_eq!(adsr_level(&adsr, 450.0, 500.0), 0.25);
+        assert_eq!(adsr_level(&adsr, 500.0, 500.0), 0.0);
+    }
+
+    #[test]
+    fn test_bass_rings_longer_than_treble() {
+        // Compare how much of the opening level is left after half a second
+        let sustain_ratio = |frequency: f64| {
+            let peaks = window_peaks(&generate_piano_wave(frequency, 1000), 50);
+            peaks[10] / peaks[1]
+        };
+        assert!(sustain_ratio(65.0) > sustain_ratio(2000.0));
+    }
+}
@@ -0,0 +1,173 @@
+// Note timeline for Conway's Steinway
+// Turns the keys played each generation into timed note events for offline
+// consumers such as MIDI export, independent of live playback
+
+use config::Config;
+
+use crate::audio_engine::ChordDetector;
+
+/// A single note with absolute start time and length, both in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoteEvent {
+    pub key: usize,
+    pub start_ms: u64,
+    pub duration_ms: u64,
+}
+
+impl NoteEvent {
+    pub fn end_ms(&self) -> u64 {
+        self.start_ms + self.duration_ms
+    }
+}
+
+/// Lay out each generation's keys on a timeline. Generations are spaced by the
+/// effective step delay, following any tempo ramp; detected chords sound together for `chord_duration_ms`,
+/// while individual notes are staggered by `gap_ms` and last `note_duration_ms`.
+pub fn build_timeline(generations: &[Vec<usize>], config: &Config) -> Vec<NoteEvent> {
+    let chord_detector = ChordDetector::new(config.chord_cluster_size, config.chord_cluster_gap);
+    let mut events = Vec::new();
+
+    let starts = generation_starts_ms(generations.len(), config);
+    for (keys, &generation_start) in generations.iter().zip(&starts) {
+
+        if config.detect_chords && chord_detector.is_chord_pattern(keys) {
+            events.extend(keys.iter().map(|&key| NoteEvent {
+                key,
+                start_ms: generation_start,
+                duration_ms: config.chord_duration_ms,
+            }));
+        } else {
+            events.extend(keys.iter().enumerate().map(|(i, &key)| NoteEvent {
+                key,
+                start_ms: generation_start + i as u64 * config.gap_ms,
+                duration_ms: config.note_duration_ms,
+            }));
+        }
+    }
+
+    events
+}
+
+/// Start of each of `generation_count` generations, followed by the end of the
+/// last one. Each step lasts its delay from Config::get_effective_delay_at.
+pub fn generation_starts_ms(generation_count: usize, config: &Config) -> Vec<u64> {
+    let total = generation_count as u32;
+    let mut starts = Vec::with_capacity(generation_count + 1);
+    let mut elapsed = 0;
+    starts.push(elapsed);
+    for step in 0..total {
+        elapsed += config.get_effective_delay_at(step, total);
+        starts.push(elapsed);
+    }
+    starts
+}
+
+/// Length of a performance of `generation_count` generations, including the
+/// final step's delay
+pub fn timeline_length_ms(generation_count: usize, config: &Config) -> u64 {
+    generation_starts_ms(generation_count, config).last().copied().unwrap_or(0)
+}
+
+/// When the visual event for something scheduled at `note_ms` should be emitted.
+/// Audio reaches the listener after the output buffer's latency, so visuals are
+/// held back by `av_sync_ms` to line up with what is heard.
+pub fn visual_timestamp_ms(note_ms: u64, av_sync_ms: u64) -> u64 {
+    note_ms + av_sync_ms
+}
+
+/// The timeline as visual events: the same notes, each shifted by the sync offset
+pub fn visual_timeline(events: &[NoteEvent], av_sync_ms: u64) -> Vec<NoteEvent> {
+    events.iter()
+        .map(|event| NoteEvent {
+            start_ms: visual_timestamp_ms(event.start_ms, av_sync_ms),
+            ..*event
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_and_chord_durations() {
+        let config = Config {
+            step_delay_ms: 1000,
+            note_duration_ms: 180,
+            chord_duration_ms: 420,
+            gap_ms: 40,
+            ..Config::default()
+        };
+
+        let generations = vec![
+            vec![10, 60],       // Individual notes
+            vec![],             // Silence
+            vec![24, 28, 31],   // C major triad
+        ];
+
+        let events = build_timeline(&generations, &config);
+        assert_eq!(events.len(), 5);
+
+        // Single notes use the note duration and are staggered by the gap
+        assert_eq!(events[0], NoteEvent { key: 10, start_ms: 0, duration_ms: 180 });
+        assert_eq!(events[1], NoteEvent { key: 60, start_ms: 40, duration_ms: 180 });
+
+        // The chord starts together at its generation and uses the chord duration
+        for event in &events[2..] {
+            assert_eq!(event.start_ms, 2000);
+            assert_eq!(event.duration_ms, 420);
+            assert_eq!(event.end_ms(), 2420);
+        }
+    }
+
+    #[test]
+    fn test_generations_follow_tempo_ramp() {
+        let config = Config {
+            tempo_start_bpm: Some(60.0),
+            tempo_end_bpm: Some(120.0),
+            ..Config::default()
+        };
+
+        // 60, 90 and 120 BPM: 500 ms, then 333 ms, then 250 ms per step
+        assert_eq!(generation_starts_ms(3, &config), vec![0, 500, 833, 1083]);
+        assert_eq!(timeline_length_ms(3, &config), 1083);
+
+        let events = build_timeline(&[vec![10], vec![20], vec![30]], &config);
+        let starts: Vec<u64> = events.iter().map(|event| event.start_ms).collect();
+        assert_eq!(starts, vec![0, 500, 833]);
+    }
+
+    #[test]
+    fn test_chords_use_note_duration_without_detection() {
+        let config = Config {
+            detect_chords: false,
+            note_duration_ms: 150,
+            ..Config::default()
+        };
+
+        let events = build_timeline(&[vec![24, 28, 31]], &config);
+        assert!(events.iter().all(|event| event.duration_ms == 150));
+    }
+
+    #[test]
+    fn test_visual_events_shifted_by_sync_offset() {
+        let config = Config {
+            step_delay_ms: 500,
+            av_sync_ms: 120,
+            ..Config::default()
+        };
+
+        let notes = build_timeline(&[vec![10, 60], vec![], vec![40]], &config);
+        let visuals = visual_timeline(&notes, config.av_sync_ms);
+
+        assert_eq!(visuals.len(), notes.len());
+        for (note, visual) in notes.iter().zip(&visuals) {
+            assert_eq!(visual.start_ms, note.start_ms + 120);
+            assert_eq!(visual.key, note.key);
+            assert_eq!(visual.duration_ms, note.duration_ms);
+        }
+
+        // No offset leaves the timing alone
+        assert_eq!(visual_timeline(&notes, 0), notes);
+    }
+}
@@ -2,9 +2,16 @@
 //
 // This module provides shared utilities for finding paths relative to the repository root
 
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::env;
 
+use log::warn;
+
+/// Environment variable pointing straight at the repository root, for CI,
+/// containers and installs where the root can't be found by looking around
+pub const ROOT_ENV: &str = "CONWAYS_STEINWAY_ROOT";
+
 /// Represents paths to important directories in the repository
 pub struct RepoStructure {
     /// Path to the repository root
@@ -26,7 +33,11 @@ impl Default for RepoStructure {
 impl RepoStructure {
     /// Create a new RepoStructure by finding the repository root
     pub fn new() -> Self {
-        let root = find_repo_root();
+        Self::with_root(find_repo_root())
+    }
+
+    /// Create a RepoStructure for a known repository root
+    pub fn with_root(root: PathBuf) -> Self {
         let static_dir = root.join("static");
         let logs_dir = root.join("logs");
         let config_dir = root.join("config");
@@ -62,9 +73,32 @@ impl RepoStructure {
 
 /// Find the repository root directory
 ///
-/// This function tries to find the repository root by looking for certain marker directories
+/// CONWAYS_STEINWAY_ROOT wins when it names an existing directory. Otherwise
+/// this function tries to find the repository root by looking for certain marker directories
 /// like "static", "rust", and "python" that indicate the root of the project.
 pub fn find_repo_root() -> PathBuf {
+    find_repo_root_from(env::var_os(ROOT_ENV))
+}
+
+/// The repository root given the value of the override variable, if it is set
+fn find_repo_root_from(value: Option<OsString>) -> PathBuf {
+    root_override(value).unwrap_or_else(search_repo_root)
+}
+
+/// The root named by the override variable, if it is set and exists
+fn root_override(value: Option<OsString>) -> Option<PathBuf> {
+    let root = PathBuf::from(value.filter(|value| !value.is_empty())?);
+    if root.is_dir() {
+        Some(root)
+    } else {
+        warn!("{} is set to {}, which is not a directory; searching for the repository root instead",
+              ROOT_ENV, root.display());
+        None
+    }
+}
+
+/// Walk up from the current directory to the first one that looks like the root
+fn search_repo_root() -> PathBuf {
     // Start with the current directory
     let mut current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
     
@@ -125,6 +159,33 @@ mod tests {
         assert!(root.join("static").join("audio").is_dir(), "Static directory should contain an audio directory");
     }
     
+    #[test]
+    fn test_root_override() {
+        let dir = env::temp_dir();
+        assert_eq!(root_override(Some(dir.clone().into_os_string())), Some(dir.clone()));
+
+        // Missing or unset overrides fall back to the search
+        assert_eq!(root_override(Some(dir.join("no-such-steinway-root").into_os_string())), None);
+        assert_eq!(root_override(Some(OsString::new())), None);
+        assert_eq!(root_override(None), None);
+    }
+
+    #[test]
+    fn test_env_var_short_circuits_search() {
+        // A different spelling of the real root than the search would produce
+        let root = search_repo_root().join("static").join("..");
+        assert_eq!(find_repo_root_from(Some(root.clone().into_os_string())), root);
+        assert_eq!(find_repo_root_from(None), search_repo_root());
+    }
+
+    #[test]
+    fn test_with_root() {
+        let repo = RepoStructure::with_root(PathBuf::from("/opt/steinway"));
+        assert_eq!(repo.root, PathBuf::from("/opt/steinway"));
+        assert_eq!(repo.audio_sample_path("C4.wav"), PathBuf::from("/opt/steinway/static/audio/C4.wav"));
+        assert_eq!(repo.default_log_file(), PathBuf::from("/opt/steinway/logs/backend/conways_steinway.log"));
+    }
+
     #[test]
     fn test_path_from_repo_root() {
         let path = path_from_repo_root("static/audio");
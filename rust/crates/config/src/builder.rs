@@ -0,0 +1,76 @@
+// Conway's Steinway Configuration Builder
+//
+// Fluent construction of a Config for library code and tests, validated on build.
+
+use crate::types::{BoardType, Config, ConfigError, GenerationLimit};
+
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    // Start from the default configuration
+    pub fn new() -> Self {
+        ConfigBuilder {
+            config: Config::default(),
+        }
+    }
+
+    pub fn board_type(mut self, board_type: BoardType) -> Self {
+        self.config.board_type = board_type;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    pub fn tempo_bpm(mut self, bpm: f64) -> Self {
+        self.config.tempo_bpm = Some(bpm);
+        self
+    }
+
+    pub fn silent(mut self, silent: bool) -> Self {
+        self.config.silent = silent;
+        self
+    }
+
+    pub fn generations(mut self, generations: GenerationLimit) -> Self {
+        self.config.generations = generations;
+        self
+    }
+
+    pub fn step_delay_ms(mut self, delay: u64) -> Self {
+        self.config.step_delay_ms = delay;
+        self
+    }
+
+    pub fn volume(mut self, volume: f64) -> Self {
+        self.config.volume = volume;
+        self
+    }
+
+    pub fn alive_probability(mut self, probability: f64) -> Self {
+        self.config.alive_probability = probability;
+        self
+    }
+
+    pub fn log_level(mut self, level: &str) -> Self {
+        self.config.log_level = level.to_string();
+        self
+    }
+
+    // Validate and return the finished configuration
+    pub fn build(self) -> Result<Config, Vec<ConfigError>> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+impl Config {
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+    }
+}
@@ -1,8 +1,10 @@
 // Config module library for Conway's Steinway
 // Provides configuration loading and validation functionality
 
+pub mod builder;
 pub mod loader;
 pub mod types;
 
 // Re-export commonly used types for convenience
-pub use types::{Config, BoardType, GenerationLimit};
+pub use builder::ConfigBuilder;
+pub use types::{AdsrConfig, Config, ConfigError, BoardType, GenerationLimit, KeyRangePolicy, Scale, SonifyRow, VelocityCurve};
@@ -128,3 +128,62 @@ fn test_audio_settings() {
     
     assert_eq!(delay_config.get_effective_delay(), 250);
 }
+
+#[test]
+fn test_fur_elise_board_defaults() {
+    // The curated experience replaces user generations and fills in the tempo
+    let mut curated = Config {
+        board_type: BoardType::FurElise,
+        generations: GenerationLimit::Limited(10),
+        ..Default::default()
+    };
+    curated.apply_board_defaults();
+    assert!(matches!(curated.generations, GenerationLimit::Limited(80)));
+    assert_eq!(curated.tempo_bpm, Some(126.0));
+
+    // With the override flag the user's values are preserved
+    let mut overridden = Config {
+        board_type: BoardType::FurElise,
+        generations: GenerationLimit::Limited(10),
+        tempo_bpm: Some(90.0),
+        allow_fur_elise_override: true,
+        ..Default::default()
+    };
+    overridden.apply_board_defaults();
+    assert!(matches!(overridden.generations, GenerationLimit::Limited(10)));
+    assert_eq!(overridden.tempo_bpm, Some(90.0));
+
+    // Other board types are untouched
+    let mut random = Config {
+        generations: GenerationLimit::Limited(10),
+        ..Default::default()
+    };
+    random.apply_board_defaults();
+    assert!(matches!(random.generations, GenerationLimit::Limited(10)));
+    assert!(random.tempo_bpm.is_none());
+}
+
+#[test]
+fn test_pattern_file_selects_custom_board() {
+    let config = Config::from_args(["conways-steinway", "--pattern-file", "glider.rle"]).unwrap();
+    assert!(matches!(config.board_type, BoardType::Custom(ref path) if path == std::path::Path::new("glider.rle")));
+
+    // Without the option the board type is left alone
+    let config = Config::from_args(["conways-steinway", "--board-type", "static"]).unwrap();
+    assert!(matches!(config.board_type, BoardType::Static));
+}
+
+#[test]
+fn test_custom_board_survives_save_and_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("custom.cfg");
+    let config = Config {
+        board_type: BoardType::Custom("patterns/pulsar.cells".into()),
+        ..Default::default()
+    };
+    config.save_to_file(&path).unwrap();
+
+    let mut loaded = Config::default();
+    loaded.load_from_file(&path).unwrap();
+    assert!(matches!(loaded.board_type, BoardType::Custom(ref p) if p == std::path::Path::new("patterns/pulsar.cells")));
+}
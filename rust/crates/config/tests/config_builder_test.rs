@@ -0,0 +1,45 @@
+// Integration tests for the configuration builder
+// Verifies fluent construction matches struct literals and validates on build
+
+use config::{BoardType, Config, ConfigBuilder, ConfigError, GenerationLimit};
+
+#[test]
+fn test_builder_matches_struct_literal() {
+    let built = Config::builder()
+        .board_type(BoardType::Soup)
+        .seed(42)
+        .tempo_bpm(96.0)
+        .silent(true)
+        .generations(GenerationLimit::Limited(20))
+        .build()
+        .expect("Valid configuration should build");
+
+    let literal = Config {
+        board_type: BoardType::Soup,
+        seed: Some(42),
+        tempo_bpm: Some(96.0),
+        silent: true,
+        generations: GenerationLimit::Limited(20),
+        ..Default::default()
+    };
+
+    // Config has no PartialEq, so compare the full debug representation
+    assert_eq!(format!("{:?}", built), format!("{:?}", literal));
+}
+
+#[test]
+fn test_builder_defaults() {
+    let built = ConfigBuilder::new().build().unwrap();
+    assert_eq!(format!("{:?}", built), format!("{:?}", Config::default()));
+}
+
+#[test]
+fn test_builder_runs_validation() {
+    let result = Config::builder()
+        .board_type(BoardType::Random)
+        .tempo_bpm(0.0)
+        .build();
+
+    let errors = result.expect_err("Zero tempo should fail validation");
+    assert_eq!(errors, vec![ConfigError::InvalidTempo(0.0)]);
+}
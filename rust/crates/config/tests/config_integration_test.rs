@@ -1,6 +1,6 @@
 // Integration test for Config module
 
-use config::{Config, BoardType, GenerationLimit};
+use config::{Config, BoardType, GenerationLimit, KeyRangePolicy, Scale, VelocityCurve};
 
 #[test]
 fn test_config_defaults() {
@@ -49,10 +49,165 @@ fn test_tempo_to_delay_conversion() {
     assert_eq!(delay_60_bpm, delay_120_bpm * 2);
     
     // Test effective delay with and without tempo
-    let mut config = Config::default();
-    config.step_delay_ms = 100;
+    let mut config = Config {
+        step_delay_ms: 100,
+        ..Config::default()
+    };
     assert_eq!(config.get_effective_delay(), 100); // Uses step_delay_ms
     
     config.tempo_bpm = Some(120.0);
     assert_eq!(config.get_effective_delay(), delay_120_bpm); // Uses tempo
 }
+
+#[test]
+fn test_save_and_load_round_trip() {
+    // The config crate is the single Config implementation; what it saves it
+    // must be able to load back unchanged
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("round_trip.cfg");
+
+    let original = Config {
+        board_type: BoardType::Static,
+        generations: GenerationLimit::Limited(42),
+        step_delay_ms: 300,
+        tempo_bpm: Some(96.0),
+        seed: Some(7),
+        ..Config::default()
+    };
+    original.save_to_file(&path).unwrap();
+
+    let mut loaded = Config::default();
+    loaded.load_from_file(&path).unwrap();
+
+    assert!(matches!(loaded.board_type, BoardType::Static));
+    assert!(matches!(loaded.generations, GenerationLimit::Limited(42)));
+    assert_eq!(loaded.step_delay_ms, 300);
+    assert_eq!(loaded.tempo_bpm, Some(96.0));
+    assert_eq!(loaded.seed, Some(7));
+    assert_eq!(loaded.silent, original.silent);
+}
+
+#[test]
+fn test_layered_config_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.cfg");
+    let machine = dir.path().join("machine.cfg");
+
+    std::fs::write(&base, "[core]\nboard_type = static\ngenerations = 10\nstep_delay_ms = 300\n\n[audio]\nvolume = 0.5\n").unwrap();
+    std::fs::write(&machine, "[core]\ngenerations = 20\n\n[audio]\nnote_duration_ms = 150\n").unwrap();
+
+    let mut config = Config::default();
+    config.load_from_files(&[base, machine.clone()]).unwrap();
+
+    // The later file wins on conflicts...
+    assert!(matches!(config.generations, GenerationLimit::Limited(20)));
+    // ...and keys set in only one file are all kept
+    assert!(matches!(config.board_type, BoardType::Static));
+    assert_eq!(config.step_delay_ms, 300);
+    assert!((config.volume - 0.5).abs() < 1e-9);
+    assert_eq!(config.note_duration_ms, 150);
+
+    assert_eq!(config.config_file, Some(machine));
+}
+
+#[test]
+fn test_toml_yaml_and_ini_files_load_identically() {
+    let dir = tempfile::tempdir().unwrap();
+    let ini = dir.path().join("settings.cfg");
+    let toml = dir.path().join("settings.toml");
+    let yaml = dir.path().join("settings.yaml");
+
+    std::fs::write(&ini, "[core]\nboard_type = static\ngenerations = 25\nstep_delay_ms = 150\n\n\
+                          [audio]\nvolume = 0.4\nmax_polyphony = 6\n\n[board]\nheight = 30\n\n\
+                          [rust]\nlog_path_prefix = logs/test\n").unwrap();
+    // Sections work as in the INI file, and fields can also be set directly
+    std::fs::write(&toml, "board_type = \"static\"\ngenerations = { limited = 25 }\nstep_delay_ms = 150\n\
+                           log_file_path = \"logs/test/conways_steinway.log\"\n\n\
+                           [audio]\nvolume = 0.4\nmax_polyphony = 6\n\n[board]\nheight = 30\n").unwrap();
+    std::fs::write(&yaml, "board_type: Static\ngenerations:\n  Limited: 25\nstep_delay_ms: 150\n\
+                           volume: 0.4\nmax_polyphony: 6\nboard_height: 30\n\
+                           log_file_path: logs/test/conways_steinway.log\n").unwrap();
+
+    let load = |path: &std::path::PathBuf| {
+        let mut config = Config::default();
+        config.load_from_file(path).unwrap();
+        serde_json::to_value(&config).unwrap()
+    };
+    let from_ini = load(&ini);
+    assert_eq!(from_ini["board_height"], 30);
+    assert_eq!(load(&toml), from_ini);
+    assert_eq!(load(&yaml), from_ini);
+}
+
+#[test]
+fn test_structured_files_only_override_the_keys_they_set() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.cfg");
+    let overrides = dir.path().join("overrides.yml");
+
+    std::fs::write(&base, "[core]\nstep_delay_ms = 300\n\n[audio]\nvolume = 0.5\n").unwrap();
+    std::fs::write(&overrides, "audio:\n  volume: 0.25\n").unwrap();
+
+    let mut config = Config::default();
+    config.load_from_files(&[base, overrides]).unwrap();
+    assert_eq!(config.step_delay_ms, 300);
+    assert!((config.volume - 0.25).abs() < 1e-9);
+
+    // Malformed files are reported rather than ignored
+    let broken = dir.path().join("broken.toml");
+    std::fs::write(&broken, "volume = = 1").unwrap();
+    assert!(Config::default().load_from_file(&broken).is_err());
+}
+
+#[test]
+fn test_structured_files_accept_ini_spellings() {
+    let dir = tempfile::tempdir().unwrap();
+    let ini = dir.path().join("settings.cfg");
+    let toml = dir.path().join("settings.toml");
+    let yaml = dir.path().join("settings.yaml");
+
+    std::fs::write(&ini, "[core]\ngenerations = 0\n\n[audio]\nscale = pentatonic\nvelocity_curve = exponential\n\
+                          out_of_range = wrap\nreverb_mix = 1.5\n\n[logging]\nlevel = DEBUG\n\n\
+                          [logging.module]\naudio = TRACE\n").unwrap();
+    // Plain lowercase names and counts, as written in the INI file
+    std::fs::write(&toml, "generations = 0\nscale = \"pentatonic\"\nvelocity_curve = \"exponential\"\n\
+                           out_of_range = \"wrap\"\nreverb_mix = 1.5\nlog_level = \"DEBUG\"\n\
+                           log_file_path = \"logs/rust/conways_steinway.log\"\n\
+                           log_module_levels = { audio = \"TRACE\" }\n").unwrap();
+    std::fs::write(&yaml, "core:\n  generations: 0\naudio:\n  scale: pentatonic\n  velocity_curve: exponential\n\
+                           out_of_range: wrap\nreverb_mix: 1.5\nlog_level: DEBUG\n\
+                           log_file_path: logs/rust/conways_steinway.log\n\
+                           log_module_levels:\n  audio: TRACE\n").unwrap();
+
+    let load = |path: &std::path::PathBuf| {
+        let mut config = Config::default();
+        config.load_from_file(path).unwrap();
+        config
+    };
+    let from_ini = load(&ini);
+    assert!(matches!(from_ini.generations, GenerationLimit::Unlimited));
+    assert_eq!(from_ini.scale, Scale::Pentatonic);
+    assert_eq!(from_ini.velocity_curve, VelocityCurve::Exponential);
+    assert_eq!(from_ini.out_of_range, KeyRangePolicy::Wrap);
+    assert_eq!(from_ini.reverb_mix, 1.0);
+    assert_eq!(from_ini.log_level, "debug");
+    assert_eq!(from_ini.log_module_levels["audio"], "trace");
+
+    let expected = serde_json::to_value(&from_ini).unwrap();
+    assert_eq!(serde_json::to_value(load(&toml)).unwrap(), expected);
+    assert_eq!(serde_json::to_value(load(&yaml)).unwrap(), expected);
+
+    // A count of generations above zero is a limit
+    std::fs::write(&toml, "generations = 12\n").unwrap();
+    assert!(matches!(load(&toml).generations, GenerationLimit::Limited(12)));
+}
+
+#[test]
+fn test_repository_toml_config_loads() {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../../config/conways_steinway.toml");
+    let mut config = Config::default();
+    config.load_from_file(&path).unwrap();
+    assert!(matches!(config.board_type, BoardType::Random));
+    assert!(matches!(config.generations, GenerationLimit::Unlimited));
+    assert_eq!(config.board_height, 40);
+}
@@ -0,0 +1,66 @@
+// Benchmarks for board evolution
+// Run with: cargo bench -p life
+//
+// Boards are built in the batch setup so only the evolution itself is timed.
+// Every scenario is deterministic (fixed seeds, generation-derived injected
+// rows), so timings can be compared across changes to the board storage.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use life::{GameBoard, GameOfLife};
+
+// Seed and density for the dense random board
+const DENSE_SEED: u64 = 42;
+const DENSE_PROBABILITY: f64 = 0.5;
+
+// Steps taken by the main-loop benchmark
+const ADVANCE_STEPS: usize = 1000;
+
+type BoardBuilder = fn() -> GameOfLife;
+
+// The empty board is the floor, the complex board is the default run and the
+// dense board the worst case
+const BOARDS: [(&str, BoardBuilder); 3] = [
+    ("next_generation (empty board)", GameOfLife::new),
+    ("next_generation (complex board)", GameBoard::create_complex_board),
+    ("next_generation (dense random board)", dense_board),
+];
+
+fn dense_board() -> GameOfLife {
+    GameBoard::create_random_board_seeded(DENSE_SEED, DENSE_PROBABILITY)
+}
+
+// One generation on each kind of board
+fn bench_next_generation(c: &mut Criterion) {
+    for (name, make_board) in BOARDS {
+        c.bench_function(name, |b| {
+            b.iter_batched(
+                make_board,
+                |mut game| {
+                    game.next_generation();
+                    game
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+// The main loop's step: read the bottom row, scroll, inject and evolve
+fn bench_bottom_row_and_advance(c: &mut Criterion) {
+    c.bench_function("get_bottom_row_and_advance x1000 (complex board)", |b| {
+        b.iter_batched(
+            GameBoard::create_complex_board,
+            |mut game| {
+                let mut notes = 0;
+                for _ in 0..ADVANCE_STEPS {
+                    notes += GameBoard::get_bottom_row_and_advance(&mut game).len();
+                }
+                black_box(notes)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_next_generation, bench_bottom_row_and_advance);
+criterion_main!(benches);
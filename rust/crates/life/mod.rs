@@ -1,6 +0,0 @@
-// Life module for Conway's Steinway
-// Re-exports game of life components
-
-mod game_board;
-
-pub use game_board::GameBoard;
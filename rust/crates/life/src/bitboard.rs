@@ -0,0 +1,165 @@
+// Packed cell storage for GameOfLife
+// Live cells are one bit each in a flat bitset indexed by row * BOARD_WIDTH + col,
+// so a default 88x40 board fits in 55 words. Dying states ("Generations" mode)
+// need a counter per cell and are kept in a separate byte array that is only
+// allocated once a dying cell appears.
+
+use std::hash::{Hash, Hasher};
+
+use crate::{Cell, BOARD_WIDTH, ROW_MASK};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct BitBoard {
+    height: usize,
+    alive: Vec<u64>,
+    // Decay states left for each dying cell (0 = not dying); empty until needed
+    dying: Vec<u8>,
+}
+
+#[inline]
+fn index(row: usize, col: usize) -> usize {
+    row * BOARD_WIDTH + col
+}
+
+impl BitBoard {
+    pub(crate) fn new(height: usize) -> Self {
+        BitBoard {
+            height,
+            alive: vec![0; (BOARD_WIDTH * height).div_ceil(64)],
+            dying: Vec::new(),
+        }
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    // Callers keep row and col on the board
+    #[inline]
+    pub(crate) fn is_alive(&self, row: usize, col: usize) -> bool {
+        let i = index(row, col);
+        self.alive[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    pub(crate) fn get(&self, row: usize, col: usize) -> Cell {
+        if self.is_alive(row, col) {
+            return Cell::Alive;
+        }
+        match self.dying.get(index(row, col)) {
+            Some(&left) if left > 0 => Cell::Dying(left),
+            _ => Cell::Dead,
+        }
+    }
+
+    pub(crate) fn set(&mut self, row: usize, col: usize, state: Cell) {
+        let i = index(row, col);
+        let bit = 1 << (i % 64);
+        match state {
+            Cell::Alive => self.alive[i / 64] |= bit,
+            _ => self.alive[i / 64] &= !bit,
+        }
+
+        let left = match state {
+            Cell::Dying(left) => left,
+            _ => 0,
+        };
+        if left > 0 && self.dying.is_empty() {
+            self.dying = vec![0; BOARD_WIDTH * self.height];
+        }
+        if let Some(slot) = self.dying.get_mut(i) {
+            *slot = left;
+        }
+    }
+
+    // Live cells of one row as bits, column 0 in bit 0
+    pub(crate) fn row_bits(&self, row: usize) -> u128 {
+        let start = index(row, 0);
+        let (w, offset) = (start / 64, start % 64);
+        let word = |i: usize| self.alive.get(i).copied().unwrap_or(0) as u128;
+
+        let mut bits = (word(w) | word(w + 1) << 64) >> offset;
+        if offset > 0 {
+            bits |= word(w + 2) << (128 - offset);
+        }
+        bits & ROW_MASK
+    }
+
+    // Replace the live cells of one row; dying states are left alone
+    pub(crate) fn set_row_bits(&mut self, row: usize, bits: u128) {
+        let start = index(row, 0);
+        let end = start + BOARD_WIDTH;
+
+        for w in start / 64..end.div_ceil(64) {
+            let lo = start.max(w * 64);
+            let hi = end.min(w * 64 + 64);
+            let width_mask = ((1u128 << (hi - lo)) - 1) as u64;
+            let shift = lo - w * 64;
+            let value = ((bits >> (lo - start)) as u64) & width_mask;
+            self.alive[w] = (self.alive[w] & !(width_mask << shift)) | (value << shift);
+        }
+    }
+
+    pub(crate) fn has_dying(&self) -> bool {
+        !self.dying.is_empty()
+    }
+
+    pub(crate) fn population(&self) -> usize {
+        self.alive.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+// Boards with the same cells hash the same whether or not the dying array
+// has been allocated
+impl Hash for BitBoard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.alive.hash(state);
+        if self.dying.iter().any(|&left| left > 0) {
+            self.dying.hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BOARD_HEIGHT;
+
+    #[test]
+    fn test_cells_round_trip_across_word_boundaries() {
+        let mut board = BitBoard::new(BOARD_HEIGHT);
+        // Index 63 and 64 sit either side of the first word boundary
+        let cells = [(0, 63), (0, 64), (BOARD_HEIGHT - 1, BOARD_WIDTH - 1)];
+        for &(row, col) in &cells {
+            board.set(row, col, Cell::Alive);
+        }
+        for &(row, col) in &cells {
+            assert_eq!(board.get(row, col), Cell::Alive);
+        }
+        assert_eq!(board.population(), 3);
+
+        board.set(0, 64, Cell::Dying(2));
+        assert_eq!(board.get(0, 64), Cell::Dying(2));
+        assert_eq!(board.population(), 2);
+
+        board.set(0, 64, Cell::Dead);
+        assert_eq!(board.get(0, 64), Cell::Dead);
+    }
+
+    #[test]
+    fn test_row_bits_match_cells() {
+        let mut board = BitBoard::new(BOARD_HEIGHT);
+        // Row 5 starts at bit 440, mid-word, and spans two word boundaries
+        let pattern: u128 = 0xA5_5A_F0_0F_C3_3C_81_18_E7_7E_99;
+        board.set_row_bits(5, pattern);
+
+        assert_eq!(board.row_bits(5), pattern & ROW_MASK);
+        for col in 0..BOARD_WIDTH {
+            assert_eq!(board.is_alive(5, col), pattern >> col & 1 == 1, "column {}", col);
+        }
+        assert_eq!(board.row_bits(4), 0);
+        assert_eq!(board.row_bits(6), 0);
+
+        board.set_row_bits(BOARD_HEIGHT - 1, ROW_MASK);
+        assert_eq!(board.population(), (pattern & ROW_MASK).count_ones() as usize + BOARD_WIDTH);
+    }
+}
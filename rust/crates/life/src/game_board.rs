@@ -1,32 +1,283 @@
-use crate::{GameOfLife, Cell, BOARD_WIDTH, BOARD_HEIGHT};
-use log::{debug, trace};
+use crate::{GameOfLife, Cell, BOARD_WIDTH};
+use log::{debug, trace, warn};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Sixteenth and eighth notes at the Für Elise tempo of 126 BPM, where one beat
+// is a quarter note (see Config::tempo_to_delay_ms)
+const SIXTEENTH_MS: u64 = 119;
+const EIGHTH_MS: u64 = 238;
+
+/// The Für Elise melody that `create_fur_elise_board` approximates, as
+/// (piano key, duration in milliseconds) pairs. Keys are numbered from 0 for
+/// A0, so A4 is key 48 and middle C (C4) is key 39.
+pub const FUR_ELISE: &[(usize, u64)] = &[
+    // Main phrase: E5-D#5-E5-D#5-E5-B4-D5-C5-A4
+    (55, SIXTEENTH_MS),
+    (54, SIXTEENTH_MS),
+    (55, SIXTEENTH_MS),
+    (54, SIXTEENTH_MS),
+    (55, SIXTEENTH_MS),
+    (50, SIXTEENTH_MS),
+    (53, SIXTEENTH_MS),
+    (51, SIXTEENTH_MS),
+    (48, EIGHTH_MS),
+    // Second phrase: C4-E4-A4-B4
+    (39, SIXTEENTH_MS),
+    (43, SIXTEENTH_MS),
+    (48, SIXTEENTH_MS),
+    (50, EIGHTH_MS),
+];
 
 pub struct GameBoard;
 
+/// Velocity of a note whose cell had no live neighbors
+pub const MIN_VELOCITY: u8 = 40;
+/// Velocity of a note whose cell was completely surrounded
+pub const MAX_VELOCITY: u8 = 127;
+
+/// Note velocity for a cell with `neighbors` live neighbors, rising linearly
+/// from MIN_VELOCITY for an isolated cell to MAX_VELOCITY for eight neighbors
+pub fn neighbor_velocity(neighbors: u8) -> u8 {
+    let span = (MAX_VELOCITY - MIN_VELOCITY) as u32;
+    MIN_VELOCITY + (span * neighbors.min(8) as u32 / 8) as u8
+}
+
+/// Live cells of a glider heading down and to the right, as (row, col) offsets
+pub const GLIDER_CELLS: &[(usize, usize)] = &[(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)];
+
+// Live cells of the other built-in patterns, as (row, col) offsets from the
+// top-left of their bounding boxes
+
+// Still lifes
+const BLOCK_CELLS: &[(usize, usize)] = &[(0, 0), (0, 1), (1, 0), (1, 1)];
+const BEEHIVE_CELLS: &[(usize, usize)] = &[(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 2)];
+const LOAF_CELLS: &[(usize, usize)] = &[(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 3), (3, 2)];
+const BOAT_CELLS: &[(usize, usize)] = &[(0, 0), (0, 1), (1, 0), (1, 2), (2, 1)];
+
+// Oscillators
+const BLINKER_CELLS: &[(usize, usize)] = &[(0, 0), (0, 1), (0, 2)];
+const TOAD_CELLS: &[(usize, usize)] = &[(0, 1), (0, 2), (0, 3), (1, 0), (1, 1), (1, 2)];
+const BEACON_CELLS: &[(usize, usize)] = &[(0, 0), (0, 1), (1, 0), (1, 1), (2, 2), (2, 3), (3, 2), (3, 3)];
+// Drawn two cells in from its anchor on every side
+const PULSAR_CELLS: &[(usize, usize)] = &[
+    (2, 4), (2, 5), (2, 6), (2, 10), (2, 11), (2, 12),
+    (4, 2), (4, 7), (4, 9), (4, 14),
+    (5, 2), (5, 7), (5, 9), (5, 14),
+    (6, 2), (6, 7), (6, 9), (6, 14),
+    (7, 4), (7, 5), (7, 6), (7, 10), (7, 11), (7, 12),
+    (9, 4), (9, 5), (9, 6), (9, 10), (9, 11), (9, 12),
+    (10, 2), (10, 7), (10, 9), (10, 14),
+    (11, 2), (11, 7), (11, 9), (11, 14),
+    (12, 2), (12, 7), (12, 9), (12, 14),
+    (14, 4), (14, 5), (14, 6), (14, 10), (14, 11), (14, 12),
+];
+const PENTADECATHLON_CELLS: &[(usize, usize)] = &[
+    (0, 1), (1, 1), (2, 1), (3, 0), (3, 1), (3, 2), (4, 0), (4, 1), (4, 2), (5, 1), (6, 1), (7, 1),
+];
+
+// Light, middle and heavy-weight spaceships
+const LWSS_CELLS: &[(usize, usize)] = &[(0, 1), (0, 4), (1, 0), (2, 0), (2, 4), (3, 0), (3, 1), (3, 2), (3, 3)];
+const MWSS_CELLS: &[(usize, usize)] = &[
+    (0, 2), (1, 0), (1, 4), (2, 5), (3, 0), (3, 5), (4, 1), (4, 2), (4, 3), (4, 4), (4, 5),
+];
+const HWSS_CELLS: &[(usize, usize)] = &[
+    (0, 2), (0, 3), (1, 0), (1, 5), (2, 6), (3, 0), (3, 6), (4, 1), (4, 2), (4, 3), (4, 4), (4, 5), (4, 6),
+];
+
+// Methuselahs
+const R_PENTOMINO_CELLS: &[(usize, usize)] = &[(0, 1), (0, 2), (1, 0), (1, 1), (2, 1)];
+const DIEHARD_CELLS: &[(usize, usize)] = &[(0, 6), (1, 0), (1, 1), (2, 1), (2, 5), (2, 6), (2, 7)];
+const ACORN_CELLS: &[(usize, usize)] = &[(0, 1), (1, 3), (2, 0), (2, 1), (2, 4), (2, 5), (2, 6)];
+
+// Gosper glider gun: the left block, the left and right parts, then the right block
+const GOSPER_GUN_CELLS: &[(usize, usize)] = &[
+    (6, 0), (6, 1), (7, 0), (7, 1),
+    (4, 10), (5, 10), (6, 10), (3, 11), (7, 11), (2, 12), (8, 12), (2, 13), (8, 13),
+    (5, 14), (3, 15), (7, 15), (4, 16), (5, 16), (6, 16), (5, 17),
+    (2, 20), (3, 20), (4, 20), (2, 21), (3, 21), (4, 21), (1, 22), (5, 22),
+    (0, 24), (1, 24), (5, 24), (6, 24),
+    (4, 34), (4, 35), (5, 34), (5, 35),
+];
+
+/// Clockwise rotation applied to a pattern before it is placed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rotation {
+    R0,
+    R90,
+    R180,
+    R270,
+}
+
+/// Mirror image applied to a pattern before it is placed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reflection {
+    // Mirror left-to-right
+    Horizontal,
+    // Mirror top-to-bottom
+    Vertical,
+}
+
+// Shift cells so their bounding box starts at (0, 0) and return its height and width
+fn normalize(cells: &[(usize, usize)]) -> (Vec<(usize, usize)>, usize, usize) {
+    let min_row = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+    let min_col = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+    let normalized: Vec<(usize, usize)> = cells.iter().map(|&(row, col)| (row - min_row, col - min_col)).collect();
+    let height = normalized.iter().map(|&(row, _)| row + 1).max().unwrap_or(0);
+    let width = normalized.iter().map(|&(_, col)| col + 1).max().unwrap_or(0);
+    (normalized, height, width)
+}
+
+/// Rotate cells clockwise within their bounding box; the result starts at (0, 0)
+pub fn rotate_cells(cells: &[(usize, usize)], rotation: Rotation) -> Vec<(usize, usize)> {
+    let (cells, height, width) = normalize(cells);
+    cells.into_iter()
+        .map(|(row, col)| match rotation {
+            Rotation::R0 => (row, col),
+            Rotation::R90 => (col, height - 1 - row),
+            Rotation::R180 => (height - 1 - row, width - 1 - col),
+            Rotation::R270 => (width - 1 - col, row),
+        })
+        .collect()
+}
+
+/// Mirror cells within their bounding box; the result starts at (0, 0)
+pub fn reflect_cells(cells: &[(usize, usize)], reflection: Reflection) -> Vec<(usize, usize)> {
+    let (cells, height, width) = normalize(cells);
+    cells.into_iter()
+        .map(|(row, col)| match reflection {
+            Reflection::Horizontal => (row, width - 1 - col),
+            Reflection::Vertical => (height - 1 - row, col),
+        })
+        .collect()
+}
+
+// True with the given probability, clamped to 0.0-1.0
+fn chance(rng: &mut StdRng, probability: f64) -> bool {
+    rng.random_bool(probability.clamp(0.0, 1.0))
+}
+
+// Generator for one injected top row: the run's seed mixed with the generation,
+// so every row differs but the whole run replays exactly from the same seed
+fn row_rng(seed: u64, generation: u32) -> StdRng {
+    StdRng::seed_from_u64(seed ^ (generation as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+/// A pattern that can be seeded on its own by name (`--pattern`). `width` and
+/// `height` are the footprint `place` fills below and to the right of its anchor.
+pub struct NamedPattern {
+    pub name: &'static str,
+    pub kind: &'static str,
+    pub width: usize,
+    pub height: usize,
+    pub place: fn(&mut GameOfLife, usize, usize) -> usize,
+}
+
+/// Every pattern GameBoard can place, in the order --list-patterns prints them
+pub const PATTERNS: &[NamedPattern] = &[
+    NamedPattern { name: "block", kind: "still life", width: 2, height: 2, place: GameBoard::create_block },
+    NamedPattern { name: "beehive", kind: "still life", width: 4, height: 3, place: GameBoard::create_beehive },
+    NamedPattern { name: "loaf", kind: "still life", width: 4, height: 4, place: GameBoard::create_loaf },
+    NamedPattern { name: "boat", kind: "still life", width: 3, height: 3, place: GameBoard::create_boat },
+    NamedPattern { name: "blinker", kind: "oscillator", width: 3, height: 1, place: GameBoard::create_blinker },
+    NamedPattern { name: "toad", kind: "oscillator", width: 4, height: 2, place: GameBoard::create_toad },
+    NamedPattern { name: "beacon", kind: "oscillator", width: 4, height: 4, place: GameBoard::create_beacon },
+    // The pulsar is drawn two cells in from its anchor
+    NamedPattern { name: "pulsar", kind: "oscillator", width: 17, height: 17, place: GameBoard::create_pulsar },
+    NamedPattern { name: "pentadecathlon", kind: "oscillator", width: 3, height: 8, place: GameBoard::create_pentadecathlon },
+    NamedPattern { name: "glider", kind: "spaceship", width: 3, height: 3, place: GameBoard::create_glider },
+    NamedPattern { name: "lwss", kind: "spaceship", width: 5, height: 4, place: GameBoard::create_lwss },
+    NamedPattern { name: "mwss", kind: "spaceship", width: 6, height: 5, place: GameBoard::create_mwss },
+    NamedPattern { name: "hwss", kind: "spaceship", width: 7, height: 5, place: GameBoard::create_hwss },
+    NamedPattern { name: "r_pentomino", kind: "methuselah", width: 3, height: 3, place: GameBoard::create_r_pentomino },
+    NamedPattern { name: "diehard", kind: "methuselah", width: 8, height: 3, place: GameBoard::create_diehard },
+    NamedPattern { name: "acorn", kind: "methuselah", width: 7, height: 3, place: GameBoard::create_acorn },
+    NamedPattern { name: "gosper_gun", kind: "gun", width: 36, height: 9, place: GameBoard::create_gosper_glider_gun },
+];
+
+/// Look up a pattern in the registry by name (case-insensitive)
+pub fn find_pattern(name: &str) -> Option<&'static NamedPattern> {
+    PATTERNS.iter().find(|pattern| pattern.name.eq_ignore_ascii_case(name.trim()))
+}
+
 impl GameBoard {
-    pub fn create_random_board() -> GameOfLife {
-        debug!("Creating random game board");
+    // Random board seeded from OS entropy, so every call gives a different board.
+    // Use create_random_board_seeded to reproduce one.
+    pub fn create_random_board(alive_probability: f64) -> GameOfLife {
+        let seed = rand::random();
+        debug!("Creating random game board (seed {})", seed);
+        Self::create_random_board_seeded(seed, alive_probability)
+    }
+    
+    // Random board driven by an explicit seed, so runs can be reproduced or
+    // varied deterministically (e.g. batch mode uses seed, seed+1, ...)
+    pub fn create_random_board_seeded(seed: u64, alive_probability: f64) -> GameOfLife {
         let mut game = GameOfLife::new();
-        
-        // Simple random seeding based on time-like value
-        let mut seed = 12345u64;
-        
-        // Fill board with random cells (about 25% alive)
+        Self::fill_random_seeded(&mut game, seed, alive_probability);
+        game
+    }
+
+    // Seed every cell of an existing board (of any height) at random
+    pub fn fill_random_seeded(game: &mut GameOfLife, seed: u64, alive_probability: f64) {
+        debug!("Creating seeded random game board (seed {}, probability {:.2})", seed, alive_probability);
+
+        let mut rng = StdRng::seed_from_u64(seed);
         let mut alive_cells = 0;
-        for row in 0..BOARD_HEIGHT {
+        for row in 0..game.height() {
             for col in 0..BOARD_WIDTH {
-                seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
-                if seed % 4 == 0 {
+                if chance(&mut rng, alive_probability) {
                     game.set_cell(row, col, Cell::Alive);
                     alive_cells += 1;
                 }
             }
         }
-        
-        debug!("Random board created with {} alive cells", alive_cells);
+
+        debug!("Seeded random board created with {} alive cells", alive_cells);
+    }
+
+    // Apgsearch-style soup: a random rectangle of cells in an otherwise empty board.
+    // The region is clipped to the board if it extends past an edge.
+    pub fn create_soup_board(seed: u64, alive_probability: f64, row: usize, col: usize,
+                             width: usize, height: usize) -> GameOfLife {
+        let mut game = GameOfLife::new();
+        Self::place_soup(&mut game, seed, alive_probability, row, col, width, height);
         game
     }
-    
+
+    // Seed a soup region on an existing board, clipped to its edges
+    pub fn place_soup(game: &mut GameOfLife, seed: u64, alive_probability: f64, row: usize, col: usize,
+                      width: usize, height: usize) {
+        debug!("Creating {}x{} soup at ({}, {}) with seed {}", width, height, row, col, seed);
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut alive_cells = 0;
+        for r in row..row.saturating_add(height).min(game.height()) {
+            for c in col..col.saturating_add(width).min(BOARD_WIDTH) {
+                if chance(&mut rng, alive_probability) {
+                    game.set_cell(r, c, Cell::Alive);
+                    alive_cells += 1;
+                }
+            }
+        }
+
+        debug!("Soup board created with {} alive cells", alive_cells);
+    }
+
+    // A single pattern from the registry, centered on an empty board
+    pub fn create_named_board(name: &str) -> Option<GameOfLife> {
+        find_pattern(name).map(Self::create_centered_board)
+    }
+
+    // Place a pattern so its bounding box sits in the middle of the board
+    fn create_centered_board(pattern: &NamedPattern) -> GameOfLife {
+        let mut game = GameOfLife::new();
+        let row = game.height().saturating_sub(pattern.height) / 2;
+        let col = BOARD_WIDTH.saturating_sub(pattern.width) / 2;
+        debug!("Placing {} ({}x{}) at ({}, {})", pattern.name, pattern.width, pattern.height, row, col);
+        (pattern.place)(&mut game, row, col);
+        game
+    }
+
     pub fn create_complex_board() -> GameOfLife {
         debug!("Creating complex game board with predefined patterns");
         let mut game = GameOfLife::new();
@@ -75,19 +326,23 @@ impl GameBoard {
     }
     
     pub fn add_random_row(game: &mut GameOfLife) {
+        if !game.random_injection_enabled() {
+            trace!("Random injection disabled, leaving top row alone");
+            return;
+        }
         trace!("Adding random top row, generation: {}", game.generation());
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        
-        let mut hasher = DefaultHasher::new();
-        game.generation().hash(&mut hasher);
-        let seed = hasher.finish();
-        
-        let mut rng_state = seed;
+
+        let probability = game.injection_probability();
+
+        // A seeded run draws its rows from the run's seed, so it repeats
+        // exactly; otherwise every run gets fresh rows
+        let mut rng = match game.injection_seed() {
+            Some(seed) => row_rng(seed, game.generation()),
+            None => StdRng::from_os_rng(),
+        };
         let mut alive_count = 0;
         for col in 0..BOARD_WIDTH {
-            rng_state = rng_state.wrapping_mul(1664525).wrapping_add(1013904223);
-            let cell = if (rng_state % 5) == 0 {
+            let cell = if chance(&mut rng, probability) {
                 alive_count += 1;
                 Cell::Alive
             } else {
@@ -105,7 +360,7 @@ impl GameBoard {
         let mut game = GameOfLife::new();
         
         for (row_idx, &row) in pattern.iter().enumerate() {
-            if row_idx >= BOARD_HEIGHT { break; }
+            if row_idx >= game.height() { break; }
             
             for (col_idx, ch) in row.chars().enumerate() {
                 if col_idx >= BOARD_WIDTH { break; }
@@ -123,303 +378,212 @@ impl GameBoard {
         debug!("Getting bottom row and advancing board, generation: {}", game.generation());
         
         let bottom_row_keys: Vec<usize> = (0..BOARD_WIDTH)
-            .filter(|&col| game.get_cell(BOARD_HEIGHT - 1, col) == Cell::Alive)
+            .filter(|&col| game.get_cell(game.height() - 1, col) == Cell::Alive)
             .collect();
 
         trace!("Bottom row has {} active cells: {:?}", bottom_row_keys.len(), bottom_row_keys);
 
+        Self::advance_board(game);
+        bottom_row_keys
+    }
+
+    // Like get_bottom_row_and_advance, but each key comes with a velocity from
+    // how many live neighbors its cell had before the row was played
+    pub fn get_bottom_row_with_velocity(game: &mut GameOfLife) -> Vec<(usize, u8)> {
+        debug!("Getting bottom row with velocities and advancing board, generation: {}", game.generation());
+
+        let bottom = game.height() - 1;
+        let neighbors = game.neighbor_counts(bottom);
+        let notes: Vec<(usize, u8)> = (0..BOARD_WIDTH)
+            .filter(|&col| game.get_cell(bottom, col) == Cell::Alive)
+            .map(|col| (col, neighbor_velocity(neighbors[col])))
+            .collect();
+
+        trace!("Bottom row notes (key, velocity): {:?}", notes);
+
+        Self::advance_board(game);
+        notes
+    }
+
+    // Drop the bottom row, inject a new top row if enabled and evolve the board
+    fn advance_board(game: &mut GameOfLife) {
         // Shift board down (remove bottom row, add empty row at top)
         trace!("Shifting board down one row");
-        for row in (1..BOARD_HEIGHT).rev() {
-            for col in 0..BOARD_WIDTH {
-                let cell = game.get_cell(row - 1, col);
-                game.set_cell(row, col, cell);
-            }
-        }
+        game.shift_rows_down();
         
-        // Clear top row
-        for col in 0..BOARD_WIDTH {
-            game.set_cell(0, col, Cell::Dead);
+        if game.random_injection_active() {
+            Self::add_random_row(game);
+        } else {
+            trace!("Random injection stopped at generation {:?}", game.random_injection_generations());
         }
-        
-        Self::add_random_row(game);
         trace!("Calculating next generation");
         game.next_generation();
         
         debug!("Board advanced to generation: {}", game.generation());
-        bottom_row_keys
     }
     
     // Still Life patterns
-    pub fn create_block(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col, Cell::Alive);
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 1, Cell::Alive);
+    pub fn create_block(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, BLOCK_CELLS, row, col)
     }
     
-    pub fn create_beehive(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row, col + 2, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 3, Cell::Alive);
-        game.set_cell(row + 2, col + 1, Cell::Alive);
-        game.set_cell(row + 2, col + 2, Cell::Alive);
+    pub fn create_beehive(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, BEEHIVE_CELLS, row, col)
     }
     
-    pub fn create_loaf(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row, col + 2, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 3, Cell::Alive);
-        game.set_cell(row + 2, col + 1, Cell::Alive);
-        game.set_cell(row + 2, col + 3, Cell::Alive);
-        game.set_cell(row + 3, col + 2, Cell::Alive);
+    pub fn create_loaf(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, LOAF_CELLS, row, col)
     }
     
-    pub fn create_boat(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col, Cell::Alive);
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 2, Cell::Alive);
-        game.set_cell(row + 2, col + 1, Cell::Alive);
+    pub fn create_boat(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, BOAT_CELLS, row, col)
     }
     
     // Oscillator patterns
-    pub fn create_blinker(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col, Cell::Alive);
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row, col + 2, Cell::Alive);
+    pub fn create_blinker(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, BLINKER_CELLS, row, col)
     }
     
-    pub fn create_toad(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row, col + 2, Cell::Alive);
-        game.set_cell(row, col + 3, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 1, Cell::Alive);
-        game.set_cell(row + 1, col + 2, Cell::Alive);
+    pub fn create_toad(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, TOAD_CELLS, row, col)
     }
     
-    pub fn create_beacon(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col, Cell::Alive);
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 1, Cell::Alive);
-        game.set_cell(row + 2, col + 2, Cell::Alive);
-        game.set_cell(row + 2, col + 3, Cell::Alive);
-        game.set_cell(row + 3, col + 2, Cell::Alive);
-        game.set_cell(row + 3, col + 3, Cell::Alive);
+    pub fn create_beacon(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, BEACON_CELLS, row, col)
     }
     
-    pub fn create_pulsar(game: &mut GameOfLife, row: usize, col: usize) {
-        // Top cross
-        for i in 0..3 {
-            game.set_cell(row + 2, col + 4 + i, Cell::Alive);
-            game.set_cell(row + 2, col + 10 + i, Cell::Alive);
-        }
-        
-        // Vertical lines
-        for i in 0..3 {
-            game.set_cell(row + 4 + i, col + 2, Cell::Alive);
-            game.set_cell(row + 4 + i, col + 7, Cell::Alive);
-            game.set_cell(row + 4 + i, col + 9, Cell::Alive);
-            game.set_cell(row + 4 + i, col + 14, Cell::Alive);
-            
-            game.set_cell(row + 10 + i, col + 2, Cell::Alive);
-            game.set_cell(row + 10 + i, col + 7, Cell::Alive);
-            game.set_cell(row + 10 + i, col + 9, Cell::Alive);
-            game.set_cell(row + 10 + i, col + 14, Cell::Alive);
-        }
-        
-        // Horizontal lines
-        for i in 0..3 {
-            game.set_cell(row + 7, col + 4 + i, Cell::Alive);
-            game.set_cell(row + 7, col + 10 + i, Cell::Alive);
-            game.set_cell(row + 9, col + 4 + i, Cell::Alive);
-            game.set_cell(row + 9, col + 10 + i, Cell::Alive);
-        }
-        
-        // Bottom cross
-        for i in 0..3 {
-            game.set_cell(row + 14, col + 4 + i, Cell::Alive);
-            game.set_cell(row + 14, col + 10 + i, Cell::Alive);
-        }
+    pub fn create_pulsar(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, PULSAR_CELLS, row, col)
     }
     
-    pub fn create_pentadecathlon(game: &mut GameOfLife, row: usize, col: usize) {
-        // Central line
-        for i in 0..8 {
-            game.set_cell(row + i, col + 1, Cell::Alive);
-        }
-        
-        // End pieces
-        game.set_cell(row + 3, col, Cell::Alive);
-        game.set_cell(row + 3, col + 2, Cell::Alive);
-        game.set_cell(row + 4, col, Cell::Alive);
-        game.set_cell(row + 4, col + 2, Cell::Alive);
+    pub fn create_pentadecathlon(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, PENTADECATHLON_CELLS, row, col)
     }
     
+    // Place a pattern's cells with their bounding box's top-left at (row, col),
+    // returning how many cells fell off the board. A pattern that doesn't fit
+    // is still placed as far as it goes, with a warning.
+    fn place_cells(game: &mut GameOfLife, cells: &[(usize, usize)], row: usize, col: usize) -> usize {
+        let clipped = cells.iter()
+            .filter(|&&(r, c)| !game.try_set_cell(row.saturating_add(r), col.saturating_add(c), Cell::Alive))
+            .count();
+
+        if clipped > 0 {
+            warn!("Pattern at ({}, {}) is partially off the board: {} of {} cells clipped",
+                  row, col, clipped, cells.len());
+        }
+        clipped
+    }
+
+    // Layer an ASCII block onto the board with its top-left corner at
+    // (offset_row, offset_col). 'O', 'X' and '*' are live cells; any other
+    // character leaves the cell underneath alone, so several blocks can be
+    // composed on one board. Whatever falls past the edges is clipped.
+    pub fn from_ascii(game: &mut GameOfLife, rows: &[&str], offset_row: usize, offset_col: usize) {
+        for (r, row) in rows.iter().enumerate() {
+            let row_idx = offset_row.saturating_add(r);
+            if row_idx >= game.height() {
+                break;
+            }
+            for (c, ch) in row.chars().enumerate() {
+                let col_idx = offset_col.saturating_add(c);
+                if col_idx >= BOARD_WIDTH {
+                    break;
+                }
+                if matches!(ch, 'O' | 'X' | '*') {
+                    game.set_cell(row_idx, col_idx, Cell::Alive);
+                }
+            }
+        }
+    }
+
+    // Place a pattern in any of its four orientations, e.g. a glider heading
+    // up-left instead of down-right
+    pub fn place_rotated(game: &mut GameOfLife, pattern_cells: &[(usize, usize)],
+                         row: usize, col: usize, rotation: Rotation) -> usize {
+        Self::place_cells(game, &rotate_cells(pattern_cells, rotation), row, col)
+    }
+
+    // Place the mirror image of a pattern
+    pub fn place_reflected(game: &mut GameOfLife, pattern_cells: &[(usize, usize)],
+                           row: usize, col: usize, reflection: Reflection) -> usize {
+        Self::place_cells(game, &reflect_cells(pattern_cells, reflection), row, col)
+    }
+
     // Spaceship patterns
-    pub fn create_glider(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col + 2, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 2, Cell::Alive);
-        game.set_cell(row + 2, col + 1, Cell::Alive);
-        game.set_cell(row + 2, col + 2, Cell::Alive);
+    pub fn create_glider(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, GLIDER_CELLS, row, col)
     }
     
-    pub fn create_lwss(game: &mut GameOfLife, row: usize, col: usize) {
+    pub fn create_lwss(game: &mut GameOfLife, row: usize, col: usize) -> usize {
         // Light-weight spaceship
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row, col + 4, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 2, col, Cell::Alive);
-        game.set_cell(row + 2, col + 4, Cell::Alive);
-        game.set_cell(row + 3, col, Cell::Alive);
-        game.set_cell(row + 3, col + 1, Cell::Alive);
-        game.set_cell(row + 3, col + 2, Cell::Alive);
-        game.set_cell(row + 3, col + 3, Cell::Alive);
+        Self::place_cells(game, LWSS_CELLS, row, col)
     }
     
-    pub fn create_mwss(game: &mut GameOfLife, row: usize, col: usize) {
+    pub fn create_mwss(game: &mut GameOfLife, row: usize, col: usize) -> usize {
         // Middle-weight spaceship
-        game.set_cell(row, col + 2, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 4, Cell::Alive);
-        game.set_cell(row + 2, col + 5, Cell::Alive);
-        game.set_cell(row + 3, col, Cell::Alive);
-        game.set_cell(row + 3, col + 5, Cell::Alive);
-        game.set_cell(row + 4, col + 1, Cell::Alive);
-        game.set_cell(row + 4, col + 2, Cell::Alive);
-        game.set_cell(row + 4, col + 3, Cell::Alive);
-        game.set_cell(row + 4, col + 4, Cell::Alive);
-        game.set_cell(row + 4, col + 5, Cell::Alive);
+        Self::place_cells(game, MWSS_CELLS, row, col)
     }
     
-    pub fn create_hwss(game: &mut GameOfLife, row: usize, col: usize) {
+    pub fn create_hwss(game: &mut GameOfLife, row: usize, col: usize) -> usize {
         // Heavy-weight spaceship
-        game.set_cell(row, col + 2, Cell::Alive);
-        game.set_cell(row, col + 3, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 5, Cell::Alive);
-        game.set_cell(row + 2, col + 6, Cell::Alive);
-        game.set_cell(row + 3, col, Cell::Alive);
-        game.set_cell(row + 3, col + 6, Cell::Alive);
-        game.set_cell(row + 4, col + 1, Cell::Alive);
-        game.set_cell(row + 4, col + 2, Cell::Alive);
-        game.set_cell(row + 4, col + 3, Cell::Alive);
-        game.set_cell(row + 4, col + 4, Cell::Alive);
-        game.set_cell(row + 4, col + 5, Cell::Alive);
-        game.set_cell(row + 4, col + 6, Cell::Alive);
+        Self::place_cells(game, HWSS_CELLS, row, col)
     }
     
     // Methuselah patterns
-    pub fn create_r_pentomino(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row, col + 2, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 1, Cell::Alive);
-        game.set_cell(row + 2, col + 1, Cell::Alive);
+    pub fn create_r_pentomino(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, R_PENTOMINO_CELLS, row, col)
     }
     
-    pub fn create_diehard(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col + 6, Cell::Alive);
-        game.set_cell(row + 1, col, Cell::Alive);
-        game.set_cell(row + 1, col + 1, Cell::Alive);
-        game.set_cell(row + 2, col + 1, Cell::Alive);
-        game.set_cell(row + 2, col + 5, Cell::Alive);
-        game.set_cell(row + 2, col + 6, Cell::Alive);
-        game.set_cell(row + 2, col + 7, Cell::Alive);
+    pub fn create_diehard(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, DIEHARD_CELLS, row, col)
     }
     
-    pub fn create_acorn(game: &mut GameOfLife, row: usize, col: usize) {
-        game.set_cell(row, col + 1, Cell::Alive);
-        game.set_cell(row + 1, col + 3, Cell::Alive);
-        game.set_cell(row + 2, col, Cell::Alive);
-        game.set_cell(row + 2, col + 1, Cell::Alive);
-        game.set_cell(row + 2, col + 4, Cell::Alive);
-        game.set_cell(row + 2, col + 5, Cell::Alive);
-        game.set_cell(row + 2, col + 6, Cell::Alive);
+    pub fn create_acorn(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, ACORN_CELLS, row, col)
     }
     
     // Gun patterns
-    pub fn create_gosper_glider_gun(game: &mut GameOfLife, row: usize, col: usize) {
-        // Left block
-        Self::create_block(game, row + 5, col);
-        
-        // Left part
-        game.set_cell(row + 3, col + 10, Cell::Alive);
-        game.set_cell(row + 4, col + 10, Cell::Alive);
-        game.set_cell(row + 5, col + 10, Cell::Alive);
-        game.set_cell(row + 2, col + 11, Cell::Alive);
-        game.set_cell(row + 6, col + 11, Cell::Alive);
-        game.set_cell(row + 1, col + 12, Cell::Alive);
-        game.set_cell(row + 7, col + 12, Cell::Alive);
-        game.set_cell(row + 1, col + 13, Cell::Alive);
-        game.set_cell(row + 7, col + 13, Cell::Alive);
-        game.set_cell(row + 4, col + 14, Cell::Alive);
-        game.set_cell(row + 2, col + 15, Cell::Alive);
-        game.set_cell(row + 6, col + 15, Cell::Alive);
-        game.set_cell(row + 3, col + 16, Cell::Alive);
-        game.set_cell(row + 4, col + 16, Cell::Alive);
-        game.set_cell(row + 5, col + 16, Cell::Alive);
-        game.set_cell(row + 4, col + 17, Cell::Alive);
-        
-        // Right part
-        game.set_cell(row + 1, col + 20, Cell::Alive);
-        game.set_cell(row + 2, col + 20, Cell::Alive);
-        game.set_cell(row + 3, col + 20, Cell::Alive);
-        game.set_cell(row + 1, col + 21, Cell::Alive);
-        game.set_cell(row + 2, col + 21, Cell::Alive);
-        game.set_cell(row + 3, col + 21, Cell::Alive);
-        game.set_cell(row, col + 22, Cell::Alive);
-        game.set_cell(row + 4, col + 22, Cell::Alive);
-        game.set_cell(row - 1, col + 24, Cell::Alive);
-        game.set_cell(row, col + 24, Cell::Alive);
-        game.set_cell(row + 4, col + 24, Cell::Alive);
-        game.set_cell(row + 5, col + 24, Cell::Alive);
-        
-        // Right block
-        Self::create_block(game, row + 3, col + 34);
+    pub fn create_gosper_glider_gun(game: &mut GameOfLife, row: usize, col: usize) -> usize {
+        Self::place_cells(game, GOSPER_GUN_CELLS, row, col)
     }
     
     // Board configuration to play "Für Elise" melody
     pub fn create_fur_elise_board() -> GameOfLife {
         let mut game = GameOfLife::new();
         
-        // Für Elise melody notes (piano key numbers, 1-88):
+        // Für Elise melody notes (0-based piano keys, A0 = 0), see FUR_ELISE:
         // E5-D#5-E5-D#5-E5-B4-D5-C5-A4 (main phrase)
-        // Piano keys: 52-51-52-51-52-47-50-49-45
+        // Piano keys: 55-54-55-54-55-50-53-51-48
         
         // Create patterns that will hit the bottom row to play these notes
         // Using careful timing with different pattern types and positions
         
-        // E5 (key 52) - First note, immediate impact
+        // E5 (key 55) - First note, immediate impact
         Self::create_glider(&mut game, 36, 51); // Will reach bottom quickly
         
-        // D#5 (key 51) - Second note
+        // D#5 (key 54) - Second note
         Self::create_blinker(&mut game, 35, 50); // Oscillates, hits on step 2
         
-        // E5 (key 52) - Third note  
+        // E5 (key 55) - Third note  
         Self::create_glider(&mut game, 34, 51); // Delayed glider
         
-        // D#5 (key 51) - Fourth note
+        // D#5 (key 54) - Fourth note
         Self::create_toad(&mut game, 32, 49); // Toad pattern, hits step 4
         
-        // E5 (key 52) - Fifth note
+        // E5 (key 55) - Fifth note
         Self::create_glider(&mut game, 30, 51); // Another glider
         
-        // B4 (key 47) - Sixth note
+        // B4 (key 50) - Sixth note
         Self::create_r_pentomino(&mut game, 25, 45); // Long-term pattern
         
-        // D5 (key 50) - Seventh note
-        Self::create_lwss(&mut game, 28, 46); // Spaceship moving toward key 50
+        // D5 (key 53) - Seventh note
+        Self::create_lwss(&mut game, 28, 46); // Spaceship moving toward D5
         
-        // C5 (key 49) - Eighth note  
+        // C5 (key 51) - Eighth note  
         Self::create_beacon(&mut game, 26, 47); // Beacon oscillator
         
-        // A4 (key 45) - Ninth note
+        // A4 (key 48) - Ninth note
         Self::create_acorn(&mut game, 20, 42); // Acorn methuselah
         
         // Add some supporting patterns for rhythm and harmony
@@ -439,10 +603,10 @@ impl GameBoard {
         
         // Second phrase preparation - more complex patterns
         Self::create_diehard(&mut game, 15, 20);  // Dies and creates space
-        Self::create_gosper_glider_gun(&mut game, 2, 10); // Continuous glider generation
+        Self::create_gosper_glider_gun(&mut game, 1, 10); // Continuous glider generation
         
         // Add patterns for the second phrase melody
-        // C4-E4-A4-B4 sequence (keys 41-44-45-47)
+        // C4-E4-A4-B4 sequence (keys 39-43-48-50)
         Self::create_hwss(&mut game, 22, 38);    // Heavy spaceship for C4
         Self::create_mwss(&mut game, 24, 41);    // Medium spaceship for E4
         Self::create_glider(&mut game, 26, 44);  // Glider for A4
@@ -0,0 +1,92 @@
+// Generation history for Conway's Steinway
+// GameOfLifeHistory wraps a board and snapshots it before every generation it
+// advances, keeping the most recent snapshots in a bounded ring buffer so an
+// explorer can rewind. Only the cells and the generation counter are saved;
+// the board's settings (rules, injection, decay) don't change as it evolves.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::bitboard::BitBoard;
+use crate::GameOfLife;
+
+// Generations kept by default
+pub const DEFAULT_HISTORY_DEPTH: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    // Every stored generation has already been stepped back through
+    Exhausted,
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryError::Exhausted => write!(f, "No earlier generation in the history"),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+pub struct GameOfLifeHistory {
+    game: GameOfLife,
+    snapshots: VecDeque<(BitBoard, u32)>,
+    depth: usize,
+}
+
+impl GameOfLifeHistory {
+    // Track `game`, remembering up to `depth` earlier generations
+    pub fn new(game: GameOfLife, depth: usize) -> Self {
+        GameOfLifeHistory {
+            game,
+            snapshots: VecDeque::with_capacity(depth),
+            depth,
+        }
+    }
+
+    pub fn game(&self) -> &GameOfLife {
+        &self.game
+    }
+
+    pub fn into_inner(self) -> GameOfLife {
+        self.game
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    // Number of generations that can currently be stepped back through
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn next_generation(&mut self) {
+        self.advance(GameOfLife::next_generation)
+    }
+
+    // Snapshot the board, then run one step of `step` on it, e.g.
+    // GameBoard::get_bottom_row_and_advance to play and advance in one go
+    pub fn advance<R>(&mut self, step: impl FnOnce(&mut GameOfLife) -> R) -> R {
+        if self.depth > 0 {
+            if self.snapshots.len() == self.depth {
+                self.snapshots.pop_front();
+            }
+            self.snapshots.push_back((self.game.board.clone(), self.game.generation));
+        }
+        step(&mut self.game)
+    }
+
+    // Restore the board and generation counter from before the last step
+    pub fn step_back(&mut self) -> Result<(), HistoryError> {
+        let (board, generation) = self.snapshots.pop_back().ok_or(HistoryError::Exhausted)?;
+        self.game.board = board;
+        self.game.generation = generation;
+        Ok(())
+    }
+}
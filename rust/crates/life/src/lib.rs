@@ -1,20 +1,46 @@
 // Library interface for Conway's Game of Life module
-use std::fmt;
 use std::collections::hash_map::DefaultHasher;
+use std::fmt;
 use std::hash::{Hash, Hasher};
 
+mod bitboard;
 pub mod game_board;
+pub mod history;
+pub mod pattern_file;
+pub mod plaintext;
+pub mod rle;
+pub mod ruleset;
+pub mod snapshot;
+pub mod stability;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // Re-export commonly used types
+use bitboard::BitBoard;
 pub use game_board::*;
+pub use history::{GameOfLifeHistory, HistoryError, DEFAULT_HISTORY_DEPTH};
+pub use pattern_file::{detect_format, PatternError, PatternFormat};
+pub use rle::RleError;
+pub use ruleset::{RuleParseError, Ruleset};
+pub use stability::{StabilityDetector, DEFAULT_MAX_PERIOD};
+#[cfg(feature = "wasm")]
+pub use wasm::WasmGame;
 
 pub const BOARD_WIDTH: usize = 88;
+// Height of boards from GameOfLife::new; with_dimensions picks another
 pub const BOARD_HEIGHT: usize = 40;
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+// Bits of a full row in a u128
+const ROW_MASK: u128 = (1 << BOARD_WIDTH) - 1;
+
+// Chance of a live cell in each injected top row unless configured otherwise
+pub const DEFAULT_INJECTION_PROBABILITY: f64 = 0.2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Cell {
     Dead,
     Alive,
+    Dying(u8), // "Generations" mode: decay states left before the cell is dead
 }
 
 impl fmt::Display for Cell {
@@ -22,14 +48,51 @@ impl fmt::Display for Cell {
         let symbol = match *self {
             Cell::Dead => '.',
             Cell::Alive => 'O',
+            Cell::Dying(_) => '+',
         };
         write!(f, "{}", symbol)
     }
 }
 
+// What lies beyond the board edges when counting neighbors
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum BoundaryMode {
+    // Cells off the edge are always dead
+    #[default]
+    Dead,
+    // Edges wrap around, so the board is a torus and gliders re-enter on the far side
+    Toroidal,
+}
+
+// Which surrounding cells count as neighbors
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Neighborhood {
+    // All eight surrounding cells, as in Conway's Life
+    #[default]
+    Moore,
+    // Only the four orthogonal cells (no diagonals)
+    VonNeumann,
+}
+
+#[derive(Clone)]
 pub struct GameOfLife {
-    board: Vec<Vec<Cell>>,
+    board: BitBoard,
     generation: u32,
+    // Generation after which no more random rows are injected (None = never stop)
+    random_injection_generations: Option<u32>,
+    // Whether random rows are injected at all
+    random_injection: bool,
+    // Dying states a cell passes through before it is dead (0 = plain Conway rules)
+    decay_states: u8,
+    // How neighbors are counted at the board edges
+    boundary_mode: BoundaryMode,
+    neighborhood: Neighborhood,
+    // Birth and survival rules (Conway's B3/S23 by default)
+    ruleset: Ruleset,
+    // Seed for injected top rows (None = fresh rows on every run)
+    injection_seed: Option<u64>,
+    // Chance that each cell of an injected top row is alive
+    injection_probability: f64,
 }
 
 impl fmt::Display for GameOfLife {
@@ -38,10 +101,10 @@ impl fmt::Display for GameOfLife {
         writeln!(f, "Piano Keys: 1-88 (left to right)")?;
         writeln!(f, "{}", "=".repeat(BOARD_WIDTH + 4))?;
         
-        for row in &self.board {
+        for row in 0..self.height() {
             write!(f, "| ")?;
-            for &cell in row {
-                write!(f, "{}", cell)?;
+            for col in 0..BOARD_WIDTH {
+                write!(f, "{}", self.board.get(row, col))?;
             }
             writeln!(f, " |")?;
         }
@@ -58,112 +121,349 @@ impl Default for GameOfLife {
 
 impl GameOfLife {
     pub fn new() -> Self {
-        let board = vec![vec![Cell::Dead; BOARD_WIDTH]; BOARD_HEIGHT];
+        Self::with_dimensions(BOARD_HEIGHT)
+    }
+
+    // Empty board `height` rows tall (at least one); the width is always one
+    // column per piano key
+    pub fn with_dimensions(height: usize) -> Self {
         GameOfLife {
-            board,
+            board: BitBoard::new(height.max(1)),
             generation: 0,
+            random_injection_generations: None,
+            random_injection: true,
+            decay_states: 0,
+            boundary_mode: BoundaryMode::Dead,
+            neighborhood: Neighborhood::Moore,
+            ruleset: Ruleset::conway(),
+            injection_seed: None,
+            injection_probability: DEFAULT_INJECTION_PROBABILITY,
         }
     }
 
+    // Empty board that evolves under the given birth/survival rules
+    pub fn with_ruleset(ruleset: Ruleset) -> Self {
+        let mut game = Self::new();
+        game.ruleset = ruleset;
+        game
+    }
+
+    // A fresh board holding an ASCII pattern at its top-left corner
     pub fn from_pattern(pattern: &[&str]) -> Self {
         let mut game = Self::new();
-        
-        for (row_idx, &row) in pattern.iter().enumerate() {
-            if row_idx >= BOARD_HEIGHT { break; }
-            
-            for (col_idx, ch) in row.chars().enumerate() {
-                if col_idx >= BOARD_WIDTH { break; }    
-                
-                if ch == 'O' || ch == 'X' || ch == '*' {
-                    game.board[row_idx][col_idx] = Cell::Alive;
-                }
-            }
-        }
-        
+        GameBoard::from_ascii(&mut game, pattern, 0, 0);
         game
     }
 
     pub fn set_cell(&mut self, row: usize, col: usize, state: Cell) {
-        if row < BOARD_HEIGHT && col < BOARD_WIDTH {
-            self.board[row][col] = state;
+        self.try_set_cell(row, col, state);
+    }
+
+    // Set a cell, returning false (and changing nothing) if it is off the board
+    pub fn try_set_cell(&mut self, row: usize, col: usize, state: Cell) -> bool {
+        let on_board = row < self.height() && col < BOARD_WIDTH;
+        if on_board {
+            self.board.set(row, col, state);
         }
+        on_board
     }
 
     pub fn get_cell(&self, row: usize, col: usize) -> Cell {
-        if row < BOARD_HEIGHT && col < BOARD_WIDTH {
-            self.board[row][col]
+        if row < self.height() && col < BOARD_WIDTH {
+            self.board.get(row, col)
         } else {
             Cell::Dead
         }
     }
 
-    fn count_neighbors(&self, row: usize, col: usize) -> u8 {
-        let mut count = 0;
-        
-        for dr in -1i32..=1 {
-            for dc in -1i32..=1 {
-                if dr == 0 && dc == 0 { continue; }
-                
-                let new_row = row as i32 + dr;
-                let new_col = col as i32 + dc;
-                
-                if new_row >= 0 && new_row < BOARD_HEIGHT as i32 &&
-                   new_col >= 0 && new_col < BOARD_WIDTH as i32 && 
-                   self.board[new_row as usize][new_col as usize] == Cell::Alive {
-                    count += 1;
-                }
+    // One row's live cells shifted up a bit, with the neighbors just past each
+    // edge in bit 0 and bit BOARD_WIDTH + 1 (always dead unless the board wraps).
+    // Rows off the board are empty unless the board wraps.
+    fn padded_row(&self, row: isize) -> u128 {
+        let toroidal = self.boundary_mode == BoundaryMode::Toroidal;
+        let height = self.height() as isize;
+        let row = if toroidal {
+            row.rem_euclid(height)
+        } else if row < 0 || row >= height {
+            return 0;
+        } else {
+            row
+        };
+
+        let bits = self.board.row_bits(row as usize);
+        let mut padded = bits << 1;
+        if toroidal {
+            padded |= bits >> (BOARD_WIDTH - 1) & 1;
+            padded |= (bits & 1) << (BOARD_WIDTH + 1);
+        }
+        padded
+    }
+
+    // Live-neighbor counts for a whole row at once, read straight from the
+    // padded rows above, at and below it. The count for column c is spread
+    // over four bit-planes: bit c of planes[i] is bit i of that count.
+    fn count_neighbors(neighborhood: Neighborhood, above: u128, current: u128, below: u128) -> [u128; 4] {
+        let mut planes = [0u128; 4];
+        let moore = [
+            above, above >> 1, above >> 2,
+            current, current >> 2,
+            below, below >> 1, below >> 2,
+        ];
+        let neighbors: &[u128] = match neighborhood {
+            Neighborhood::Moore => &moore,
+            Neighborhood::VonNeumann => &[above >> 1, current, current >> 2, below >> 1],
+        };
+
+        // Ripple-carry add each neighbor into every column's counter in parallel
+        for &neighbor in neighbors {
+            let mut carry = neighbor;
+            for plane in planes.iter_mut() {
+                let next_carry = *plane & carry;
+                *plane ^= carry;
+                carry = next_carry;
             }
         }
-        
-        count
+        planes
+    }
+
+    // Columns whose count (from count_neighbors) is one of `counts`
+    fn columns_with_count(planes: &[u128; 4], counts: &[u8]) -> u128 {
+        counts.iter().fold(0, |mask, &n| {
+            let equal = (0..4).fold(ROW_MASK, |equal, bit| {
+                if n >> bit & 1 == 1 { equal & planes[bit] } else { equal & !planes[bit] }
+            });
+            mask | equal
+        })
+    }
+
+    // Live neighbors of every cell in a row, counted the same way as
+    // next_generation counts them (so the boundary mode applies)
+    pub fn neighbor_counts(&self, row: usize) -> Vec<u8> {
+        if row >= self.height() {
+            return vec![0; BOARD_WIDTH];
+        }
+        let row = row as isize;
+        let planes = Self::count_neighbors(self.neighborhood, self.padded_row(row - 1), self.padded_row(row),
+                                           self.padded_row(row + 1));
+        (0..BOARD_WIDTH)
+            .map(|col| (0..4).map(|bit| ((planes[bit] >> col & 1) as u8) << bit).sum())
+            .collect()
     }
 
     pub fn next_generation(&mut self) {
-        let mut new_board = self.board.clone();
-        
-        for (row_idx, row) in new_board.iter_mut().enumerate().take(BOARD_HEIGHT) {
-            for (col_idx, cell) in row.iter_mut().enumerate().take(BOARD_WIDTH) {
-                let neighbors = self.count_neighbors(row_idx, col_idx);
-                let current_cell = self.board[row_idx][col_idx];
-                
-                *cell = match (current_cell, neighbors) {
-                    (Cell::Alive, 2) | (Cell::Alive, 3) => Cell::Alive,
-                    (Cell::Alive, _) => Cell::Dead,
-                    (Cell::Dead, 3) => Cell::Alive,
-                    (Cell::Dead, _) => Cell::Dead,
-                };
+        let mut new_board = BitBoard::new(self.height());
+        let has_dying = self.board.has_dying();
+        let birth: Vec<u8> = (0..=8).filter(|&n| self.ruleset.is_born(n)).collect();
+        let survival: Vec<u8> = (0..=8).filter(|&n| self.ruleset.survives(n)).collect();
+
+        for row_idx in 0..self.height() {
+            let above = self.padded_row(row_idx as isize - 1);
+            let current = self.padded_row(row_idx as isize);
+            let below = self.padded_row(row_idx as isize + 1);
+
+            let planes = Self::count_neighbors(self.neighborhood, above, current, below);
+            let alive = current >> 1 & ROW_MASK;
+            let survivors = alive & Self::columns_with_count(&planes, &survival);
+            let mut born = !alive & Self::columns_with_count(&planes, &birth);
+
+            // Dying cells don't count as neighbors and can't be reborn
+            // until they've fully decayed
+            if has_dying {
+                for col_idx in 0..BOARD_WIDTH {
+                    if let Cell::Dying(left) = self.board.get(row_idx, col_idx) {
+                        born &= !(1 << col_idx);
+                        if left > 1 {
+                            new_board.set(row_idx, col_idx, Cell::Dying(left - 1));
+                        }
+                    }
+                }
+            }
+            if self.decay_states > 0 {
+                let died = alive & !survivors;
+                for col_idx in (0..BOARD_WIDTH).filter(|&col| died >> col & 1 == 1) {
+                    new_board.set(row_idx, col_idx, Cell::Dying(self.decay_states));
+                }
             }
+
+            new_board.set_row_bits(row_idx, survivors | born);
         }
         
         self.board = new_board;
         self.generation += 1;
     }
 
+    // Move every row down one, dropping the bottom row and leaving the top row empty
+    pub(crate) fn shift_rows_down(&mut self) {
+        if self.board.has_dying() {
+            for row in (1..self.height()).rev() {
+                for col in 0..BOARD_WIDTH {
+                    let cell = self.board.get(row - 1, col);
+                    self.board.set(row, col, cell);
+                }
+            }
+            for col in 0..BOARD_WIDTH {
+                self.board.set(0, col, Cell::Dead);
+            }
+            return;
+        }
+
+        for row in (1..self.height()).rev() {
+            let above = self.board.row_bits(row - 1);
+            self.board.set_row_bits(row, above);
+        }
+        self.board.set_row_bits(0, 0);
+    }
+
     // This method is being replaced by GameBoard::get_bottom_row_and_advance
     // Kept for backward compatibility but marked as deprecated
     #[deprecated(since = "0.1.0", note = "Use GameBoard::get_bottom_row_and_advance instead")]
     pub fn get_bottom_row_and_advance(&mut self) -> Vec<usize> {
-        // Implementation now delegates to GameBoard for consistency
+        // Delegates to GameBoard so both entry points shift, inject and evolve
+        // the board identically
         GameBoard::get_bottom_row_and_advance(self)
     }
 
+    // Kept for callers of the old API; shares GameBoard's row generator so
+    // both entry points inject exactly the same cells
     pub fn add_random_top_row(&mut self) {
-        let mut hasher = DefaultHasher::new();
-        self.generation.hash(&mut hasher);
-        let seed = hasher.finish();
-        
-        let mut rng_state = seed;
-        for col in 0..BOARD_WIDTH {
-            rng_state = rng_state.wrapping_mul(1664525).wrapping_add(1013904223);
-            self.board[0][col] = if (rng_state % 5) == 0 {
-                Cell::Alive
-            } else {
-                Cell::Dead
-            };
-        }
+        GameBoard::add_random_row(self);
     }
 
     pub fn generation(&self) -> u32 {
         self.generation
     }
+
+    pub fn height(&self) -> usize {
+        self.board.height()
+    }
+
+    // Make the board `height` rows tall (at least one), keeping the bottom
+    // rows in place: the bottom row is the one played, so a pattern stays
+    // the same number of generations away from the keys. Rows are added or
+    // dropped at the top.
+    pub fn set_height(&mut self, height: usize) {
+        let height = height.max(1);
+        let old_height = self.height();
+        if height == old_height {
+            return;
+        }
+
+        let mut board = BitBoard::new(height);
+        for row in 0..old_height.min(height) {
+            let (from, to) = (old_height - 1 - row, height - 1 - row);
+            for col in 0..BOARD_WIDTH {
+                board.set(to, col, self.board.get(from, col));
+            }
+        }
+        self.board = board;
+    }
+
+    // Number of fully live cells; dying cells are on their way out and never
+    // bring new cells to life, so they don't count
+    pub fn population(&self) -> usize {
+        self.board.population()
+    }
+
+    // True once every cell has died out
+    pub fn is_empty(&self) -> bool {
+        self.population() == 0
+    }
+
+    // Hash of every cell on the board, for spotting repeated generations
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.board.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // Fraction of the board that is alive, 0.0-1.0
+    pub fn density(&self) -> f64 {
+        self.population() as f64 / (BOARD_WIDTH * self.height()) as f64
+    }
+
+    // Stop injecting random top rows once this many generations have passed,
+    // letting the board evolve (and possibly die out) on its own
+    pub fn set_random_injection_generations(&mut self, limit: Option<u32>) {
+        self.random_injection_generations = limit;
+    }
+
+    pub fn random_injection_generations(&self) -> Option<u32> {
+        self.random_injection_generations
+    }
+
+    // Switch to a "Generations" automaton (the Brian's Brain family) where a
+    // cell that would die first decays through this many states. Only fully
+    // live cells count as neighbors or play notes. 0 keeps plain Conway rules.
+    pub fn set_decay_states(&mut self, states: u8) {
+        self.decay_states = states;
+    }
+
+    pub fn decay_states(&self) -> u8 {
+        self.decay_states
+    }
+
+    pub fn set_ruleset(&mut self, ruleset: Ruleset) {
+        self.ruleset = ruleset;
+    }
+
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    // Choose whether the board edges are dead or wrap around
+    pub fn set_boundary_mode(&mut self, mode: BoundaryMode) {
+        self.boundary_mode = mode;
+    }
+
+    pub fn boundary_mode(&self) -> BoundaryMode {
+        self.boundary_mode
+    }
+
+    // Count all eight surrounding cells (Moore) or only the orthogonal four (VonNeumann)
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+    }
+
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    // Seed the injected top rows so a run can be replayed exactly
+    pub fn set_injection_seed(&mut self, seed: Option<u64>) {
+        self.injection_seed = seed;
+    }
+
+    pub fn injection_seed(&self) -> Option<u64> {
+        self.injection_seed
+    }
+
+    // Chance (0.0-1.0) that each cell of an injected top row starts alive
+    pub fn set_injection_probability(&mut self, probability: f64) {
+        self.injection_probability = probability.clamp(0.0, 1.0);
+    }
+
+    pub fn injection_probability(&self) -> f64 {
+        self.injection_probability
+    }
+
+    // Turn random top-row injection off entirely, so the board evolves only
+    // from its seeded patterns
+    pub fn set_random_injection(&mut self, enabled: bool) {
+        self.random_injection = enabled;
+    }
+
+    pub fn random_injection_enabled(&self) -> bool {
+        self.random_injection
+    }
+
+    // Whether a random top row should still be injected at the current generation
+    pub fn random_injection_active(&self) -> bool {
+        if !self.random_injection {
+            return false;
+        }
+        match self.random_injection_generations {
+            Some(limit) => self.generation < limit,
+            None => true,
+        }
+    }
 }
@@ -0,0 +1,81 @@
+// Pattern file loading for Conway's Steinway
+// Looks at a pattern file's contents to tell RLE from Plaintext (.cells) and
+// hands it to the matching decoder, so callers don't need to know the format.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{GameOfLife, RleError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PatternFormat {
+    Rle,
+    Plaintext,
+}
+
+#[derive(Debug)]
+pub enum PatternError {
+    Io(io::Error),
+    Rle(RleError),
+    UnknownFormat(String),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::Io(e) => write!(f, "Unable to read pattern file: {}", e),
+            PatternError::Rle(e) => write!(f, "Invalid RLE pattern: {}", e),
+            PatternError::UnknownFormat(line) => {
+                write!(f, "Unrecognized pattern format (expected RLE or Plaintext), first line: '{}'", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl From<io::Error> for PatternError {
+    fn from(e: io::Error) -> Self {
+        PatternError::Io(e)
+    }
+}
+
+impl From<RleError> for PatternError {
+    fn from(e: RleError) -> Self {
+        PatternError::Rle(e)
+    }
+}
+
+/// Guess a pattern's format from its first non-blank line: "#" comments and
+/// the "x = .." header mean RLE, "!" comments or a bare ./O grid mean Plaintext
+pub fn detect_format(text: &str) -> Option<PatternFormat> {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+
+    if line.starts_with('#') || (line.starts_with('x') && line.contains('=')) {
+        Some(PatternFormat::Rle)
+    } else if line.starts_with('!') || line.chars().all(|c| matches!(c, '.' | 'O' | '*')) {
+        Some(PatternFormat::Plaintext)
+    } else if line.chars().all(|c| c.is_ascii_digit() || matches!(c, 'b' | 'o' | '$' | '!')) {
+        // Headerless RLE data such as "bob$2bo$3o!"
+        Some(PatternFormat::Rle)
+    } else {
+        None
+    }
+}
+
+impl GameOfLife {
+    /// Load an RLE or Plaintext pattern file, detecting the format from its contents
+    pub fn from_pattern_file(path: &Path) -> Result<GameOfLife, PatternError> {
+        let text = fs::read_to_string(path)?;
+        match detect_format(&text) {
+            Some(PatternFormat::Rle) => Ok(GameOfLife::from_rle(&text)?),
+            Some(PatternFormat::Plaintext) => Ok(GameOfLife::from_plaintext(&text)),
+            None => {
+                let first_line = text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("");
+                Err(PatternError::UnknownFormat(first_line.to_string()))
+            }
+        }
+    }
+}
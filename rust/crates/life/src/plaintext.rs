@@ -0,0 +1,52 @@
+// Plaintext (.cells) pattern import and export for Conway's Steinway
+// The simplest pattern format: "!" comment lines followed by a grid where
+// "O" is alive and "." is dead. Most tutorials and the LifeWiki offer it
+// alongside RLE.
+
+use crate::{Cell, GameOfLife, BOARD_WIDTH};
+
+impl GameOfLife {
+    /// Decode a .cells pattern onto an empty board at the top-left corner.
+    /// "O" and "*" are alive, anything else is dead, and cells past the board
+    /// edges are clipped.
+    pub fn from_plaintext(text: &str) -> GameOfLife {
+        let mut game = GameOfLife::new();
+        let rows = text.lines().filter(|line| !line.starts_with('!'));
+
+        for (row, line) in rows.enumerate().take(game.height()) {
+            for (col, ch) in line.chars().enumerate().take(BOARD_WIDTH) {
+                if ch == 'O' || ch == '*' {
+                    game.set_cell(row, col, Cell::Alive);
+                }
+            }
+        }
+
+        game
+    }
+
+    /// Encode the live cells as a .cells pattern covering their bounding box
+    pub fn to_plaintext(&self) -> String {
+        let mut text = format!("!Conway's Steinway, generation {}\n", self.generation());
+
+        let live: Vec<(usize, usize)> = (0..self.height())
+            .flat_map(|row| (0..BOARD_WIDTH).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.get_cell(row, col) == Cell::Alive)
+            .collect();
+        if live.is_empty() {
+            return text;
+        }
+
+        let min_row = live.iter().map(|&(row, _)| row).min().unwrap_or(0);
+        let max_row = live.iter().map(|&(row, _)| row).max().unwrap_or(0);
+        let min_col = live.iter().map(|&(_, col)| col).min().unwrap_or(0);
+        let max_col = live.iter().map(|&(_, col)| col).max().unwrap_or(0);
+
+        for row in min_row..=max_row {
+            text.extend((min_col..=max_col).map(|col| {
+                if self.get_cell(row, col) == Cell::Alive { 'O' } else { '.' }
+            }));
+            text.push('\n');
+        }
+        text
+    }
+}
@@ -0,0 +1,177 @@
+// RLE pattern import and export for Conway's Steinway
+// Decodes the run-length-encoded format used by the LifeWiki and most Life
+// editors, so patterns can be dropped onto the board without hand-written
+// set_cell calls, and encodes the board so interesting generations can be shared.
+
+// Longest line written by to_rle, as recommended by the format description
+const RLE_LINE_WIDTH: usize = 70;
+
+use std::fmt;
+
+use crate::{Cell, GameOfLife, RuleParseError, Ruleset, BOARD_WIDTH};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RleError {
+    InvalidHeader(String),
+    InvalidRule(RuleParseError),
+    UnexpectedChar(char),
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RleError::InvalidHeader(line) => write!(f, "Invalid RLE header '{}'", line),
+            RleError::InvalidRule(e) => write!(f, "Invalid RLE rule: {}", e),
+            RleError::UnexpectedChar(c) => write!(f, "Unexpected character '{}' in RLE data", c),
+        }
+    }
+}
+
+impl std::error::Error for RleError {}
+
+// Check the "x = 3, y = 3, rule = B3/S23" header and return its rule, if any
+fn parse_header(line: &str) -> Result<Option<Ruleset>, RleError> {
+    let mut rule = None;
+
+    for field in line.split(',') {
+        let (key, value) = field.split_once('=')
+            .ok_or_else(|| RleError::InvalidHeader(line.to_string()))?;
+        let value = value.trim();
+        match key.trim() {
+            "x" | "y" => {
+                value.parse::<usize>().map_err(|_| RleError::InvalidHeader(line.to_string()))?;
+            }
+            "rule" => rule = Some(Ruleset::parse(value).map_err(RleError::InvalidRule)?),
+            _ => return Err(RleError::InvalidHeader(line.to_string())),
+        }
+    }
+
+    Ok(rule)
+}
+
+// Append "<count><tag>" (count omitted when 1) for a run
+fn push_run(tokens: &mut Vec<String>, count: usize, tag: char) {
+    if count == 1 {
+        tokens.push(tag.to_string());
+    } else if count > 1 {
+        tokens.push(format!("{}{}", count, tag));
+    }
+}
+
+impl GameOfLife {
+    /// Encode the live cells as an RLE block. The header covers the live-cell
+    /// bounding box rather than the whole board, so the pattern decodes at
+    /// (0, 0) relative to its top-left live cell.
+    pub fn to_rle(&self) -> String {
+        let live: Vec<(usize, usize)> = (0..self.height())
+            .flat_map(|row| (0..BOARD_WIDTH).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.get_cell(row, col) == Cell::Alive)
+            .collect();
+
+        if live.is_empty() {
+            return format!("x = 0, y = 0, rule = {}\n!\n", self.ruleset());
+        }
+
+        let min_row = live.iter().map(|&(row, _)| row).min().unwrap_or(0);
+        let max_row = live.iter().map(|&(row, _)| row).max().unwrap_or(0);
+        let min_col = live.iter().map(|&(_, col)| col).min().unwrap_or(0);
+        let max_col = live.iter().map(|&(_, col)| col).max().unwrap_or(0);
+
+        let mut tokens = Vec::new();
+        let mut pending_rows = 0;
+        for row in min_row..=max_row {
+            let cells: Vec<bool> = (min_col..=max_col)
+                .map(|col| self.get_cell(row, col) == Cell::Alive)
+                .collect();
+            // Trailing dead cells are implied by the end of the row
+            let Some(last_alive) = cells.iter().rposition(|&alive| alive) else {
+                pending_rows += 1;
+                continue;
+            };
+
+            push_run(&mut tokens, pending_rows, '$');
+            pending_rows = 1;
+
+            let mut start = 0;
+            while start <= last_alive {
+                let alive = cells[start];
+                let length = cells[start..=last_alive].iter().take_while(|&&c| c == alive).count();
+                push_run(&mut tokens, length, if alive { 'o' } else { 'b' });
+                start += length;
+            }
+        }
+        tokens.push("!".to_string());
+
+        let mut rle = format!("x = {}, y = {}, rule = {}\n",
+                              max_col - min_col + 1, max_row - min_row + 1, self.ruleset());
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length + token.len() > RLE_LINE_WIDTH {
+                rle.push('\n');
+                line_length = 0;
+            }
+            line_length += token.len();
+            rle.push_str(&token);
+        }
+        rle.push('\n');
+        rle
+    }
+
+    /// Decode an RLE pattern onto an empty board with its top-left corner at (0, 0)
+    pub fn from_rle(rle: &str) -> Result<GameOfLife, RleError> {
+        Self::from_rle_at(rle, 0, 0)
+    }
+
+    /// Decode an RLE pattern with its top-left corner at (row, col). Cells that
+    /// fall outside the board are clipped. A rule in the header is applied to
+    /// the board.
+    pub fn from_rle_at(rle: &str, row: usize, col: usize) -> Result<GameOfLife, RleError> {
+        let mut game = GameOfLife::new();
+        let mut current_row = row;
+        let mut current_col = col;
+        let mut run = 0usize;
+
+        'lines: for line in rle.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('x') {
+                if let Some(ruleset) = parse_header(line)? {
+                    game.set_ruleset(ruleset);
+                }
+                continue;
+            }
+
+            for c in line.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    run = run.saturating_mul(10).saturating_add(digit as usize);
+                    continue;
+                }
+
+                let count = run.max(1);
+                run = 0;
+                match c {
+                    'b' | '.' => current_col = current_col.saturating_add(count),
+                    'o' | 'O' => {
+                        if current_row < game.height() {
+                            for c in current_col..current_col.saturating_add(count).min(BOARD_WIDTH) {
+                                game.set_cell(current_row, c, Cell::Alive);
+                            }
+                        }
+                        current_col = current_col.saturating_add(count);
+                    }
+                    '$' => {
+                        current_row = current_row.saturating_add(count);
+                        current_col = col;
+                    }
+                    '!' => break 'lines,
+                    c if c.is_whitespace() => {}
+                    c => return Err(RleError::UnexpectedChar(c)),
+                }
+            }
+        }
+
+        Ok(game)
+    }
+}
@@ -0,0 +1,135 @@
+// Life-like rulesets in B/S notation for Conway's Steinway
+// "B3/S23" means a dead cell with 3 live neighbors is born and a live cell
+// with 2 or 3 survives. Other rules in the family (HighLife, Day & Night, ...)
+// give the piano a different texture from the same starting board.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleParseError {
+    MissingBirth(String),
+    MissingSurvival(String),
+    InvalidDigit(char),
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleParseError::MissingBirth(rule) => {
+                write!(f, "Invalid rule '{}': expected a birth part like B3", rule)
+            }
+            RuleParseError::MissingSurvival(rule) => {
+                write!(f, "Invalid rule '{}': expected a survival part like S23", rule)
+            }
+            RuleParseError::InvalidDigit(c) => {
+                write!(f, "Invalid neighbor count '{}': counts must be digits 0-8", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+/// Neighbor counts that cause a birth and that let a live cell survive
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ruleset {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
+
+impl Ruleset {
+    /// Classic Conway rules, B3/S23
+    pub fn conway() -> Self {
+        Ruleset {
+            birth: Self::counts(&[3]),
+            survival: Self::counts(&[2, 3]),
+        }
+    }
+
+    fn counts(neighbors: &[u8]) -> [bool; 9] {
+        let mut counts = [false; 9];
+        for &n in neighbors {
+            counts[n as usize] = true;
+        }
+        counts
+    }
+
+    /// Parse B/S notation such as "B3/S23" or "B36/S23" (case-insensitive,
+    /// either part may come first)
+    pub fn parse(rule: &str) -> Result<Ruleset, RuleParseError> {
+        let mut birth = None;
+        let mut survival = None;
+
+        for part in rule.trim().split('/') {
+            let mut chars = part.trim().chars();
+            let target = match chars.next().map(|c| c.to_ascii_uppercase()) {
+                Some('B') => &mut birth,
+                Some('S') => &mut survival,
+                _ => continue,
+            };
+
+            let mut counts = [false; 9];
+            for c in chars {
+                match c.to_digit(10) {
+                    Some(n) if n <= 8 => counts[n as usize] = true,
+                    _ => return Err(RuleParseError::InvalidDigit(c)),
+                }
+            }
+            *target = Some(counts);
+        }
+
+        Ok(Ruleset {
+            birth: birth.ok_or_else(|| RuleParseError::MissingBirth(rule.to_string()))?,
+            survival: survival.ok_or_else(|| RuleParseError::MissingSurvival(rule.to_string()))?,
+        })
+    }
+
+    pub fn is_born(&self, neighbors: u8) -> bool {
+        self.birth.get(neighbors as usize).copied().unwrap_or(false)
+    }
+
+    pub fn survives(&self, neighbors: u8) -> bool {
+        self.survival.get(neighbors as usize).copied().unwrap_or(false)
+    }
+}
+
+impl fmt::Display for Ruleset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |counts: &[bool; 9]| -> String {
+            (0..9).filter(|&n| counts[n]).map(|n| n.to_string()).collect()
+        };
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_known_rules() {
+        assert_eq!(Ruleset::parse("B3/S23").unwrap(), Ruleset::conway());
+        assert_eq!(Ruleset::parse("s23/b3").unwrap(), Ruleset::conway());
+
+        let highlife = Ruleset::parse("B36/S23").unwrap();
+        assert!(highlife.is_born(6));
+        assert!(!highlife.survives(6));
+        assert_eq!(highlife.to_string(), "B36/S23");
+
+        let day_and_night = Ruleset::parse("B3678/S34678").unwrap();
+        assert_eq!(day_and_night.to_string(), "B3678/S34678");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Ruleset::parse("S23"), Err(RuleParseError::MissingBirth("S23".to_string())));
+        assert_eq!(Ruleset::parse("B3"), Err(RuleParseError::MissingSurvival("B3".to_string())));
+        assert_eq!(Ruleset::parse("B39/S23"), Err(RuleParseError::InvalidDigit('9')));
+    }
+}
@@ -0,0 +1,129 @@
+// Board snapshots for Conway's Steinway
+// Saves the board and generation counter to a small binary file so a run can
+// be paused and resumed later.
+//
+// Layout (little-endian):
+//   magic "CSLB" | version u8 | width u16 | height u16 | generation u32 |
+//   live cells, one bit per cell, row-major, padded to a whole byte |
+//   dying flag u8 | if set, one byte of decay states left per cell
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use crate::{Cell, GameOfLife, BOARD_WIDTH};
+
+const MAGIC: &[u8; 4] = b"CSLB";
+const VERSION: u8 = 1;
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+// Reads fields off the front of the snapshot, failing cleanly if it is truncated
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(invalid("Snapshot is truncated".to_string()));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+impl GameOfLife {
+    /// Write the board and generation counter to a binary snapshot file
+    pub fn save_state(&self, path: &Path) -> io::Result<()> {
+        let cell_count = BOARD_WIDTH * self.height();
+        let mut bytes = Vec::with_capacity(16 + cell_count.div_ceil(8));
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(BOARD_WIDTH as u16).to_le_bytes());
+        bytes.extend_from_slice(&(self.height() as u16).to_le_bytes());
+        bytes.extend_from_slice(&self.generation().to_le_bytes());
+
+        let cells: Vec<Cell> = (0..self.height())
+            .flat_map(|row| (0..BOARD_WIDTH).map(move |col| (row, col)))
+            .map(|(row, col)| self.get_cell(row, col))
+            .collect();
+
+        let mut packed = vec![0u8; cell_count.div_ceil(8)];
+        for (i, &cell) in cells.iter().enumerate() {
+            if cell == Cell::Alive {
+                packed[i / 8] |= 1 << (i % 8);
+            }
+        }
+        bytes.extend_from_slice(&packed);
+
+        let has_dying = cells.iter().any(|cell| matches!(cell, Cell::Dying(_)));
+        bytes.push(has_dying as u8);
+        if has_dying {
+            bytes.extend(cells.iter().map(|cell| match cell {
+                Cell::Dying(left) => *left,
+                _ => 0,
+            }));
+        }
+
+        fs::write(path, bytes)
+    }
+
+    /// Read a snapshot written by `save_state`, at the height it was saved
+    /// with. Snapshots from a board of a different width are rejected.
+    pub fn load_state(path: &Path) -> io::Result<GameOfLife> {
+        let bytes = fs::read(path)?;
+        let mut reader = Reader { bytes: &bytes };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(invalid(format!("{} is not a board snapshot", path.display())));
+        }
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(invalid(format!("Unsupported snapshot version {}", version)));
+        }
+        let (width, height) = (reader.u16()? as usize, reader.u16()? as usize);
+        if width != BOARD_WIDTH || height == 0 {
+            return Err(invalid(format!("Snapshot is {}x{} but the board is {} wide",
+                                       width, height, BOARD_WIDTH)));
+        }
+
+        let mut game = GameOfLife::with_dimensions(height);
+        game.generation = reader.u32()?;
+
+        let cell_count = BOARD_WIDTH * height;
+        let packed = reader.take(cell_count.div_ceil(8))?;
+        let dying = if reader.u8()? == 1 { Some(reader.take(cell_count)?) } else { None };
+
+        for i in 0..cell_count {
+            let cell = if packed[i / 8] >> (i % 8) & 1 == 1 {
+                Cell::Alive
+            } else {
+                match dying.map(|states| states[i]) {
+                    Some(left) if left > 0 => Cell::Dying(left),
+                    _ => continue,
+                }
+            };
+            game.set_cell(i / BOARD_WIDTH, i % BOARD_WIDTH, cell);
+        }
+
+        Ok(game)
+    }
+}
@@ -0,0 +1,72 @@
+// Cycle detection for Conway's Steinway
+// Once random injection stops, boards tend to settle into still lifes or
+// short-period oscillators. StabilityDetector remembers the fingerprints of
+// recent generations so the main loop can notice the repetition and stop.
+
+use std::collections::VecDeque;
+
+// Longest cycle looked for by default; covers the common oscillators
+// (blinkers, toads, beacons, pulsars, pentadecathlons are period 2, 3 and 15)
+pub const DEFAULT_MAX_PERIOD: usize = 15;
+
+pub struct StabilityDetector {
+    history: VecDeque<u64>,
+    max_period: usize,
+}
+
+impl StabilityDetector {
+    pub fn new(max_period: usize) -> Self {
+        StabilityDetector {
+            history: VecDeque::with_capacity(max_period),
+            max_period,
+        }
+    }
+
+    /// Record the fingerprint of the latest generation. Returns the period of
+    /// the cycle if it matches one of the last `max_period` generations
+    /// (1 = still life), using the shortest such period.
+    pub fn observe(&mut self, fingerprint: u64) -> Option<usize> {
+        let period = self.history.iter()
+            .rev()
+            .position(|&previous| previous == fingerprint)
+            .map(|index| index + 1);
+
+        if self.max_period > 0 {
+            if self.history.len() == self.max_period {
+                self.history.pop_front();
+            }
+            self.history.push_back(fingerprint);
+        }
+        period
+    }
+
+    pub fn reset(&mut self) {
+        self.history.clear();
+    }
+}
+
+impl Default for StabilityDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_PERIOD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycles_longer_than_max_period_are_ignored() {
+        let mut detector = StabilityDetector::new(2);
+        for fingerprint in [1, 2, 3] {
+            assert_eq!(detector.observe(fingerprint), None);
+        }
+        // 1 has fallen out of the buffer, 2 is still two generations back
+        assert_eq!(detector.observe(1), None);
+        assert_eq!(detector.observe(3), Some(2));
+
+        detector.reset();
+        assert_eq!(detector.observe(3), None);
+        assert_eq!(detector.observe(3), Some(1));
+    }
+}
@@ -0,0 +1,88 @@
+// Browser bindings for Conway's Steinway (the `wasm` feature)
+// A thin wasm-bindgen wrapper around GameOfLife with no audio of its own: each
+// step hands the bottom-row keys back to JavaScript to play through Web Audio.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, GameBoard, GameOfLife, BOARD_WIDTH};
+
+#[wasm_bindgen]
+pub struct WasmGame {
+    game: GameOfLife,
+}
+
+impl Default for WasmGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmGame {
+    // An empty board of the default height
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGame {
+        WasmGame { game: GameOfLife::new() }
+    }
+
+    // An empty board with the given number of rows
+    pub fn with_height(height: u32) -> WasmGame {
+        WasmGame { game: GameOfLife::with_dimensions(height as usize) }
+    }
+
+    // A random board that replays exactly from the same seed
+    pub fn random(seed: u64, alive_probability: f64) -> WasmGame {
+        WasmGame { game: GameBoard::create_random_board_seeded(seed, alive_probability) }
+    }
+
+    // A single pattern from the registry (see PATTERNS), centered on the board
+    pub fn from_pattern_name(name: &str) -> Option<WasmGame> {
+        GameBoard::create_named_board(name).map(|game| WasmGame { game })
+    }
+
+    pub fn next_generation(&mut self) {
+        self.game.next_generation();
+    }
+
+    // Piano keys (0-87) of the live cells in the bottom row, then advance a generation
+    pub fn get_bottom_row_and_advance(&mut self) -> Vec<u32> {
+        GameBoard::get_bottom_row_and_advance(&mut self.game)
+            .into_iter()
+            .map(|key| key as u32)
+            .collect()
+    }
+
+    pub fn get_cell(&self, row: u32, col: u32) -> bool {
+        self.game.get_cell(row as usize, col as usize) == Cell::Alive
+    }
+
+    pub fn set_cell(&mut self, row: u32, col: u32, alive: bool) {
+        let state = if alive { Cell::Alive } else { Cell::Dead };
+        self.game.set_cell(row as usize, col as usize, state);
+    }
+
+    pub fn set_random_injection(&mut self, enabled: bool) {
+        self.game.set_random_injection(enabled);
+    }
+
+    pub fn width(&self) -> u32 {
+        BOARD_WIDTH as u32
+    }
+
+    pub fn height(&self) -> u32 {
+        self.game.height() as u32
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.game.generation()
+    }
+
+    pub fn population(&self) -> u32 {
+        self.game.population() as u32
+    }
+
+    // The board as text, the same as printing it on the command line
+    pub fn render(&self) -> String {
+        self.game.to_string()
+    }
+}
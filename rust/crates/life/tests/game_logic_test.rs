@@ -1,7 +1,7 @@
 // Integration tests for Game of Life logic
 // These tests verify core game logic without relying on mocks or external resources
 
-use life::{Cell, GameOfLife, GameBoard, BOARD_WIDTH, BOARD_HEIGHT};
+use life::{BoundaryMode, Cell, GameOfLife, GameBoard, Neighborhood, Ruleset, StabilityDetector, BOARD_WIDTH, BOARD_HEIGHT, MIN_VELOCITY};
 
 #[test]
 fn test_new_game_creation() {
@@ -182,3 +182,427 @@ fn test_bottom_row_extraction() {
     // Instead, we just verify the generation incremented correctly, which is the important part.
     assert_eq!(game.generation(), 1, "Generation should be incremented after extraction");
 }
+
+#[test]
+fn test_random_injection_stops_after_limit() {
+    let mut game = GameOfLife::new();
+    game.set_injection_seed(Some(1));
+    game.set_random_injection_generations(Some(3));
+
+    // Injection runs while the generation is below the limit, filling the
+    // otherwise empty board from the top
+    for _ in 0..3 {
+        assert!(game.random_injection_active());
+        GameBoard::get_bottom_row_and_advance(&mut game);
+    }
+    assert!(!game.random_injection_active());
+    assert!(game.population() > 0, "Rows should be injected before the limit");
+
+    // From here on the board only follows the Life rules, exactly like one
+    // that never injects, and nothing new appears at the top
+    let mut uninjected = game.clone();
+    uninjected.set_random_injection(false);
+    for step in 1..=10 {
+        GameBoard::get_bottom_row_and_advance(&mut game);
+        GameBoard::get_bottom_row_and_advance(&mut uninjected);
+        for row in 0..BOARD_HEIGHT {
+            for col in 0..BOARD_WIDTH {
+                assert_eq!(game.get_cell(row, col), uninjected.get_cell(row, col),
+                           "No random cells should be injected after the limit");
+            }
+        }
+        for col in 0..BOARD_WIDTH {
+            assert_eq!(game.get_cell(0, col), Cell::Dead, "The top row should stay empty");
+        }
+        assert_eq!(game.generation(), 3 + step, "The board should keep advancing");
+    }
+}
+
+#[test]
+fn test_no_injection_glider_leaves_top_row_empty() {
+    let mut game = GameOfLife::new();
+    game.set_random_injection(false);
+    GameBoard::create_glider(&mut game, 5, 40);
+
+    // Explicit calls are skipped too
+    GameBoard::add_random_row(&mut game);
+
+    for _ in 0..10 {
+        GameBoard::get_bottom_row_and_advance(&mut game);
+        for col in 0..BOARD_WIDTH {
+            assert_eq!(game.get_cell(0, col), Cell::Dead,
+                       "No random cells should be injected when injection is disabled");
+        }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_advance_matches_game_board() {
+    let mut old_api = GameBoard::create_complex_board();
+    let mut new_api = GameBoard::create_complex_board();
+    // Unseeded rows differ from board to board
+    old_api.set_injection_seed(Some(3));
+    new_api.set_injection_seed(Some(3));
+
+    for _ in 0..25 {
+        let old_keys = old_api.get_bottom_row_and_advance();
+        let new_keys = GameBoard::get_bottom_row_and_advance(&mut new_api);
+        assert_eq!(old_keys, new_keys, "Both entry points should play the same keys");
+        assert_eq!(old_api.generation(), new_api.generation());
+
+        for row in 0..BOARD_HEIGHT {
+            for col in 0..BOARD_WIDTH {
+                assert_eq!(old_api.get_cell(row, col), new_api.get_cell(row, col),
+                           "Boards diverged at ({}, {})", row, col);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_random_top_row_matches_game_board() {
+    let mut old_api = GameOfLife::new();
+    let mut new_api = GameOfLife::new();
+    old_api.set_injection_seed(Some(3));
+    new_api.set_injection_seed(Some(3));
+
+    old_api.add_random_top_row();
+    GameBoard::add_random_row(&mut new_api);
+
+    for col in 0..BOARD_WIDTH {
+        assert_eq!(old_api.get_cell(0, col), new_api.get_cell(0, col));
+    }
+}
+
+#[test]
+fn test_decay_states_pass_through_dying() {
+    let mut game = GameOfLife::new();
+    game.set_decay_states(3);
+
+    // A lone cell dies of underpopulation and then decays one state per generation
+    game.set_cell(10, 10, Cell::Alive);
+    let mut states = Vec::new();
+    for _ in 0..5 {
+        game.next_generation();
+        states.push(game.get_cell(10, 10));
+    }
+    assert_eq!(states, vec![Cell::Dying(3), Cell::Dying(2), Cell::Dying(1), Cell::Dead, Cell::Dead]);
+
+    // Conway mode (the default) kills it outright
+    let mut conway = GameOfLife::new();
+    conway.set_cell(10, 10, Cell::Alive);
+    conway.next_generation();
+    assert_eq!(conway.get_cell(10, 10), Cell::Dead);
+}
+
+#[test]
+fn test_dying_cells_do_not_play_notes() {
+    let mut game = GameOfLife::new();
+    game.set_random_injection(false);
+    game.set_cell(BOARD_HEIGHT - 1, 5, Cell::Dying(2));
+    game.set_cell(BOARD_HEIGHT - 1, 7, Cell::Alive);
+
+    assert_eq!(GameBoard::get_bottom_row_and_advance(&mut game), vec![7]);
+}
+
+#[test]
+fn test_glider_crosses_a_taller_board() {
+    let mut game = GameOfLife::with_dimensions(60);
+    assert_eq!(game.height(), 60);
+    game.set_random_injection(false);
+    GameBoard::create_glider(&mut game, 0, 0);
+
+    // Four generations move the glider one cell down and right; past row 40
+    // it is still on the board
+    for _ in 0..4 * 50 {
+        game.next_generation();
+    }
+    let mut expected = GameOfLife::with_dimensions(60);
+    GameBoard::create_glider(&mut expected, 50, 50);
+    for row in 0..60 {
+        for col in 0..BOARD_WIDTH {
+            assert_eq!(game.get_cell(row, col), expected.get_cell(row, col), "Mismatch at ({}, {})", row, col);
+        }
+    }
+    assert_eq!(game.to_string().lines().count(), 60 + 4);
+
+    // Rows scroll down to the bottom row, 60 rows below the top
+    let mut scrolled = GameOfLife::with_dimensions(60);
+    scrolled.set_random_injection(false);
+    scrolled.set_cell(0, 10, Cell::Alive);
+    scrolled.set_cell(0, 11, Cell::Alive);
+    scrolled.set_cell(1, 10, Cell::Alive);
+    scrolled.set_cell(1, 11, Cell::Alive);
+    let played: Vec<Vec<usize>> = (0..60).map(|_| GameBoard::get_bottom_row_and_advance(&mut scrolled)).collect();
+    assert!(played[..58].iter().all(|keys| keys.is_empty()));
+    assert_eq!(played[58], vec![10, 11]);
+}
+
+#[test]
+fn test_set_height_keeps_the_bottom_rows() {
+    let mut game = GameOfLife::new();
+    game.set_cell(BOARD_HEIGHT - 1, 5, Cell::Alive);
+    game.set_cell(0, 6, Cell::Alive);
+
+    game.set_height(60);
+    assert_eq!(game.height(), 60);
+    assert_eq!(game.get_cell(59, 5), Cell::Alive);
+    assert_eq!(game.get_cell(20, 6), Cell::Alive);
+    assert_eq!(game.population(), 2);
+
+    // Shrinking drops the top rows
+    game.set_height(10);
+    assert_eq!(game.get_cell(9, 5), Cell::Alive);
+    assert_eq!(game.population(), 1);
+}
+
+#[test]
+fn test_lone_cell_dies_out() {
+    let mut game = GameOfLife::new();
+    assert!(game.is_empty());
+
+    game.set_cell(20, 40, Cell::Alive);
+    assert!(!game.is_empty());
+
+    // A cell with no neighbors dies of loneliness
+    game.next_generation();
+    assert!(game.is_empty());
+    assert_eq!(game.population(), 0);
+}
+
+#[test]
+fn test_row_neighbor_counts_match_per_cell_counts() {
+    // The bit-parallel row counts next_generation uses, checked against
+    // counting each cell's eight neighbors one at a time
+    for mode in [BoundaryMode::Dead, BoundaryMode::Toroidal] {
+        let mut game = GameBoard::create_random_board_seeded(7, 0.4);
+        game.set_boundary_mode(mode);
+
+        for row in 0..BOARD_HEIGHT {
+            let counts = game.neighbor_counts(row);
+            for (col, &count) in counts.iter().enumerate() {
+                let mut expected = 0;
+                for dr in [-1isize, 0, 1] {
+                    for dc in [-1isize, 0, 1] {
+                        if dr == 0 && dc == 0 {
+                            continue;
+                        }
+                        let (r, c) = (row as isize + dr, col as isize + dc);
+                        let (r, c) = match mode {
+                            BoundaryMode::Toroidal => (r.rem_euclid(BOARD_HEIGHT as isize), c.rem_euclid(BOARD_WIDTH as isize)),
+                            BoundaryMode::Dead => (r, c),
+                        };
+                        if r >= 0 && c >= 0 && game.get_cell(r as usize, c as usize) == Cell::Alive {
+                            expected += 1;
+                        }
+                    }
+                }
+                assert_eq!(count, expected, "{:?} neighbors of ({}, {})", mode, row, col);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_von_neumann_ignores_diagonal_neighbors() {
+    // Only the four diagonal neighbors of (10, 10) are alive
+    let mut game = GameOfLife::new();
+    for (row, col) in [(9, 9), (9, 11), (11, 9), (11, 11)] {
+        game.set_cell(row, col, Cell::Alive);
+    }
+    assert_eq!(game.neighborhood(), Neighborhood::Moore);
+    assert_eq!(game.neighbor_counts(10)[10], 4);
+
+    game.set_neighborhood(Neighborhood::VonNeumann);
+    assert_eq!(game.neighbor_counts(10)[10], 0);
+
+    // Orthogonal neighbors still count
+    game.set_cell(10, 11, Cell::Alive);
+    assert_eq!(game.neighbor_counts(10)[10], 1);
+}
+
+#[test]
+fn test_toroidal_glider_wraps_across_right_edge() {
+    // The glider travels one cell down and right every four generations
+    let mut wrapped = GameOfLife::new();
+    wrapped.set_boundary_mode(BoundaryMode::Toroidal);
+    GameBoard::create_glider(&mut wrapped, 10, BOARD_WIDTH - 5);
+
+    let mut walled = GameOfLife::new();
+    assert_eq!(walled.boundary_mode(), BoundaryMode::Dead);
+    GameBoard::create_glider(&mut walled, 10, BOARD_WIDTH - 5);
+
+    for _ in 0..40 {
+        wrapped.next_generation();
+        walled.next_generation();
+    }
+
+    // Ten cells further right puts the glider back near the left edge
+    let mut expected = GameOfLife::new();
+    GameBoard::create_glider(&mut expected, 20, 5);
+    for row in 0..BOARD_HEIGHT {
+        for col in 0..BOARD_WIDTH {
+            assert_eq!(wrapped.get_cell(row, col), expected.get_cell(row, col),
+                       "Wrapped glider mismatch at ({}, {})", row, col);
+        }
+    }
+
+    // With dead edges nothing ever reaches the left side
+    for row in 0..BOARD_HEIGHT {
+        for col in 0..10 {
+            assert_eq!(walled.get_cell(row, col), Cell::Dead);
+        }
+    }
+}
+
+#[test]
+fn test_corner_blinker_under_both_boundary_modes() {
+    let seed = |mode| {
+        let mut game = GameOfLife::new();
+        game.set_boundary_mode(mode);
+        for col in 0..3 {
+            game.set_cell(0, col, Cell::Alive);
+        }
+        game
+    };
+
+    // Toroidal: the vertical phase spans the top and bottom rows and the
+    // blinker keeps oscillating
+    let mut wrapped = seed(BoundaryMode::Toroidal);
+    wrapped.next_generation();
+    assert_eq!(wrapped.get_cell(BOARD_HEIGHT - 1, 1), Cell::Alive);
+    assert_eq!(wrapped.get_cell(0, 1), Cell::Alive);
+    assert_eq!(wrapped.get_cell(1, 1), Cell::Alive);
+    assert_eq!(wrapped.population(), 3);
+    wrapped.next_generation();
+    for col in 0..3 {
+        assert_eq!(wrapped.get_cell(0, col), Cell::Alive);
+    }
+    assert_eq!(wrapped.population(), 3);
+
+    // Dead edges clip the vertical phase and the blinker dies out
+    let mut walled = seed(BoundaryMode::Dead);
+    walled.next_generation();
+    assert_eq!(walled.get_cell(BOARD_HEIGHT - 1, 1), Cell::Dead);
+    assert_eq!(walled.population(), 2);
+    walled.next_generation();
+    assert_eq!(walled.population(), 0);
+}
+
+#[test]
+fn test_highlife_replicator() {
+    // The standard HighLife replicator copies itself diagonally every 12 generations
+    let replicator = ["..OOO", ".O..O", "O...O", "O..O.", "OOO.."];
+    let place = |game: &mut GameOfLife, row: usize, col: usize| {
+        for (r, line) in replicator.iter().enumerate() {
+            for (c, ch) in line.chars().enumerate() {
+                if ch == 'O' {
+                    game.set_cell(row + r, col + c, Cell::Alive);
+                }
+            }
+        }
+    };
+    let run = |ruleset: Ruleset| {
+        let mut game = GameOfLife::with_ruleset(ruleset);
+        place(&mut game, 15, 40);
+        for _ in 0..12 {
+            game.next_generation();
+        }
+        game
+    };
+
+    let mut two_copies = GameOfLife::new();
+    place(&mut two_copies, 13, 38);
+    place(&mut two_copies, 17, 42);
+    let matches_copies = |game: &GameOfLife| {
+        (0..BOARD_HEIGHT).all(|row| (0..BOARD_WIDTH).all(|col| {
+            game.get_cell(row, col) == two_copies.get_cell(row, col)
+        }))
+    };
+
+    let highlife = run(Ruleset::parse("B36/S23").unwrap());
+    assert!(matches_copies(&highlife), "HighLife should replicate the seed:\n{}", highlife);
+
+    let conway = run(Ruleset::conway());
+    assert!(!matches_copies(&conway), "Conway rules should not replicate the seed");
+    assert_eq!(GameOfLife::new().ruleset(), Ruleset::conway());
+}
+
+#[test]
+fn test_population_and_density() {
+    let mut game = GameOfLife::new();
+    assert_eq!(game.population(), 0);
+    assert_eq!(game.density(), 0.0);
+
+    GameBoard::create_block(&mut game, 10, 10);
+    assert_eq!(game.population(), 4);
+    assert!((game.density() - 4.0 / (BOARD_WIDTH * BOARD_HEIGHT) as f64).abs() < 1e-12);
+
+    // Reading the metrics doesn't disturb the board
+    assert_eq!(game.generation(), 0);
+    game.next_generation();
+    assert_eq!(game.population(), 4);
+
+    for col in 0..BOARD_WIDTH {
+        game.set_cell(0, col, Cell::Alive);
+    }
+    assert!((game.density() - (BOARD_WIDTH + 4) as f64 / (BOARD_WIDTH * BOARD_HEIGHT) as f64).abs() < 1e-12);
+}
+
+#[test]
+fn test_stability_detector_finds_blinker_period() {
+    let mut game = GameOfLife::new();
+    GameBoard::create_blinker(&mut game, 10, 10);
+    let mut detector = StabilityDetector::default();
+
+    assert_eq!(detector.observe(game.fingerprint()), None);
+    let mut detected = None;
+    for _ in 0..4 {
+        game.next_generation();
+        detected = detector.observe(game.fingerprint());
+        if detected.is_some() {
+            break;
+        }
+    }
+
+    assert_eq!(detected, Some(2));
+    assert_eq!(game.generation(), 2);
+
+    // A still life repeats every generation
+    let mut still = GameOfLife::new();
+    GameBoard::create_block(&mut still, 5, 5);
+    let mut detector = StabilityDetector::default();
+    detector.observe(still.fingerprint());
+    still.next_generation();
+    assert_eq!(detector.observe(still.fingerprint()), Some(1));
+}
+
+#[test]
+fn test_crowded_cells_play_louder() {
+    let mut game = GameOfLife::new();
+    let bottom = BOARD_HEIGHT - 1;
+
+    // An isolated cell on the left and a cell boxed in by five neighbors on the right
+    game.set_cell(bottom, 10, Cell::Alive);
+    for col in 59..=61 {
+        game.set_cell(bottom - 1, col, Cell::Alive);
+    }
+    game.set_cell(bottom, 59, Cell::Alive);
+    game.set_cell(bottom, 60, Cell::Alive);
+    game.set_cell(bottom, 61, Cell::Alive);
+
+    let notes = GameBoard::get_bottom_row_with_velocity(&mut game);
+    let velocity = |key: usize| notes.iter().find(|&&(k, _)| k == key).map(|&(_, v)| v).unwrap();
+
+    assert_eq!(notes.iter().map(|&(key, _)| key).collect::<Vec<_>>(), vec![10, 59, 60, 61]);
+    assert_eq!(velocity(10), MIN_VELOCITY);
+    assert!(velocity(60) > velocity(59));
+    assert!(velocity(59) > velocity(10));
+
+    // The plain API plays the same keys
+    let mut again = GameOfLife::new();
+    again.set_cell(bottom, 10, Cell::Alive);
+    again.set_cell(bottom - 1, 60, Cell::Alive);
+    assert_eq!(GameBoard::get_bottom_row_and_advance(&mut again), vec![10]);
+}
@@ -1,7 +1,16 @@
 // Integration tests for Game of Life patterns
 // These tests verify that patterns are correctly generated and evolve as expected
 
-use life::{Cell, GameOfLife, GameBoard, BOARD_WIDTH, BOARD_HEIGHT};
+use life::{
+    detect_format, find_pattern, reflect_cells, rotate_cells, Cell, GameOfLife, GameBoard, PatternError, PatternFormat,
+    Reflection, RleError, Rotation,
+    BOARD_WIDTH, BOARD_HEIGHT, FUR_ELISE, GLIDER_CELLS, PATTERNS,
+};
+
+// Helper function to check two boards hold the same cells
+fn boards_match(a: &GameOfLife, b: &GameOfLife) -> bool {
+    (0..BOARD_HEIGHT).all(|row| (0..BOARD_WIDTH).all(|col| a.get_cell(row, col) == b.get_cell(row, col)))
+}
 
 // Helper function to count alive cells in a game board
 fn count_alive_cells(game: &GameOfLife) -> usize {
@@ -18,7 +27,7 @@ fn count_alive_cells(game: &GameOfLife) -> usize {
 
 #[test]
 fn test_create_random_board() {
-    let game = GameBoard::create_random_board();
+    let game = GameBoard::create_random_board(0.25);
     
     // Random board should have some alive cells (typically around 25%)
     let alive_count = count_alive_cells(&game);
@@ -169,3 +178,378 @@ fn test_bottom_row_extraction_with_patterns() {
     // Verify the generation advanced
     assert_eq!(game.generation(), 1);
 }
+
+#[test]
+fn test_soup_board_confined_to_region() {
+    let (top, left, width, height) = (10, 30, 12, 8);
+    let game = GameBoard::create_soup_board(7, 0.5, top, left, width, height);
+
+    let mut alive_in_region = 0;
+    for row in 0..BOARD_HEIGHT {
+        for col in 0..BOARD_WIDTH {
+            let inside = (top..top + height).contains(&row) && (left..left + width).contains(&col);
+            if game.get_cell(row, col) == Cell::Alive {
+                assert!(inside, "Cell ({}, {}) outside the soup region is alive", row, col);
+                alive_in_region += 1;
+            }
+        }
+    }
+    assert!(alive_in_region > 0, "The soup region should contain live cells");
+
+    // Same seed, same soup
+    let again = GameBoard::create_soup_board(7, 0.5, top, left, width, height);
+    for row in top..top + height {
+        for col in left..left + width {
+            assert_eq!(game.get_cell(row, col), again.get_cell(row, col));
+        }
+    }
+}
+
+#[test]
+fn test_huge_soup_region_is_clipped_to_the_board() {
+    let game = GameBoard::create_soup_board(7, 0.5, 10, 30, usize::MAX, usize::MAX);
+    assert!(game.population() > 0);
+    assert_eq!(game.get_cell(9, 30), Cell::Dead);
+}
+
+#[test]
+fn test_fur_elise_melody_opening() {
+    // E5-D#5-E5-D#5-E5-B4-D5-C5-A4, with A4 on key 48
+    let opening: Vec<usize> = FUR_ELISE.iter().take(9).map(|&(key, _)| key).collect();
+    assert_eq!(opening, vec![55, 54, 55, 54, 55, 50, 53, 51, 48]);
+
+    // The melody's shape, in semitones between successive notes
+    let intervals: Vec<i32> = opening.windows(2).map(|pair| pair[1] as i32 - pair[0] as i32).collect();
+    assert_eq!(intervals, vec![-1, 1, -1, 1, -5, 3, -2, -3]);
+
+    // Second phrase C4-E4-A4-B4 climbs from middle C back to the A4 above
+    let second: Vec<usize> = FUR_ELISE.iter().skip(9).map(|&(key, _)| key).collect();
+    assert_eq!(second, vec![39, 43, 48, 50]);
+
+    // Every note is a real piano key with a duration
+    assert!(FUR_ELISE.iter().all(|&(key, duration)| key < BOARD_WIDTH && duration > 0));
+}
+
+#[test]
+fn test_rle_glider() {
+    let rle = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!";
+    let mut decoded = GameOfLife::from_rle_at(rle, 10, 20).unwrap();
+    assert_eq!(count_alive_cells(&decoded), 5);
+    assert_eq!(decoded.get_cell(10, 21), Cell::Alive);
+    assert_eq!(decoded.get_cell(12, 20), Cell::Alive);
+
+    // create_glider draws the phase two generations later, one row lower
+    decoded.next_generation();
+    decoded.next_generation();
+    let mut expected = GameOfLife::new();
+    GameBoard::create_glider(&mut expected, 11, 20);
+    assert!(boards_match(&decoded, &expected));
+}
+
+#[test]
+fn test_rle_pulsar() {
+    let rle = "x = 13, y = 13, rule = B3/S23\n\
+               2b3o3b3o2b2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2b2$\n\
+               2b3o3b3o2b$o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!";
+
+    // create_pulsar leaves a two-cell margin around the 13x13 pattern
+    let decoded = GameOfLife::from_rle_at(rle, 7, 12).unwrap();
+    let mut expected = GameOfLife::new();
+    GameBoard::create_pulsar(&mut expected, 5, 10);
+    assert!(boards_match(&decoded, &expected));
+    assert_eq!(count_alive_cells(&decoded), 48);
+}
+
+#[test]
+fn test_rle_clipping_and_errors() {
+    // A 100-cell row at the top-left is clipped to the board width
+    let wide = GameOfLife::from_rle("100o!").unwrap();
+    assert_eq!(count_alive_cells(&wide), BOARD_WIDTH);
+
+    // Rows that run past the bottom edge are dropped
+    let below = GameOfLife::from_rle_at("3o$3o!", BOARD_HEIGHT - 1, 0).unwrap();
+    assert_eq!(count_alive_cells(&below), 3);
+
+    assert_eq!(GameOfLife::from_rle("2oz!").err(), Some(RleError::UnexpectedChar('z')));
+    assert!(matches!(GameOfLife::from_rle("x = three, y = 1\no!"), Err(RleError::InvalidHeader(_))));
+    assert!(matches!(GameOfLife::from_rle("x = 1, y = 1, rule = B9/S23\no!"), Err(RleError::InvalidRule(_))));
+}
+
+
+#[test]
+fn test_rle_export_round_trip() {
+    let mut board = GameOfLife::new();
+    GameBoard::create_glider(&mut board, 5, 10);
+    GameBoard::create_glider(&mut board, 5, 30);
+    GameBoard::create_glider(&mut board, 20, 60);
+    GameBoard::create_glider(&mut board, 31, 12);
+
+    let rle = board.to_rle();
+    assert!(rle.starts_with("x = 53, y = 29, rule = B3/S23\n"), "Unexpected header: {}", rle);
+    assert!(rle.trim_end().ends_with('!'));
+    assert!(rle.lines().all(|line| line.len() <= 70));
+
+    // The pattern decodes relative to its bounding box, which starts at (5, 10)
+    let decoded = GameOfLife::from_rle_at(&rle, 5, 10).unwrap();
+    assert!(boards_match(&decoded, &board));
+}
+
+#[test]
+fn test_rle_export_small_and_empty() {
+    let mut board = GameOfLife::new();
+    GameBoard::create_glider(&mut board, 0, 0);
+    assert_eq!(board.to_rle(), "x = 3, y = 3, rule = B3/S23\n2bo$obo$b2o!\n");
+
+    let empty = GameOfLife::new().to_rle();
+    assert_eq!(empty, "x = 0, y = 0, rule = B3/S23\n!\n");
+    assert_eq!(count_alive_cells(&GameOfLife::from_rle(&empty).unwrap()), 0);
+}
+
+#[test]
+fn test_seeded_random_boards_are_reproducible() {
+    let first = GameBoard::create_random_board_seeded(7, 0.3);
+    let again = GameBoard::create_random_board_seeded(7, 0.3);
+    let other = GameBoard::create_random_board_seeded(8, 0.3);
+
+    assert!(boards_match(&first, &again));
+    assert_ne!(count_alive_cells(&first), count_alive_cells(&other));
+
+    // Roughly the requested fraction of cells start alive
+    let density = count_alive_cells(&first) as f64 / (BOARD_WIDTH * BOARD_HEIGHT) as f64;
+    assert!((density - 0.3).abs() < 0.05, "Density {} far from 0.3", density);
+}
+
+#[test]
+fn test_unseeded_random_boards_differ() {
+    let first = GameBoard::create_random_board(0.3);
+    let second = GameBoard::create_random_board(0.3);
+    assert!(!boards_match(&first, &second), "Each unseeded board should get a fresh seed");
+
+    // The requested probability is respected rather than a fixed 25%
+    assert_eq!(count_alive_cells(&GameBoard::create_random_board(0.0)), 0);
+    assert_eq!(count_alive_cells(&GameBoard::create_random_board(1.0)), BOARD_WIDTH * BOARD_HEIGHT);
+}
+
+#[test]
+fn test_injected_rows_follow_the_injection_seed() {
+    let top_row = |seed: Option<u64>| {
+        let mut game = GameOfLife::new();
+        game.set_injection_seed(seed);
+        GameBoard::add_random_row(&mut game);
+        (0..BOARD_WIDTH).map(|col| game.get_cell(0, col)).collect::<Vec<Cell>>()
+    };
+
+    assert_eq!(top_row(Some(1)), top_row(Some(1)));
+    assert_ne!(top_row(Some(1)), top_row(Some(2)));
+    // Unseeded boards get fresh rows every time
+    assert_ne!(top_row(None), top_row(None));
+}
+
+#[test]
+fn test_injected_rows_honor_probability() {
+    // Alive cells across many injected rows, seeded and unseeded
+    let alive_fraction = |probability: f64, seed: Option<u64>| {
+        let mut game = GameOfLife::new();
+        game.set_injection_seed(seed);
+        game.set_injection_probability(probability);
+        let mut alive = 0;
+        let rows = 50;
+        for _ in 0..rows {
+            GameBoard::add_random_row(&mut game);
+            alive += (0..BOARD_WIDTH).filter(|&col| game.get_cell(0, col) == Cell::Alive).count();
+            game.next_generation();
+        }
+        alive as f64 / (rows * BOARD_WIDTH) as f64
+    };
+
+    for seed in [None, Some(99)] {
+        let half = alive_fraction(0.5, seed);
+        assert!((half - 0.5).abs() < 0.05, "Expected about half alive, got {} (seed {:?})", half, seed);
+        assert_eq!(alive_fraction(0.0, seed), 0.0);
+    }
+}
+
+#[test]
+fn test_plaintext_glider() {
+    let cells = "!Name: Glider\n!\n.O.\n..O\nOOO\n";
+    let glider = GameOfLife::from_plaintext(cells);
+    assert_eq!(count_alive_cells(&glider), 5);
+    for &(row, col) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+        assert_eq!(glider.get_cell(row, col), Cell::Alive);
+    }
+
+    // Same cells as the RLE version of the glider
+    assert!(boards_match(&glider, &GameOfLife::from_rle("bob$2bo$3o!").unwrap()));
+}
+
+#[test]
+fn test_plaintext_round_trip() {
+    let mut game = GameOfLife::new();
+    GameBoard::create_beehive(&mut game, 0, 0);
+    GameBoard::create_blinker(&mut game, 4, 6);
+
+    let text = game.to_plaintext();
+    assert!(text.starts_with("!"));
+    assert!(boards_match(&GameOfLife::from_plaintext(&text), &game));
+
+    // Asterisks count as live cells and columns past the board edge are clipped
+    let wide = GameOfLife::from_plaintext(&"*".repeat(BOARD_WIDTH + 10));
+    assert_eq!(count_alive_cells(&wide), BOARD_WIDTH);
+    assert_eq!(GameOfLife::new().to_plaintext().lines().count(), 1);
+}
+
+#[test]
+fn test_place_rotated_glider() {
+    let placed = |rotation| {
+        let mut game = GameOfLife::new();
+        GameBoard::place_rotated(&mut game, GLIDER_CELLS, 10, 20, rotation);
+        let mut cells: Vec<(usize, usize)> = (0..BOARD_HEIGHT)
+            .flat_map(|row| (0..BOARD_WIDTH).map(move |col| (row, col)))
+            .filter(|&(row, col)| game.get_cell(row, col) == Cell::Alive)
+            .collect();
+        cells.sort();
+        cells
+    };
+
+    // ..O      .O.      OO.      OOO
+    // O.O  ->  O..  ->  O.O  ->  ..O
+    // .OO      OOO      O..      .O.
+    assert_eq!(placed(Rotation::R0), vec![(10, 22), (11, 20), (11, 22), (12, 21), (12, 22)]);
+    assert_eq!(placed(Rotation::R90), vec![(10, 21), (11, 20), (12, 20), (12, 21), (12, 22)]);
+    assert_eq!(placed(Rotation::R180), vec![(10, 20), (10, 21), (11, 20), (11, 22), (12, 20)]);
+    assert_eq!(placed(Rotation::R270), vec![(10, 20), (10, 21), (10, 22), (11, 22), (12, 21)]);
+
+    // The unrotated placement is the same as create_glider
+    let mut expected = GameOfLife::new();
+    GameBoard::create_glider(&mut expected, 10, 20);
+    let mut game = GameOfLife::new();
+    GameBoard::place_rotated(&mut game, GLIDER_CELLS, 10, 20, Rotation::R0);
+    assert!(boards_match(&game, &expected));
+}
+
+#[test]
+fn test_reflected_patterns() {
+    let mut sorted = reflect_cells(GLIDER_CELLS, Reflection::Horizontal);
+    sorted.sort();
+    // O..
+    // O.O
+    // OO.
+    assert_eq!(sorted, vec![(0, 0), (1, 0), (1, 2), (2, 0), (2, 1)]);
+
+    let mut sorted = reflect_cells(GLIDER_CELLS, Reflection::Vertical);
+    sorted.sort();
+    // .OO
+    // O.O
+    // ..O
+    assert_eq!(sorted, vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 2)]);
+
+    // Offsets in the input don't matter, only the bounding box
+    let shifted: Vec<(usize, usize)> = GLIDER_CELLS.iter().map(|&(r, c)| (r + 5, c + 7)).collect();
+    assert_eq!(rotate_cells(&shifted, Rotation::R90), rotate_cells(GLIDER_CELLS, Rotation::R90));
+
+    let mut game = GameOfLife::new();
+    GameBoard::place_reflected(&mut game, GLIDER_CELLS, 0, 0, Reflection::Horizontal);
+    assert_eq!(game.get_cell(1, 2), Cell::Alive);
+    assert_eq!(count_alive_cells(&game), 5);
+}
+
+#[test]
+fn test_ascii_blocks_compose() {
+    let mut game = GameOfLife::new();
+    GameBoard::from_ascii(&mut game, &["OO", "OO"], 5, 10);
+    GameBoard::from_ascii(&mut game, &["..O", "O.O", ".OO"], 20, 40);
+
+    let mut expected = GameOfLife::new();
+    GameBoard::create_block(&mut expected, 5, 10);
+    GameBoard::create_glider(&mut expected, 20, 40);
+    assert!(boards_match(&game, &expected));
+    assert_eq!(game.population(), 9);
+
+    // Dead cells in a block don't erase what is already there
+    GameBoard::from_ascii(&mut game, &["...", "..."], 5, 10);
+    assert_eq!(game.population(), 9);
+
+    // Blocks hanging off the edges are clipped
+    let mut clipped = GameOfLife::new();
+    GameBoard::from_ascii(&mut clipped, &["OOO", "OOO"], BOARD_HEIGHT - 1, BOARD_WIDTH - 2);
+    assert_eq!(clipped.population(), 2);
+    assert_eq!(clipped.get_cell(BOARD_HEIGHT - 1, BOARD_WIDTH - 1), Cell::Alive);
+}
+
+#[test]
+fn test_pattern_file_detects_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let rle_path = dir.path().join("glider.rle");
+    let cells_path = dir.path().join("glider.cells");
+    let unknown_path = dir.path().join("glider.txt");
+    std::fs::write(&rle_path, "#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n").unwrap();
+    std::fs::write(&cells_path, "!Name: Glider\n.O.\n..O\nOOO\n").unwrap();
+    std::fs::write(&unknown_path, "glider: 3 cells wide\n").unwrap();
+
+    let from_rle = GameOfLife::from_pattern_file(&rle_path).unwrap();
+    let from_cells = GameOfLife::from_pattern_file(&cells_path).unwrap();
+    assert_eq!(count_alive_cells(&from_rle), 5);
+    assert!(boards_match(&from_rle, &from_cells));
+
+    assert_eq!(detect_format("bob$2bo$3o!"), Some(PatternFormat::Rle));
+    assert_eq!(detect_format(".O.\n..O\nOOO"), Some(PatternFormat::Plaintext));
+    assert!(matches!(GameOfLife::from_pattern_file(&unknown_path), Err(PatternError::UnknownFormat(_))));
+    assert!(matches!(GameOfLife::from_pattern_file(&dir.path().join("missing.rle")), Err(PatternError::Io(_))));
+}
+
+#[test]
+fn test_pattern_registry() {
+    for name in ["glider", "pulsar", "gosper_gun"] {
+        assert!(find_pattern(name).is_some(), "{} missing from the registry", name);
+    }
+    assert_eq!(find_pattern("Glider").unwrap().name, "glider");
+    assert!(find_pattern("no_such_pattern").is_none());
+
+    // Every pattern fits inside its declared footprint and so lands on the board
+    for pattern in PATTERNS {
+        let mut game = GameOfLife::new();
+        (pattern.place)(&mut game, 0, 0);
+        let live: Vec<(usize, usize)> = (0..BOARD_HEIGHT)
+            .flat_map(|row| (0..BOARD_WIDTH).map(move |col| (row, col)))
+            .filter(|&(row, col)| game.get_cell(row, col) == Cell::Alive)
+            .collect();
+        assert!(!live.is_empty(), "{} placed no cells", pattern.name);
+        assert!(live.iter().all(|&(row, col)| row < pattern.height && col < pattern.width),
+                "{} spills outside {}x{}", pattern.name, pattern.width, pattern.height);
+
+        let centered = GameBoard::create_named_board(pattern.name).unwrap();
+        assert_eq!(centered.population(), live.len(), "{}", pattern.name);
+    }
+}
+
+#[test]
+fn test_patterns_report_clipped_cells() {
+    // Only the gun's left block fits in the last 8 columns
+    let mut game = GameOfLife::new();
+    assert_eq!(GameBoard::create_gosper_glider_gun(&mut game, 10, 80), 32);
+    assert_eq!(count_alive_cells(&game), 4);
+
+
+    // Patterns that fit report nothing
+    let mut game = GameOfLife::new();
+    assert_eq!(GameBoard::create_glider(&mut game, 0, 0), 0);
+
+    assert!(game.try_set_cell(BOARD_HEIGHT - 1, BOARD_WIDTH - 1, Cell::Alive));
+    assert!(!game.try_set_cell(BOARD_HEIGHT, 0, Cell::Alive));
+    assert!(!game.try_set_cell(0, BOARD_WIDTH, Cell::Alive));
+}
+
+#[test]
+fn test_gun_fits_at_row_zero() {
+    // The gun's anchor is the top-left of its bounding box, so row 0 loses nothing
+    let mut game = GameOfLife::new();
+    assert_eq!(GameBoard::create_gosper_glider_gun(&mut game, 0, 10), 0);
+    assert_eq!(count_alive_cells(&game), 36);
+
+    // Its topmost cell sits in row 0 and its blocks at either end
+    assert_eq!(game.get_cell(0, 34), Cell::Alive);
+    for (row, col) in [(6, 10), (6, 11), (7, 10), (7, 11), (4, 44), (4, 45), (5, 44), (5, 45)] {
+        assert_eq!(game.get_cell(row, col), Cell::Alive, "({}, {})", row, col);
+    }
+    assert!((0..BOARD_WIDTH).all(|col| game.get_cell(9, col) == Cell::Dead));
+}
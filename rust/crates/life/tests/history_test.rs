@@ -0,0 +1,50 @@
+// Integration tests for stepping back through generation history
+
+use life::{GameOfLife, GameOfLifeHistory, HistoryError};
+
+fn blinker() -> GameOfLife {
+    GameOfLife::from_pattern(&[
+        "",
+        "",
+        ".OOO.",
+    ])
+}
+
+#[test]
+fn test_blinker_steps_back_to_earlier_boards() {
+    let mut history = GameOfLifeHistory::new(blinker(), 10);
+
+    // The Display output includes the generation number as well as every cell
+    let mut boards = vec![history.game().to_string()];
+    for _ in 0..3 {
+        history.next_generation();
+        boards.push(history.game().to_string());
+    }
+    assert_eq!(history.game().generation(), 3);
+
+    history.step_back().unwrap();
+    assert_eq!(history.game().generation(), 2);
+    assert_eq!(history.game().to_string(), boards[2]);
+
+    history.step_back().unwrap();
+    assert_eq!(history.game().generation(), 1);
+    assert_eq!(history.game().to_string(), boards[1]);
+
+    // Moving forward again replays the same generation
+    history.next_generation();
+    assert_eq!(history.game().to_string(), boards[2]);
+}
+
+#[test]
+fn test_stepping_back_past_the_buffer_fails() {
+    let mut history = GameOfLifeHistory::new(blinker(), 2);
+    for _ in 0..3 {
+        history.next_generation();
+    }
+    assert_eq!(history.len(), 2);
+
+    assert!(history.step_back().is_ok());
+    assert!(history.step_back().is_ok());
+    assert_eq!(history.step_back(), Err(HistoryError::Exhausted));
+    assert_eq!(history.game().generation(), 1, "A failed step back leaves the board alone");
+}
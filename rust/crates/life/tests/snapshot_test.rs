@@ -0,0 +1,68 @@
+// Integration tests for binary board snapshots
+
+use life::{Cell, GameBoard, GameOfLife, BOARD_HEIGHT, BOARD_WIDTH};
+use std::io::ErrorKind;
+
+fn cells(game: &GameOfLife) -> Vec<Cell> {
+    (0..BOARD_HEIGHT)
+        .flat_map(|row| (0..BOARD_WIDTH).map(move |col| (row, col)))
+        .map(|(row, col)| game.get_cell(row, col))
+        .collect()
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("board.snap");
+
+    let mut game = GameBoard::create_random_board_seeded(42, 0.3);
+    for _ in 0..7 {
+        game.next_generation();
+    }
+    game.save_state(&path).unwrap();
+
+    let restored = GameOfLife::load_state(&path).unwrap();
+    assert_eq!(restored.generation(), 7);
+    assert_eq!(cells(&restored), cells(&game));
+
+    // Compact: about one bit per cell
+    let size = std::fs::metadata(&path).unwrap().len() as usize;
+    assert!(size < 16 + BOARD_WIDTH * BOARD_HEIGHT / 8 + 8, "Snapshot is {} bytes", size);
+}
+
+#[test]
+fn test_snapshot_keeps_dying_cells() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dying.snap");
+
+    let mut game = GameOfLife::new();
+    game.set_cell(3, 4, Cell::Dying(2));
+    game.set_cell(3, 5, Cell::Alive);
+    game.save_state(&path).unwrap();
+
+    let restored = GameOfLife::load_state(&path).unwrap();
+    assert_eq!(restored.get_cell(3, 4), Cell::Dying(2));
+    assert_eq!(restored.get_cell(3, 5), Cell::Alive);
+}
+
+#[test]
+fn test_snapshot_rejects_bad_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("board.snap");
+    GameOfLife::new().save_state(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+
+    // A board of different dimensions
+    let mut resized = bytes.clone();
+    resized[5..7].copy_from_slice(&64u16.to_le_bytes());
+    std::fs::write(&path, &resized).unwrap();
+    assert_eq!(GameOfLife::load_state(&path).err().map(|e| e.kind()), Some(ErrorKind::InvalidData));
+
+    // Truncated cell data
+    std::fs::write(&path, &bytes[..20]).unwrap();
+    assert_eq!(GameOfLife::load_state(&path).err().map(|e| e.kind()), Some(ErrorKind::InvalidData));
+
+    // Not a snapshot at all
+    std::fs::write(&path, b"x = 3, y = 3\n").unwrap();
+    assert_eq!(GameOfLife::load_state(&path).err().map(|e| e.kind()), Some(ErrorKind::InvalidData));
+}
@@ -0,0 +1,40 @@
+// Tests for the browser bindings; built only with `--features wasm`
+
+use life::{GameOfLife, WasmGame, BOARD_HEIGHT, BOARD_WIDTH};
+
+#[test]
+fn test_blinker_plays_through_the_bindings() {
+    let mut game = WasmGame::new();
+    game.set_random_injection(false);
+    assert_eq!((game.width(), game.height()), (BOARD_WIDTH as u32, BOARD_HEIGHT as u32));
+
+    // A horizontal blinker on the bottom row
+    let row = game.height() - 1;
+    for col in 10..13 {
+        game.set_cell(row, col, true);
+    }
+    assert!(game.get_cell(row, 11));
+    assert_eq!(game.population(), 3);
+
+    // It plays its three keys, then drops off the board
+    assert_eq!(game.get_bottom_row_and_advance(), vec![10, 11, 12]);
+    assert!(game.get_bottom_row_and_advance().is_empty());
+    assert_eq!(game.generation(), 2);
+
+    game.next_generation();
+    assert_eq!(game.generation(), 3);
+    assert!(game.render().starts_with("Generation: 3"));
+}
+
+#[test]
+fn test_seeded_and_named_boards() {
+    let keys = |mut game: WasmGame| (0..5).map(|_| game.get_bottom_row_and_advance()).collect::<Vec<_>>();
+    assert_eq!(keys(WasmGame::random(7, 0.3)), keys(WasmGame::random(7, 0.3)));
+
+    let glider = WasmGame::from_pattern_name("glider").unwrap();
+    assert_eq!(glider.population(), 5);
+    assert!(WasmGame::from_pattern_name("no_such_pattern").is_none());
+
+    assert_eq!(WasmGame::with_height(60).height(), 60);
+    assert_eq!(WasmGame::default().render(), GameOfLife::new().to_string());
+}
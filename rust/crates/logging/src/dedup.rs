@@ -0,0 +1,154 @@
+// Duplicate log line suppression for Conway's Steinway
+// When a board settles into a repeating state the main loop logs the same
+// message every generation. DedupAppender wraps another appender and collapses
+// consecutive identical messages into one line plus a repeat count, the way
+// syslog reports "last message repeated N times".
+
+use log::{Level, Record};
+use log4rs::append::Append;
+use std::fmt;
+use std::sync::Mutex;
+
+/// What to write for one incoming message
+#[derive(Debug, PartialEq)]
+pub struct DedupDecision {
+    /// Summary of the run of repeats that this message ended, if any
+    pub summary: Option<String>,
+    /// Whether the message itself should be written
+    pub emit: bool,
+}
+
+/// Tracks the last message seen and how many times it has repeated since
+#[derive(Debug, Default)]
+pub struct DedupState {
+    last: Option<String>,
+    repeats: usize,
+}
+
+impl DedupState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&mut self, message: &str) -> DedupDecision {
+        if self.last.as_deref() == Some(message) {
+            self.repeats += 1;
+            return DedupDecision { summary: None, emit: false };
+        }
+
+        let summary = self.take_summary();
+        self.last = Some(message.to_string());
+        DedupDecision { summary, emit: true }
+    }
+
+    /// Summary line for any repeats not yet reported, resetting the count
+    pub fn take_summary(&mut self) -> Option<String> {
+        let repeats = std::mem::take(&mut self.repeats);
+        match repeats {
+            0 => None,
+            1 => Some("last message repeated 1 time".to_string()),
+            n => Some(format!("last message repeated {} times", n)),
+        }
+    }
+}
+
+/// Appender wrapper that drops consecutive duplicate messages
+pub struct DedupAppender {
+    inner: Box<dyn Append>,
+    state: Mutex<(DedupState, Level)>,
+}
+
+impl DedupAppender {
+    pub fn new(inner: Box<dyn Append>) -> Self {
+        DedupAppender {
+            inner,
+            state: Mutex::new((DedupState::new(), Level::Info)),
+        }
+    }
+
+    // Write a repeat summary through the inner appender at the repeated message's level
+    fn append_summary(&self, summary: &str, level: Level, target: &str) -> anyhow::Result<()> {
+        self.inner.append(
+            &Record::builder()
+                .args(format_args!("{}", summary))
+                .level(level)
+                .target(target)
+                .build(),
+        )
+    }
+}
+
+impl fmt::Debug for DedupAppender {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DedupAppender").field("inner", &self.inner).finish()
+    }
+}
+
+impl Append for DedupAppender {
+    fn append(&self, record: &Record) -> anyhow::Result<()> {
+        let message = record.args().to_string();
+        let (decision, previous_level) = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let decision = state.0.observe(&message);
+            let previous_level = state.1;
+            if decision.emit {
+                state.1 = record.level();
+            }
+            (decision, previous_level)
+        };
+
+        if let Some(summary) = decision.summary {
+            self.append_summary(&summary, previous_level, record.target())?;
+        }
+        if decision.emit {
+            self.inner.append(record)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&self) {
+        let pending = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.0.take_summary().map(|summary| (summary, state.1))
+        };
+        if let Some((summary, level)) = pending {
+            let _ = self.append_summary(&summary, level, module_path!());
+        }
+        self.inner.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_message_collapses() {
+        let mut state = DedupState::new();
+        let mut emitted = Vec::new();
+
+        for message in ["Generation stable", "Generation stable", "Generation stable", "Generation stable", "Board cleared"] {
+            let decision = state.observe(message);
+            emitted.extend(decision.summary);
+            if decision.emit {
+                emitted.push(message.to_string());
+            }
+        }
+
+        assert_eq!(emitted, vec![
+            "Generation stable".to_string(),
+            "last message repeated 3 times".to_string(),
+            "Board cleared".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_pending_repeats_reported_on_flush() {
+        let mut state = DedupState::new();
+        assert!(state.observe("tick").emit);
+        assert!(!state.observe("tick").emit);
+
+        assert_eq!(state.take_summary(), Some("last message repeated 1 time".to_string()));
+        assert_eq!(state.take_summary(), None);
+    }
+}
@@ -0,0 +1,164 @@
+// Appender filters for Conway's Steinway
+// ModuleThresholdFilter is the per-destination level check. It works like
+// log4rs's ThresholdFilter, except that records from a module listed in
+// [logging.module] are let through down to that module's own level. Without
+// this, `audio = debug` raises the audio logger but every destination still
+// drops the debug records at its own (usually info) threshold.
+//
+// TargetFilter keeps a client library's own log records away from the
+// appender built on it. The Kafka and PostgreSQL clients log through `log`
+// while sending, and feeding those records back into the same appender would
+// make every send queue another one.
+//
+// ConsoleMuteFilter silences the console destinations while something else
+// owns the terminal, such as the TUI, without touching the other destinations.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::{LevelFilter, Record};
+use log4rs::filter::{Filter, Response};
+
+static CONSOLE_MUTED: AtomicBool = AtomicBool::new(false);
+
+/// Stop (or resume) writing log records to the console destinations
+pub fn mute_console(muted: bool) {
+    CONSOLE_MUTED.store(muted, Ordering::Relaxed);
+}
+
+/// Rejects records above a destination's level, with per-module exceptions
+#[derive(Debug)]
+pub struct ModuleThresholdFilter {
+    level: LevelFilter,
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl ModuleThresholdFilter {
+    pub fn new(level: LevelFilter, modules: Vec<(String, LevelFilter)>) -> Self {
+        Self { level, modules }
+    }
+
+    /// The most verbose level a record from `target` may have and still pass
+    pub fn threshold_for(&self, target: &str) -> LevelFilter {
+        // The most specific module wins, as with log4rs loggers
+        let module = self.modules.iter()
+            .filter(|(module, _)| is_within(target, module))
+            .max_by_key(|(module, _)| module.len());
+
+        match module {
+            // A module level never hides records the destination would show
+            Some((_, module_level)) => self.level.max(*module_level),
+            None => self.level,
+        }
+    }
+}
+
+impl Filter for ModuleThresholdFilter {
+    fn filter(&self, record: &Record) -> Response {
+        if record.level() > self.threshold_for(record.target()) {
+            Response::Reject
+        } else {
+            Response::Neutral
+        }
+    }
+}
+
+/// Rejects records whose target starts with any of the given prefixes
+#[derive(Debug)]
+pub struct TargetFilter {
+    prefixes: Vec<String>,
+}
+
+impl TargetFilter {
+    pub fn excluding(prefixes: &[&str]) -> Self {
+        Self { prefixes: prefixes.iter().map(|prefix| prefix.to_string()).collect() }
+    }
+}
+
+impl Filter for TargetFilter {
+    fn filter(&self, record: &Record) -> Response {
+        if self.prefixes.iter().any(|prefix| record.target().starts_with(prefix.as_str())) {
+            Response::Reject
+        } else {
+            Response::Neutral
+        }
+    }
+}
+
+/// Rejects every record while the console is muted (see mute_console)
+#[derive(Debug)]
+pub struct ConsoleMuteFilter;
+
+impl Filter for ConsoleMuteFilter {
+    fn filter(&self, _record: &Record) -> Response {
+        if CONSOLE_MUTED.load(Ordering::Relaxed) {
+            Response::Reject
+        } else {
+            Response::Neutral
+        }
+    }
+}
+
+// Whether `target` is `module` itself or one of its submodules
+fn is_within(target: &str, module: &str) -> bool {
+    target == module
+        || target.strip_prefix(module).is_some_and(|rest| rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    fn record_from(target: &str, level: Level) -> Response {
+        let filter = ModuleThresholdFilter::new(
+            LevelFilter::Info,
+            vec![("audio".to_string(), LevelFilter::Debug), ("audio::midi".to_string(), LevelFilter::Trace)],
+        );
+        filter.filter(&Record::builder().target(target).level(level).args(format_args!("x")).build())
+    }
+
+    #[test]
+    fn test_module_level_lets_records_through() {
+        assert_eq!(record_from("audio", Level::Debug), Response::Neutral);
+        assert_eq!(record_from("audio::player", Level::Debug), Response::Neutral);
+        assert_eq!(record_from("audio::player", Level::Trace), Response::Reject);
+        assert_eq!(record_from("audio::midi", Level::Trace), Response::Neutral);
+
+        // Other targets keep the destination's level, including lookalike names
+        assert_eq!(record_from("life", Level::Debug), Response::Reject);
+        assert_eq!(record_from("audiofile", Level::Debug), Response::Reject);
+        assert_eq!(record_from("life", Level::Info), Response::Neutral);
+    }
+
+    #[test]
+    fn test_muted_console_rejects_records() {
+        let record = || ConsoleMuteFilter.filter(&Record::builder().level(Level::Error).args(format_args!("x")).build());
+        assert_eq!(record(), Response::Neutral);
+        mute_console(true);
+        assert_eq!(record(), Response::Reject);
+        mute_console(false);
+        assert_eq!(record(), Response::Neutral);
+    }
+
+    #[test]
+    fn test_postgres_client_targets_are_dropped() {
+        let filter = TargetFilter::excluding(&["postgres", "tokio_postgres"]);
+        let record = |target| filter.filter(&Record::builder().target(target).level(Level::Debug).args(format_args!("x")).build());
+
+        assert_eq!(record("tokio_postgres::prepare"), Response::Reject);
+        assert_eq!(record("postgres::client"), Response::Reject);
+        assert_eq!(record("postgres_protocol"), Response::Reject);
+        assert_eq!(record("logging::postgres_appender"), Response::Neutral);
+    }
+
+    #[test]
+    fn test_target_filter_drops_client_records() {
+        let filter = TargetFilter::excluding(&["kafka"]);
+        let record = |target| filter.filter(&Record::builder().target(target).level(Level::Error).args(format_args!("x")).build());
+
+        assert_eq!(record("kafka"), Response::Reject);
+        assert_eq!(record("kafka::producer"), Response::Reject);
+        assert_eq!(record("logging::kafka_appender"), Response::Neutral);
+        assert_eq!(record("conways_steinway"), Response::Neutral);
+    }
+}
@@ -0,0 +1,204 @@
+// Kafka log appender for Conway's Steinway
+// Produces each encoded log line as one message on the configured topic. A
+// Kafka round trip is too slow to make on the logging call, so lines are
+// queued to a background thread that owns the producer and sends whatever has
+// piled up in one batch. flush() waits for the queue to drain, and dropping
+// the appender (when the logger shuts down) sends anything still queued.
+
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context};
+use kafka::client::Compression;
+use kafka::producer::{Producer, Record, RequiredAcks};
+use log4rs::append::Append;
+use log4rs::encode::writer::simple::SimpleWriter;
+use log4rs::encode::Encode;
+
+use config::types::KafkaConfig;
+
+pub const DEFAULT_CLIENT_ID: &str = "conways-steinway";
+// Log targets of the Kafka client, which logs while the appender sends
+pub const CLIENT_LOG_TARGETS: &[&str] = &["kafka"];
+// How long the broker may take to acknowledge a batch
+const ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Map the configured compression name onto the producer's codec. lz4 is
+// accepted by other Kafka clients but this producer can't write it.
+pub fn compression_codec(compression: Option<&str>) -> anyhow::Result<Compression> {
+    match compression.map(|c| c.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("none") => Ok(Compression::NONE),
+        Some("gzip") => Ok(Compression::GZIP),
+        Some("snappy") => Ok(Compression::SNAPPY),
+        Some("lz4") => bail!("lz4 compression is not supported by the Kafka appender (use none, gzip or snappy)"),
+        Some(other) => bail!("Unknown Kafka compression '{}' (expected none, gzip or snappy)", other),
+    }
+}
+
+// Brokers must be host:port pairs
+fn check_brokers(brokers: &[String]) -> anyhow::Result<()> {
+    if brokers.is_empty() {
+        bail!("No Kafka brokers configured");
+    }
+    for broker in brokers {
+        let port = broker.rsplit_once(':').map(|(host, port)| (host, port.parse::<u16>()));
+        if !matches!(port, Some((host, Ok(_))) if !host.is_empty()) {
+            bail!("Invalid Kafka broker '{}': expected host:port", broker);
+        }
+    }
+    Ok(())
+}
+
+enum Message {
+    Line(Vec<u8>),
+    Flush(Sender<()>), // Answered once every earlier line has been sent
+}
+
+pub struct KafkaAppender {
+    queue: Mutex<Option<Sender<Message>>>,
+    worker: Option<JoinHandle<()>>,
+    topic: String,
+    brokers: Vec<String>,
+    encoder: Box<dyn Encode>,
+}
+
+impl KafkaAppender {
+    // Connect to the brokers and start the sending thread. Fails if the
+    // brokers or compression are invalid or no broker can be reached.
+    pub fn build(config: &KafkaConfig, encoder: Box<dyn Encode>) -> anyhow::Result<Self> {
+        check_brokers(&config.brokers)?;
+        let compression = compression_codec(config.compression.as_deref())?;
+        let client_id = config.client_id.clone().unwrap_or_else(|| DEFAULT_CLIENT_ID.to_string());
+
+        let producer = Producer::from_hosts(config.brokers.clone())
+            .with_client_id(client_id)
+            .with_compression(compression)
+            .with_ack_timeout(ACK_TIMEOUT)
+            .with_required_acks(RequiredAcks::One)
+            .create()
+            .with_context(|| format!("Unable to connect to Kafka brokers {}", config.brokers.join(",")))?;
+
+        let (queue, lines) = mpsc::channel();
+        let topic = config.topic.clone();
+        let worker = thread::Builder::new()
+            .name("kafka-appender".to_string())
+            .spawn(move || send_lines(producer, &topic, lines))?;
+
+        Ok(KafkaAppender {
+            queue: Mutex::new(Some(queue)),
+            worker: Some(worker),
+            topic: config.topic.clone(),
+            brokers: config.brokers.clone(),
+            encoder,
+        })
+    }
+
+    fn send(&self, message: Message) -> anyhow::Result<()> {
+        let queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        queue.as_ref()
+            .ok_or_else(|| anyhow!("Kafka appender is shut down"))?
+            .send(message)
+            .map_err(|_| anyhow!("Kafka appender thread has stopped"))
+    }
+}
+
+// Body of the sending thread: batch up whatever lines are waiting and send
+// them, until the appender is dropped
+fn send_lines(mut producer: Producer, topic: &str, lines: Receiver<Message>) {
+    while let Ok(first) = lines.recv() {
+        let mut batch = Vec::new();
+        let mut flushes = Vec::new();
+        for message in std::iter::once(first).chain(lines.try_iter()) {
+            match message {
+                Message::Line(line) => batch.push(line),
+                Message::Flush(done) => flushes.push(done),
+            }
+        }
+
+        if !batch.is_empty() {
+            let records: Vec<_> = batch.iter().map(|line| Record::from_value(topic, line.as_slice())).collect();
+            if let Err(e) = producer.send_all(&records) {
+                // Logging the failure through log would only queue more lines
+                eprintln!("log4rs: unable to send {} log lines to Kafka topic {}: {}", records.len(), topic, e);
+            }
+        }
+        for done in flushes {
+            let _ = done.send(());
+        }
+    }
+}
+
+impl fmt::Debug for KafkaAppender {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KafkaAppender")
+            .field("brokers", &self.brokers)
+            .field("topic", &self.topic)
+            .field("encoder", &self.encoder)
+            .finish()
+    }
+}
+
+impl Append for KafkaAppender {
+    fn append(&self, record: &log::Record) -> anyhow::Result<()> {
+        let mut line = SimpleWriter(Vec::new());
+        self.encoder.encode(&mut line, record)?;
+
+        // Each message is one line, so the encoder's trailing newline isn't needed
+        let mut line = line.0;
+        while matches!(line.last(), Some(b'\n') | Some(b'\r')) {
+            line.pop();
+        }
+        self.send(Message::Line(line))
+    }
+
+    fn flush(&self) {
+        let (done, sent) = mpsc::channel();
+        if self.send(Message::Flush(done)).is_ok() {
+            let _ = sent.recv();
+        }
+    }
+}
+
+impl Drop for KafkaAppender {
+    fn drop(&mut self) {
+        // Closing the queue lets the thread send what's left and exit
+        self.queue.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_codecs() {
+        assert_eq!(compression_codec(None).unwrap(), Compression::NONE);
+        assert_eq!(compression_codec(Some("none")).unwrap(), Compression::NONE);
+        assert_eq!(compression_codec(Some("GZIP")).unwrap(), Compression::GZIP);
+        assert_eq!(compression_codec(Some("snappy")).unwrap(), Compression::SNAPPY);
+        assert!(compression_codec(Some("lz4")).is_err());
+        assert!(compression_codec(Some("zstd")).is_err());
+    }
+
+    #[test]
+    fn test_invalid_brokers_fail_to_build() {
+        let config = |brokers: &[&str]| KafkaConfig {
+            brokers: brokers.iter().map(|b| b.to_string()).collect(),
+            topic: "logs".to_string(),
+            client_id: None,
+            compression: None,
+        };
+        let encoder = || Box::new(log4rs::encode::pattern::PatternEncoder::new("{m}"));
+
+        for brokers in [&[][..], &["localhost"], &["localhost:kafka"], &[":9092"]] {
+            assert!(KafkaAppender::build(&config(brokers), encoder()).is_err(), "{:?}", brokers);
+        }
+        assert!(check_brokers(&["kafka-1:9092".to_string(), "10.0.0.2:9093".to_string()]).is_ok());
+    }
+}
@@ -1,9 +1,23 @@
 // Logging module for Conway's Steinway
 // Provides multi-destination logging functionality using log4rs
 
+pub mod dedup;
+pub mod filters;
+#[cfg(feature = "kafka")]
+pub mod kafka_appender;
+pub mod pattern;
+#[cfg(feature = "postgres")]
+pub mod postgres_appender;
+#[cfg(feature = "redis")]
+pub mod redis_appender;
+
 use log::{LevelFilter, info};
+#[cfg(any(feature = "http", feature = "syslog", feature = "socket", feature = "fluentd", feature = "gelf",
+          feature = "mongodb", feature = "postgres", feature = "kafka", feature = "rabbitmq", feature = "redis"))]
+use log::warn;
 use log4rs::{
     append::{
+        Append,
         console::ConsoleAppender,
         file::FileAppender,
         rolling_file::{
@@ -13,18 +27,27 @@ use log4rs::{
             policy::compound::trigger::size::SizeTrigger,
         },
     },
-    encode::{pattern::PatternEncoder, json::JsonEncoder},
-    config::{Appender, Config, Root},
-    filter::threshold::ThresholdFilter,
+    encode::{Encode, pattern::PatternEncoder, json::JsonEncoder},
+    config::{Appender, Config, Logger, Root},
+    filter::Filter,
 };
 use std::path::PathBuf;
 use std::fs;
+use std::io::IsTerminal;
 
 use config::{Config as AppConfig};
 use config::types::{
-    LogDestinationType, DEFAULT_LOG_FILE, DEFAULT_LOG_SUBDIR
+    LogColor, LogDestination, LogDestinationType, LogEncoder, LogRotationConfig, DEFAULT_LOG_CONSOLE_LEVEL,
+    DEFAULT_LOG_FILE, DEFAULT_LOG_FILE_LEVEL, DEFAULT_LOG_LEVEL, DEFAULT_LOG_SUBDIR
 };
 use std::env;
+use std::ffi::OsString;
+
+use dedup::DedupAppender;
+use filters::{ConsoleMuteFilter, ModuleThresholdFilter};
+#[cfg(any(feature = "kafka", feature = "postgres"))]
+use filters::TargetFilter;
+use pattern::validate_pattern;
 
 // Import network appenders
 #[cfg(feature = "http")]
@@ -47,90 +70,23 @@ use log4rs_gelf::GelfAppender;
 use log4rs_mongodb::MongoDbAppender;
 
 #[cfg(feature = "postgres")]
-use log4rs_postgres::PostgresAppender;
+use postgres_appender::{PostgresAppender, CLIENT_LOG_TARGETS as POSTGRES_LOG_TARGETS};
 
 // Import message queue appenders
 #[cfg(feature = "kafka")]
-use log4rs_kafka::KafkaAppender;
+use kafka_appender::{KafkaAppender, CLIENT_LOG_TARGETS as KAFKA_LOG_TARGETS};
 
 #[cfg(feature = "rabbitmq")]
 use log4rs_rabbitmq::RabbitMQAppender;
 
 #[cfg(feature = "redis")]
-use log4rs_redis::RedisAppender;
+use redis_appender::RedisAppender;
 
 // Default log patterns
 pub const CONSOLE_PATTERN: &str = "[{h({l})}] {m}{n}";
+pub const PLAIN_CONSOLE_PATTERN: &str = "[{l}] {m}{n}";
 pub const FILE_PATTERN: &str = "[{d(%Y-%m-%d %H:%M:%S)} {l}] {t} - {m}{n}";
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::tempdir;
-
-    #[test]
-    fn test_parse_level() {
-        // Test valid log levels
-        assert_eq!(parse_level("trace"), LevelFilter::Trace);
-        assert_eq!(parse_level("debug"), LevelFilter::Debug);
-        assert_eq!(parse_level("info"), LevelFilter::Info);
-        assert_eq!(parse_level("warn"), LevelFilter::Warn);
-        assert_eq!(parse_level("error"), LevelFilter::Error);
-        
-        // Test case insensitivity
-        assert_eq!(parse_level("INFO"), LevelFilter::Info);
-        assert_eq!(parse_level("Debug"), LevelFilter::Debug);
-        assert_eq!(parse_level("ERROR"), LevelFilter::Error);
-        
-        // Test default for invalid input
-        assert_eq!(parse_level("invalid"), LevelFilter::Info);
-        assert_eq!(parse_level(""), LevelFilter::Info);
-    }
-    
-    #[test]
-    fn test_get_default_log_file_path() {
-        let config = AppConfig::default();
-        let path = get_default_log_file_path(&config);
-        
-        // Check that the path has the expected structure
-        assert!(path.ends_with(DEFAULT_LOG_FILE), 
-                "Path should end with the default log file name");
-        
-        let parent = path.parent().unwrap();
-        assert!(parent.ends_with(DEFAULT_LOG_SUBDIR), 
-                "Parent directory should end with the default log subdirectory");
-    }
-    
-    #[test]
-    fn test_init_logging_with_temp_directory() {
-        // Create a temporary directory for log files
-        let temp_dir = tempdir().expect("Failed to create temp directory");
-        let log_path = temp_dir.path().join("test.log");
-        
-        // Create a minimal config that writes to our temp file
-        let mut config = AppConfig::default();
-        config.log_to_file = true;
-        config.log_file_path = Some(log_path.clone());
-        config.log_level = "debug".to_string();
-        config.log_console_level = "info".to_string();
-        config.log_file_level = "debug".to_string();
-        
-        // Initialize logging with this config
-        let result = init_logging(&config);
-        
-        // Verify initialization succeeded
-        assert!(result.is_ok(), "Logging initialization should succeed");
-        
-        // Verify the environment variable is set correctly
-        assert_eq!(std::env::var("RUST_LOG").unwrap_or_default(), "debug", 
-                  "RUST_LOG environment variable should be set to the config log level");
-        
-        // Verify the log file is created (or at least the directory exists)
-        let parent = log_path.parent().unwrap();
-        assert!(parent.exists(), "Log directory should be created");
-    }
-}
-
 // Convert string log level to LevelFilter
 fn parse_level(level: &str) -> LevelFilter {
     match level.to_lowercase().as_str() {
@@ -143,13 +99,139 @@ fn parse_level(level: &str) -> LevelFilter {
     }
 }
 
+// Build the encoder for a destination. An explicit `encoder` setting wins over
+// the encoding the destination type would otherwise use.
+fn build_encoder(dest: &LogDestination, default_encoder: LogEncoder, default_pattern: &str) -> Box<dyn Encode> {
+    match dest.encoder.unwrap_or(default_encoder) {
+        LogEncoder::Json => Box::new(JsonEncoder::new()),
+        LogEncoder::Pattern => Box::new(PatternEncoder::new(dest.pattern.as_deref().unwrap_or(default_pattern))),
+    }
+}
+
+// Choose the highlighted or plain console pattern. Under "auto" color is used
+// only on a terminal and when NO_COLOR isn't set.
+fn console_pattern(color: LogColor, no_color: bool, is_terminal: bool) -> &'static str {
+    let colorize = match color {
+        LogColor::Always => true,
+        LogColor::Never => false,
+        LogColor::Auto => is_terminal && !no_color,
+    };
+    if colorize { CONSOLE_PATTERN } else { PLAIN_CONSOLE_PATTERN }
+}
+
+// Console pattern for the current environment
+fn default_console_pattern(config: &AppConfig) -> &'static str {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    console_pattern(config.log_color, no_color, std::io::stdout().is_terminal())
+}
+
+// Merge the legacy level settings into the destination list. Every returned
+// destination has its level filled in.
+//
+// Precedence, highest first:
+//   1. A level set on the destination itself
+//   2. The legacy log_console_level / log_file_level for Console / File
+//      destinations, when one was given
+//   3. DEFAULT_LOG_LEVEL
+//
+// If log_to_file is set and no File destination is configured, one is added from
+// the legacy file settings so --log-to-file works with the default destinations.
+fn effective_destinations(config: &AppConfig) -> Vec<LogDestination> {
+    let mut destinations = config.log_destinations.clone();
+
+    for dest in &mut destinations {
+        let legacy = match dest.destination_type {
+            LogDestinationType::Console => config.log_console_level.as_ref(),
+            LogDestinationType::File => config.log_file_level.as_ref(),
+            _ => None,
+        };
+        let level = dest.level.as_ref().or(legacy).cloned();
+        dest.level = Some(level.unwrap_or_else(|| DEFAULT_LOG_LEVEL.to_string()));
+    }
+
+    let has_file = destinations.iter().any(|d| d.destination_type == LogDestinationType::File);
+    if config.log_to_file && !has_file {
+        destinations.push(LogDestination {
+            name: "file".to_string(),
+            destination_type: LogDestinationType::File,
+            level: Some(legacy_file_level(config).to_string()),
+            pattern: None,
+            encoder: None,
+            file_path: Some(get_log_file_path(config)),
+            rotation: Some(LogRotationConfig {
+                enabled: config.log_file_rotation,
+                size_limit: config.log_file_size_limit,
+                file_count: config.log_file_count,
+            }),
+            http: None,
+            syslog: None,
+            socket: None,
+            fluentd: None,
+            gelf: None,
+            mongodb: None,
+            postgres: None,
+            kafka: None,
+            rabbitmq: None,
+            redis: None,
+        });
+    }
+
+    destinations
+}
+
+// Levels of the legacy console and log file, for the legacy appenders
+fn legacy_console_level(config: &AppConfig) -> &str {
+    config.log_console_level.as_deref().unwrap_or(DEFAULT_LOG_CONSOLE_LEVEL)
+}
+
+fn legacy_file_level(config: &AppConfig) -> &str {
+    config.log_file_level.as_deref().unwrap_or(DEFAULT_LOG_FILE_LEVEL)
+}
+
+// Level filter for an appender: its own level, widened for the modules in
+// [logging.module] so their loggers' records aren't dropped again here
+fn threshold_filter(config: &AppConfig, level: LevelFilter) -> Box<dyn Filter> {
+    let modules = config.log_module_levels.iter()
+        .map(|(module, module_level)| (module.clone(), parse_level(module_level)))
+        .collect();
+    Box::new(ModuleThresholdFilter::new(level, modules))
+}
+
+// Wrap an appender in the duplicate-line filter when log_dedupe is enabled
+fn maybe_dedupe(config: &AppConfig, appender: Box<dyn Append>) -> Box<dyn Append> {
+    if config.log_dedupe {
+        Box::new(DedupAppender::new(appender))
+    } else {
+        appender
+    }
+}
+
 // Initialize logging system based on configuration
 pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let log_config = build_log_config(config)?;
+
     // Set default log level from configuration
     if std::env::var("RUST_LOG").is_err() {
         std::env::set_var("RUST_LOG", &config.log_level);
     }
     
+    // Initialize the logging system
+    log4rs::init_config(log_config)?;
+    
+    Ok(())
+}
+
+// Build the log4rs configuration: one appender per destination on the root
+// logger, plus a logger for each module with its own level
+pub fn build_log_config(config: &AppConfig) -> Result<Config, Box<dyn std::error::Error>> {
+    // Reject malformed patterns before anything is set up
+    for dest in &config.log_destinations {
+        if let Some(pattern) = &dest.pattern {
+            validate_pattern(pattern)
+                .map_err(|e| format!("Invalid log pattern for destination '{}': {}", dest.name, e))?;
+        }
+    }
+    
     // Start building configuration
     let mut builder = Config::builder();
     let mut root_builder = Root::builder();
@@ -157,15 +239,16 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
     
     // Process each configured destination
     if !config.log_destinations.is_empty() {
-        // Process all destinations from the new configuration format
-        for dest in &config.log_destinations {
+        // Process all destinations from the new configuration format,
+        // with the legacy level settings merged in
+        for dest in &effective_destinations(config) {
             let appender_name = &dest.name;
-            let level = parse_level(&dest.level);
+            let level = parse_level(dest.level.as_deref().unwrap_or(DEFAULT_LOG_LEVEL));
             
             match dest.destination_type {
                 LogDestinationType::Console => {
                     // Create console appender
-                    let pattern = dest.pattern.as_deref().unwrap_or(CONSOLE_PATTERN);
+                    let pattern = dest.pattern.as_deref().unwrap_or_else(|| default_console_pattern(config));
                     let console = ConsoleAppender::builder()
                         .encoder(Box::new(PatternEncoder::new(pattern)))
                         .build();
@@ -173,8 +256,9 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                     // Add to configuration
                     builder = builder.appender(
                         Appender::builder()
-                            .filter(Box::new(ThresholdFilter::new(level)))
-                            .build(appender_name, Box::new(console))
+                            .filter(threshold_filter(config, level))
+                            .filter(Box::new(ConsoleMuteFilter))
+                            .build(appender_name, maybe_dedupe(config, Box::new(console)))
                     );
                     
                     appender_names.push(appender_name.clone());
@@ -191,9 +275,6 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                         fs::create_dir_all(parent)?;
                     }
                     
-                    // Use the specified pattern or default
-                    let pattern = dest.pattern.as_deref().unwrap_or(FILE_PATTERN);
-                    
                     // Check if rotation is enabled
                     if let Some(rotation) = &dest.rotation {
                         if rotation.enabled {
@@ -228,28 +309,28 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                             
                             // Build the rotating file appender
                             let rolling_file = RollingFileAppender::builder()
-                                .encoder(Box::new(PatternEncoder::new(&pattern)))
+                                .encoder(build_encoder(dest, LogEncoder::Pattern, FILE_PATTERN))
                                 .build(log_file_path.clone(), Box::new(policy))?;
                             
                             // Add to configuration
                             builder = builder.appender(
                                 Appender::builder()
-                                    .filter(Box::new(ThresholdFilter::new(level)))
-                                    .build(appender_name, Box::new(rolling_file))
+                                    .filter(threshold_filter(config, level))
+                                    .build(appender_name, maybe_dedupe(config, Box::new(rolling_file)))
                             );
                             
                             appender_names.push(appender_name.clone());
                         } else {
                             // Simple file appender without rotation
                             let file = FileAppender::builder()
-                                .encoder(Box::new(PatternEncoder::new(pattern)))
+                                .encoder(build_encoder(dest, LogEncoder::Pattern, FILE_PATTERN))
                                 .build(log_file_path.clone())?;
                             
                             // Add to configuration
                             builder = builder.appender(
                                 Appender::builder()
-                                    .filter(Box::new(ThresholdFilter::new(level)))
-                                    .build(appender_name, Box::new(file))
+                                    .filter(threshold_filter(config, level))
+                                    .build(appender_name, maybe_dedupe(config, Box::new(file)))
                             );
                             
                             appender_names.push(appender_name.clone());
@@ -257,14 +338,14 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                     } else {
                         // Simple file appender without rotation
                         let file = FileAppender::builder()
-                            .encoder(Box::new(PatternEncoder::new(pattern)))
+                            .encoder(build_encoder(dest, LogEncoder::Pattern, FILE_PATTERN))
                             .build(log_file_path.clone())?;
                         
                         // Add to configuration
                         builder = builder.appender(
                             Appender::builder()
-                                .filter(Box::new(ThresholdFilter::new(level)))
-                                .build(appender_name, Box::new(file))
+                                .filter(threshold_filter(config, level))
+                                .build(appender_name, maybe_dedupe(config, Box::new(file)))
                         );
                         
                         appender_names.push(appender_name.clone());
@@ -324,30 +405,30 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                                 Box::new(roller),
                             );
                             
-                            // Build the rotating file appender with JSON encoder
+                            // Build the rotating file appender (JSON unless overridden)
                             let rolling_file = RollingFileAppender::builder()
-                                .encoder(Box::new(JsonEncoder::new()))
+                                .encoder(build_encoder(dest, LogEncoder::Json, FILE_PATTERN))
                                 .build(log_file_path.clone(), Box::new(policy))?;
                             
                             // Add to configuration
                             builder = builder.appender(
                                 Appender::builder()
-                                    .filter(Box::new(ThresholdFilter::new(level)))
-                                    .build(appender_name, Box::new(rolling_file))
+                                    .filter(threshold_filter(config, level))
+                                    .build(appender_name, maybe_dedupe(config, Box::new(rolling_file)))
                             );
                             
                             appender_names.push(appender_name.clone());
                         } else {
                             // Simple file appender without rotation
                             let file = FileAppender::builder()
-                                .encoder(Box::new(JsonEncoder::new()))
+                                .encoder(build_encoder(dest, LogEncoder::Json, FILE_PATTERN))
                                 .build(log_file_path.clone())?;
                             
                             // Add to configuration
                             builder = builder.appender(
                                 Appender::builder()
-                                    .filter(Box::new(ThresholdFilter::new(level)))
-                                    .build(appender_name, Box::new(file))
+                                    .filter(threshold_filter(config, level))
+                                    .build(appender_name, maybe_dedupe(config, Box::new(file)))
                             );
                             
                             appender_names.push(appender_name.clone());
@@ -355,14 +436,14 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                     } else {
                         // Simple file appender without rotation
                         let file = FileAppender::builder()
-                            .encoder(Box::new(JsonEncoder::new()))
+                            .encoder(build_encoder(dest, LogEncoder::Json, FILE_PATTERN))
                             .build(log_file_path.clone())?;
                         
                         // Add to configuration
                         builder = builder.appender(
                             Appender::builder()
-                                .filter(Box::new(ThresholdFilter::new(level)))
-                                .build(appender_name, Box::new(file))
+                                .filter(threshold_filter(config, level))
+                                .build(appender_name, maybe_dedupe(config, Box::new(file)))
                         );
                         
                         appender_names.push(appender_name.clone());
@@ -384,11 +465,9 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                         }
                     };
                     
-                    // Create HTTP appender
-                    let encoder = match dest.pattern.as_deref() {
-                        Some(pattern) => Box::new(PatternEncoder::new(pattern)) as Box<dyn log4rs::encode::Encode>,
-                        None => Box::new(JsonEncoder::new()) as Box<dyn log4rs::encode::Encode>,
-                    };
+                    // Create HTTP appender (JSON payloads unless a pattern is given)
+                    let default_encoder = if dest.pattern.is_some() { LogEncoder::Pattern } else { LogEncoder::Json };
+                    let encoder = build_encoder(dest, default_encoder, FILE_PATTERN);
                     
                     let mut http_builder = HttpAppender::builder()
                         .url(&http_config.url)
@@ -430,8 +509,8 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                     // Add to configuration
                     builder = builder.appender(
                         Appender::builder()
-                            .filter(Box::new(ThresholdFilter::new(level)))
-                            .build(appender_name, Box::new(http))
+                            .filter(threshold_filter(config, level))
+                            .build(appender_name, maybe_dedupe(config, Box::new(http)))
                     );
                     
                     appender_names.push(appender_name.clone());
@@ -498,8 +577,8 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                     // Add to configuration
                     builder = builder.appender(
                         Appender::builder()
-                            .filter(Box::new(ThresholdFilter::new(level)))
-                            .build(appender_name, Box::new(syslog))
+                            .filter(threshold_filter(config, level))
+                            .build(appender_name, maybe_dedupe(config, Box::new(syslog)))
                     );
                     
                     appender_names.push(appender_name.clone());
@@ -518,10 +597,7 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                     };
                     
                     // Create socket appender
-                    let encoder = match dest.pattern.as_deref() {
-                        Some(pattern) => Box::new(PatternEncoder::new(pattern)) as Box<dyn log4rs::encode::Encode>,
-                        None => Box::new(PatternEncoder::new("{d} - {m}{n}")) as Box<dyn log4rs::encode::Encode>,
-                    };
+                    let encoder = build_encoder(dest, LogEncoder::Pattern, "{d} - {m}{n}");
                     
                     let protocol = socket_config.protocol.to_lowercase();
                     let addr = format!("{}:{}", socket_config.hostname, socket_config.port);
@@ -552,8 +628,8 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                     // Add to configuration
                     builder = builder.appender(
                         Appender::builder()
-                            .filter(Box::new(ThresholdFilter::new(level)))
-                            .build(appender_name, Box::new(socket))
+                            .filter(threshold_filter(config, level))
+                            .build(appender_name, maybe_dedupe(config, Box::new(socket)))
                     );
                     
                     appender_names.push(appender_name.clone());
@@ -588,18 +664,60 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                 
                 #[cfg(feature = "postgres")]
                 LogDestinationType::Postgres => {
-                    // For brevity, this is a simplified implementation
-                    info!("PostgreSQL appender configured - full implementation in production version");
-                    warn!("Using the PostgreSQL appender requires the 'postgres' feature to be enabled");
+                    // Get PostgreSQL configuration
+                    let postgres_config = match &dest.postgres {
+                        Some(config) => config,
+                        None => {
+                            warn!("PostgreSQL configuration required for PostgreSQL appender");
+                            continue;
+                        }
+                    };
+                    
+                    // Create PostgreSQL appender (rows hold the raw message, so no encoder)
+                    let postgres = PostgresAppender::connect(postgres_config)
+                        .map_err(|e| format!("PostgreSQL appender '{}': {:#}", dest.name, e))?;
+                    
+                    // Add to configuration, without the PostgreSQL client's own records
+                    builder = builder.appender(
+                        Appender::builder()
+                            .filter(threshold_filter(config, level))
+                            .filter(Box::new(TargetFilter::excluding(POSTGRES_LOG_TARGETS)))
+                            .build(appender_name, maybe_dedupe(config, Box::new(postgres)))
+                    );
+                    
+                    appender_names.push(appender_name.clone());
+                    info!("Logging to PostgreSQL table {}", postgres_config.table);
                 },
                 
                 // ========== MESSAGE QUEUE APPENDERS ==========
                 
                 #[cfg(feature = "kafka")]
                 LogDestinationType::Kafka => {
-                    // For brevity, this is a simplified implementation
-                    info!("Kafka appender configured - full implementation in production version");
-                    warn!("Using the Kafka appender requires the 'kafka' feature to be enabled");
+                    // Get Kafka configuration
+                    let kafka_config = match &dest.kafka {
+                        Some(config) => config,
+                        None => {
+                            warn!("Kafka configuration required for Kafka appender");
+                            continue;
+                        }
+                    };
+                    
+                    // Create Kafka appender (JSON messages unless a pattern is given)
+                    let default_encoder = if dest.pattern.is_some() { LogEncoder::Pattern } else { LogEncoder::Json };
+                    let encoder = build_encoder(dest, default_encoder, FILE_PATTERN);
+                    let kafka = KafkaAppender::build(kafka_config, encoder)
+                        .map_err(|e| format!("Kafka appender '{}': {:#}", dest.name, e))?;
+                    
+                    // Add to configuration, without the Kafka client's own records
+                    builder = builder.appender(
+                        Appender::builder()
+                            .filter(threshold_filter(config, level))
+                            .filter(Box::new(TargetFilter::excluding(KAFKA_LOG_TARGETS)))
+                            .build(appender_name, maybe_dedupe(config, Box::new(kafka)))
+                    );
+                    
+                    appender_names.push(appender_name.clone());
+                    info!("Logging to Kafka topic {} on {}", kafka_config.topic, kafka_config.brokers.join(","));
                 },
                 
                 #[cfg(feature = "rabbitmq")]
@@ -611,9 +729,30 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                 
                 #[cfg(feature = "redis")]
                 LogDestinationType::Redis => {
-                    // For brevity, this is a simplified implementation
-                    info!("Redis appender configured - full implementation in production version");
-                    warn!("Using the Redis appender requires the 'redis' feature to be enabled");
+                    // Get Redis configuration
+                    let redis_config = match &dest.redis {
+                        Some(config) => config,
+                        None => {
+                            warn!("Redis configuration required for Redis appender");
+                            continue;
+                        }
+                    };
+                    
+                    // Create Redis appender (JSON entries unless a pattern is given)
+                    let default_encoder = if dest.pattern.is_some() { LogEncoder::Pattern } else { LogEncoder::Json };
+                    let encoder = build_encoder(dest, default_encoder, FILE_PATTERN);
+                    let redis = RedisAppender::connect(redis_config, encoder)
+                        .map_err(|e| format!("Redis appender '{}': {:#}", dest.name, e))?;
+                    
+                    // Add to configuration
+                    builder = builder.appender(
+                        Appender::builder()
+                            .filter(threshold_filter(config, level))
+                            .build(appender_name, maybe_dedupe(config, Box::new(redis)))
+                    );
+                    
+                    appender_names.push(appender_name.clone());
+                    info!("Logging to Redis: {} ({} {})", redis_config.uri, redis_config.mode, redis_config.key);
                 },
                 
                 // Fallback for disabled features
@@ -627,23 +766,24 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
         // Fallback to legacy configuration
         // Always create a console appender
         let console = ConsoleAppender::builder()
-            .encoder(Box::new(PatternEncoder::new(CONSOLE_PATTERN)))
+            .encoder(Box::new(PatternEncoder::new(default_console_pattern(config))))
             .build();
         
-        let console_level = parse_level(&config.log_console_level);
+        let console_level = parse_level(legacy_console_level(config));
         
         // Add console appender to configuration
         builder = builder.appender(
             Appender::builder()
-                .filter(Box::new(ThresholdFilter::new(console_level)))
-                .build("console", Box::new(console))
+                .filter(threshold_filter(config, console_level))
+                .filter(Box::new(ConsoleMuteFilter))
+                .build("console", maybe_dedupe(config, Box::new(console)))
         );
         
         appender_names.push("console".to_string());
         
         // Add file appender if enabled
         if config.log_to_file {
-            let file_level = parse_level(&config.log_file_level);
+            let file_level = parse_level(legacy_file_level(config));
             let log_file_path = get_log_file_path(config);
             
             // Create log directory if it doesn't exist
@@ -690,8 +830,8 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                 // Add the rolling file appender to config
                 builder = builder.appender(
                     Appender::builder()
-                        .filter(Box::new(ThresholdFilter::new(file_level)))
-                        .build("rolling_file", Box::new(rolling_file))
+                        .filter(threshold_filter(config, file_level))
+                        .build("rolling_file", maybe_dedupe(config, Box::new(rolling_file)))
                 );
                 
                 appender_names.push("rolling_file".to_string());
@@ -704,8 +844,8 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
                 // Add the file appender to config
                 builder = builder.appender(
                     Appender::builder()
-                        .filter(Box::new(ThresholdFilter::new(file_level)))
-                        .build("file", Box::new(file))
+                        .filter(threshold_filter(config, file_level))
+                        .build("file", maybe_dedupe(config, Box::new(file)))
                 );
                 
                 appender_names.push("file".to_string());
@@ -720,14 +860,16 @@ pub fn init_logging(config: &AppConfig) -> Result<(), Box<dyn std::error::Error>
         root_builder = root_builder.appender(name);
     }
     
+    // Module loggers pass their records on to the root logger's appenders
+    let mut module_levels: Vec<_> = config.log_module_levels.iter().collect();
+    module_levels.sort();
+    for (module, level) in module_levels {
+        builder = builder.logger(Logger::builder().build(module, parse_level(level)));
+    }
+    
     // Determine the maximum log level
     let root_level = parse_level(&config.log_level);
-    let log_config = builder.build(root_builder.build(root_level))?;
-    
-    // Initialize the logging system
-    log4rs::init_config(log_config)?;
-    
-    Ok(())
+    Ok(builder.build(root_builder.build(root_level))?)
 }
 
 // Helper function to get the log file path (for legacy configuration)
@@ -738,19 +880,233 @@ fn get_log_file_path(config: &AppConfig) -> PathBuf {
     }
 }
 
+// Environment variable naming the logs directory, for installs where the
+// working directory isn't the project checkout
+pub const LOG_DIR_ENV: &str = "CONWAYS_STEINWAY_LOG_DIR";
+
 // Helper function to get the default log file path
 fn get_default_log_file_path(_config: &AppConfig) -> PathBuf {
-    // Get the project root directory by finding the directory containing the logs folder
-    let mut path = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    
-    // If we're in the rust directory, go up one level
-    if path.ends_with("rust") {
-        path.pop();
-    }
+    default_log_file_path_from(env::var_os(LOG_DIR_ENV))
+}
+
+// The default log file path given the value of LOG_DIR_ENV, if it is set
+fn default_log_file_path_from(log_dir: Option<OsString>) -> PathBuf {
+    let mut path = match log_dir.filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            // Get the project root directory by finding the directory containing the logs folder
+            let mut path = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            
+            // If we're in the rust directory, go up one level
+            if path.ends_with("rust") {
+                path.pop();
+            }
+            path.push("logs");
+            path
+        }
+    };
     
-    // Add logs directory, backend subdirectory, and file name
-    path.push("logs");
+    // Add backend subdirectory and file name
     path.push(DEFAULT_LOG_SUBDIR);
     path.push(DEFAULT_LOG_FILE);
     path
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_level() {
+        // Test valid log levels
+        assert_eq!(parse_level("trace"), LevelFilter::Trace);
+        assert_eq!(parse_level("debug"), LevelFilter::Debug);
+        assert_eq!(parse_level("info"), LevelFilter::Info);
+        assert_eq!(parse_level("warn"), LevelFilter::Warn);
+        assert_eq!(parse_level("error"), LevelFilter::Error);
+        
+        // Test case insensitivity
+        assert_eq!(parse_level("INFO"), LevelFilter::Info);
+        assert_eq!(parse_level("Debug"), LevelFilter::Debug);
+        assert_eq!(parse_level("ERROR"), LevelFilter::Error);
+        
+        // Test default for invalid input
+        assert_eq!(parse_level("invalid"), LevelFilter::Info);
+        assert_eq!(parse_level(""), LevelFilter::Info);
+    }
+    
+    #[test]
+    fn test_console_pattern_color() {
+        // Explicit settings ignore the environment
+        assert_eq!(console_pattern(LogColor::Never, false, true), PLAIN_CONSOLE_PATTERN);
+        assert_eq!(console_pattern(LogColor::Always, true, false), CONSOLE_PATTERN);
+
+        // Auto needs a terminal and no NO_COLOR
+        assert_eq!(console_pattern(LogColor::Auto, false, true), CONSOLE_PATTERN);
+        assert_eq!(console_pattern(LogColor::Auto, true, true), PLAIN_CONSOLE_PATTERN);
+        assert_eq!(console_pattern(LogColor::Auto, false, false), PLAIN_CONSOLE_PATTERN);
+    }
+
+    #[test]
+    fn test_legacy_levels_merge_into_destinations() {
+        let config = AppConfig {
+            log_console_level: Some("warn".to_string()),
+            ..AppConfig::default()
+        };
+
+        let destinations = effective_destinations(&config);
+        let console = destinations.iter()
+            .find(|d| d.destination_type == LogDestinationType::Console)
+            .unwrap();
+        assert_eq!(console.level.as_deref(), Some("warn"));
+
+        // A level set on the destination itself wins over the legacy setting,
+        // even when it is the default level
+        let mut config = config;
+        config.log_destinations[0].level = Some("error".to_string());
+        assert_eq!(effective_destinations(&config)[0].level.as_deref(), Some("error"));
+        config.log_destinations[0].level = Some("info".to_string());
+        assert_eq!(effective_destinations(&config)[0].level.as_deref(), Some("info"));
+
+        // --log-to-file adds a File destination at the legacy file level
+        let config = AppConfig {
+            log_to_file: true,
+            log_file_level: Some("trace".to_string()),
+            ..AppConfig::default()
+        };
+        let file = effective_destinations(&config).into_iter()
+            .find(|d| d.destination_type == LogDestinationType::File)
+            .unwrap();
+        assert_eq!(file.level.as_deref(), Some("trace"));
+    }
+
+    #[test]
+    fn test_unset_legacy_levels_leave_destinations_alone() {
+        let mut config = AppConfig::default();
+        config.log_destinations.push(LogDestination::new("audit", LogDestinationType::File));
+
+        // No legacy levels given: both fall back to the default level, and the
+        // File destination doesn't pick up the legacy file default
+        let destinations = effective_destinations(&config);
+        assert_eq!(destinations[0].level.as_deref(), Some(DEFAULT_LOG_LEVEL));
+        assert_eq!(destinations[1].level.as_deref(), Some(DEFAULT_LOG_LEVEL));
+
+        // A legacy file level given on the command line applies to it
+        config.log_file_level = Some("warn".to_string());
+        assert_eq!(effective_destinations(&config)[1].level.as_deref(), Some("warn"));
+    }
+
+    #[test]
+    fn test_encoder_override() {
+        let mut dest = AppConfig::default().log_destinations[0].clone();
+        dest.destination_type = LogDestinationType::File;
+
+        // A File destination normally gets a pattern encoder...
+        let encoder = build_encoder(&dest, LogEncoder::Pattern, FILE_PATTERN);
+        assert!(format!("{:?}", encoder).starts_with("PatternEncoder"));
+
+        // ...but encoder = json switches it to JSON
+        dest.encoder = LogEncoder::from_string("json");
+        let encoder = build_encoder(&dest, LogEncoder::Pattern, FILE_PATTERN);
+        assert!(format!("{:?}", encoder).starts_with("JsonEncoder"));
+    }
+
+    #[test]
+    fn test_bad_pattern_names_its_destination() {
+        let mut config = AppConfig::default();
+        config.log_destinations[0].pattern = Some("[{l}] {m}{n}".to_string());
+        let mut file = LogDestination::new("audit", LogDestinationType::File);
+        file.pattern = Some("{d(%Y-%m-%d %H:%M:%S} {m}{n}".to_string());
+        config.log_destinations.push(file);
+
+        let error = init_logging(&config).unwrap_err().to_string();
+        assert!(error.contains("'audit'"), "{}", error);
+        assert!(error.contains("unterminated '('"), "{}", error);
+    }
+
+    #[test]
+    fn test_module_levels_become_loggers() {
+        let mut config = AppConfig::default();
+        config.log_module_levels.insert("audio".to_string(), "debug".to_string());
+        config.log_module_levels.insert("life".to_string(), "warn".to_string());
+
+        let log_config = build_log_config(&config).unwrap();
+        let audio = log_config.loggers().iter().find(|logger| logger.name() == "audio").unwrap();
+        assert_eq!(audio.level(), LevelFilter::Debug);
+        assert!(audio.additive(), "audio records should still reach the destinations");
+        assert_eq!(log_config.loggers().len(), 2);
+        assert_eq!(log_config.root().level(), LevelFilter::Info);
+    }
+
+    #[test]
+    fn test_module_debug_records_reach_default_console() {
+        let mut config = AppConfig::default();
+        config.log_module_levels.insert("audio".to_string(), "debug".to_string());
+
+        let console = effective_destinations(&config).remove(0);
+        assert_eq!(console.destination_type, LogDestinationType::Console);
+        let filter = threshold_filter(&config, parse_level(console.level.as_deref().unwrap()));
+
+        let record = |target, level| {
+            filter.filter(&log::Record::builder().target(target).level(level).args(format_args!("x")).build())
+        };
+        assert_eq!(record("audio::player", log::Level::Debug), log4rs::filter::Response::Neutral);
+        assert_eq!(record("life", log::Level::Debug), log4rs::filter::Response::Reject);
+    }
+
+    #[test]
+    fn test_get_default_log_file_path() {
+        let config = AppConfig::default();
+        let path = get_default_log_file_path(&config);
+        
+        // Check that the path has the expected structure
+        assert!(path.ends_with(DEFAULT_LOG_FILE), 
+                "Path should end with the default log file name");
+        
+        let parent = path.parent().unwrap();
+        assert!(parent.ends_with(DEFAULT_LOG_SUBDIR), 
+                "Parent directory should end with the default log subdirectory");
+    }
+    
+    #[test]
+    fn test_log_dir_env_overrides_default_path() {
+        let temp_dir = tempdir().expect("Failed to create temp directory");
+        let path = default_log_file_path_from(Some(temp_dir.path().into()));
+        assert_eq!(path, temp_dir.path().join(DEFAULT_LOG_SUBDIR).join(DEFAULT_LOG_FILE));
+
+        // An empty value is the same as leaving it unset
+        assert_eq!(default_log_file_path_from(Some(OsString::new())), default_log_file_path_from(None));
+    }
+
+    #[test]
+    fn test_init_logging_with_temp_directory() {
+        // Create a temporary directory for log files
+        let temp_dir = tempdir().expect("Failed to create temp directory");
+        let log_path = temp_dir.path().join("test.log");
+        
+        // Create a minimal config that writes to our temp file
+        let config = AppConfig {
+            log_to_file: true,
+            log_file_path: Some(log_path.clone()),
+            log_level: "debug".to_string(),
+            log_console_level: Some("info".to_string()),
+            log_file_level: Some("debug".to_string()),
+            ..AppConfig::default()
+        };
+        
+        // Initialize logging with this config
+        let result = init_logging(&config);
+        
+        // Verify initialization succeeded
+        assert!(result.is_ok(), "Logging initialization should succeed");
+        
+        // Verify the environment variable is set correctly
+        assert_eq!(std::env::var("RUST_LOG").unwrap_or_default(), "debug", 
+                  "RUST_LOG environment variable should be set to the config log level");
+        
+        // Verify the log file is created (or at least the directory exists)
+        let parent = log_path.parent().unwrap();
+        assert!(parent.exists(), "Log directory should be created");
+    }
+}
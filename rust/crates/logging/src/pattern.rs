@@ -0,0 +1,120 @@
+// Log pattern validation for Conway's Steinway
+// log4rs only reports pattern mistakes by writing an error marker into every
+// log line, so a typo in a configured pattern shows up as garbage output.
+// validate_pattern checks the syntax up front: braces and parentheses must be
+// balanced (literal braces are written `{{` and `}}`) and every `{name}` must
+// be a formatter log4rs knows.
+
+use std::fmt;
+
+// Formatter names accepted by log4rs's PatternEncoder (the empty name groups
+// its arguments, as in `{({l} {m}):20}`)
+const FORMATTERS: &[&str] = &[
+    "", "d", "date", "D", "debug", "f", "file", "h", "highlight", "i", "tid", "I", "thread_id",
+    "K", "key_value", "l", "level", "L", "line", "m", "message", "M", "module", "n", "P", "pid",
+    "R", "release", "t", "target", "T", "thread", "X", "mdc",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    UnterminatedFormat(usize),     // `{` at this offset is never closed
+    UnterminatedArgument(usize),   // `(` at this offset is never closed
+    UnmatchedBrace(usize),         // Stray `}` at this offset
+    UnknownFormatter(String),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::UnterminatedFormat(at) => write!(f, "unterminated '{{' at offset {}", at),
+            PatternError::UnterminatedArgument(at) => write!(f, "unterminated '(' at offset {}", at),
+            PatternError::UnmatchedBrace(at) => write!(f, "unmatched '}}' at offset {} (write '}}}}' for a literal brace)", at),
+            PatternError::UnknownFormatter(name) => write!(f, "unknown formatter '{{{}}}'", name),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+pub fn validate_pattern(pattern: &str) -> Result<(), PatternError> {
+    let chars: Vec<(usize, char)> = pattern.char_indices().collect();
+    parse_text(&chars, 0, false).map(|_| ())
+}
+
+// Literal text and formats up to the end of the pattern or, inside an
+// argument, up to its closing `)` (or a stray `}`). Returns the index where
+// parsing stopped.
+fn parse_text(chars: &[(usize, char)], mut i: usize, in_argument: bool) -> Result<usize, PatternError> {
+    while i < chars.len() {
+        match chars[i].1 {
+            '{' if chars.get(i + 1).map(|c| c.1) == Some('{') => i += 2,
+            '}' if chars.get(i + 1).map(|c| c.1) == Some('}') => i += 2,
+            '{' => i = parse_format(chars, i)?,
+            // A stray brace inside an argument means the argument was never closed
+            ')' | '}' if in_argument => return Ok(i),
+            '}' => return Err(PatternError::UnmatchedBrace(chars[i].0)),
+            _ => i += 1,
+        }
+    }
+    Ok(i)
+}
+
+// One `{name(arg)(arg):spec}` starting at the `{` at `start`. Returns the
+// index just past the closing `}`.
+fn parse_format(chars: &[(usize, char)], start: usize) -> Result<usize, PatternError> {
+    let unterminated = || PatternError::UnterminatedFormat(chars[start].0);
+
+    let mut i = start + 1;
+    let name_start = i;
+    while i < chars.len() && (chars[i].1.is_ascii_alphanumeric() || chars[i].1 == '_') {
+        i += 1;
+    }
+    let name: String = chars[name_start..i].iter().map(|c| c.1).collect();
+    if !FORMATTERS.contains(&name.as_str()) {
+        return Err(PatternError::UnknownFormatter(name));
+    }
+
+    while i < chars.len() && chars[i].1 == '(' {
+        let open = chars[i].0;
+        i = parse_text(chars, i + 1, true)?;
+        if chars.get(i).map(|c| c.1) != Some(')') {
+            return Err(PatternError::UnterminatedArgument(open));
+        }
+        i += 1; // Past the ')'
+    }
+
+    // An optional `:spec` (fill, alignment, width) runs up to the closing brace
+    if i < chars.len() && chars[i].1 == ':' {
+        while i < chars.len() && chars[i].1 != '}' {
+            i += 1;
+        }
+    }
+
+    match chars.get(i) {
+        Some((_, '}')) => Ok(i + 1),
+        _ => Err(unterminated()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_patterns_are_valid() {
+        for pattern in [crate::CONSOLE_PATTERN, crate::PLAIN_CONSOLE_PATTERN, crate::FILE_PATTERN,
+                        "{d(%Y-%m-%d %H:%M:%S%.3f)(utc)} {l:<5.5} {M}:{L} - {m}{n}",
+                        "{({l} {t}):20.20} {{literal}} [%l] %m%n", ""] {
+            assert_eq!(validate_pattern(pattern), Ok(()), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_malformed_patterns_are_rejected() {
+        assert_eq!(validate_pattern("{d(%Y-%m-%d} {m}"), Err(PatternError::UnterminatedArgument(2)));
+        assert_eq!(validate_pattern("[{l] {m}"), Err(PatternError::UnterminatedFormat(1)));
+        assert_eq!(validate_pattern("{m}}"), Err(PatternError::UnmatchedBrace(3)));
+        assert_eq!(validate_pattern("{msg}{n}"), Err(PatternError::UnknownFormatter("msg".to_string())));
+        assert_eq!(validate_pattern("{l:<5"), Err(PatternError::UnterminatedFormat(0)));
+    }
+}
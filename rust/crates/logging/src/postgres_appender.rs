@@ -0,0 +1,235 @@
+// PostgreSQL log appender for Conway's Steinway
+// Stores each log record as a row (timestamp, level, target, message) in the
+// configured table, creating it on first use. Rows are buffered and written
+// batch_size at a time with one multi-row INSERT, since a round trip per log
+// line would slow the main loop down; flush() and dropping the appender write
+// out whatever is still buffered.
+
+use std::fmt;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use anyhow::{bail, Context};
+use log::Record;
+use log4rs::append::Append;
+use postgres::types::ToSql;
+use postgres::{Client, NoTls};
+
+use config::types::PostgresConfig;
+
+// Rows buffered before an INSERT when the config doesn't say
+pub const DEFAULT_POSTGRES_BATCH_SIZE: usize = 50;
+
+// Log targets of the PostgreSQL client. It logs while an INSERT runs, with the
+// appender's lock held, so its records must never reach this appender. The
+// "postgres" prefix also covers postgres_protocol and postgres_types.
+pub const CLIENT_LOG_TARGETS: &[&str] = &["postgres", "tokio_postgres"];
+
+// One buffered log record
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRow {
+    pub logged_at: SystemTime,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+impl LogRow {
+    pub fn from_record(record: &Record) -> Self {
+        LogRow {
+            logged_at: SystemTime::now(),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        }
+    }
+}
+
+// Rows waiting to be written, released in batches of `batch_size`
+#[derive(Debug)]
+pub struct LogBatch {
+    rows: Vec<LogRow>,
+    batch_size: usize,
+}
+
+impl LogBatch {
+    pub fn new(batch_size: usize) -> Self {
+        let batch_size = batch_size.max(1);
+        LogBatch { rows: Vec::with_capacity(batch_size), batch_size }
+    }
+
+    // Buffer a row; once the batch is full, hand back every buffered row
+    pub fn push(&mut self, row: LogRow) -> Option<Vec<LogRow>> {
+        self.rows.push(row);
+        (self.rows.len() >= self.batch_size).then(|| self.take())
+    }
+
+    // Everything buffered so far, leaving the batch empty
+    pub fn take(&mut self) -> Vec<LogRow> {
+        std::mem::replace(&mut self.rows, Vec::with_capacity(self.batch_size))
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+// The table name is spliced into SQL, so only plain (optionally
+// schema-qualified) identifiers are accepted
+fn check_table_name(table: &str) -> anyhow::Result<()> {
+    let valid = !table.is_empty() && table.split('.').all(|part| {
+        let mut chars = part.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    if !valid {
+        bail!("Invalid PostgreSQL table name '{}'", table);
+    }
+    Ok(())
+}
+
+// INSERT statement for `rows` rows, numbering the parameters row by row
+pub fn insert_statement(table: &str, rows: usize) -> String {
+    let values: Vec<String> = (0..rows)
+        .map(|row| {
+            let first = row * 4 + 1;
+            format!("(${}, ${}, ${}, ${})", first, first + 1, first + 2, first + 3)
+        })
+        .collect();
+    format!("INSERT INTO {} (logged_at, level, target, message) VALUES {}", table, values.join(", "))
+}
+
+pub struct PostgresAppender {
+    state: Mutex<(Client, LogBatch)>,
+    table: String,
+}
+
+impl PostgresAppender {
+    // Connect and make sure the table exists; fails if the table name is
+    // invalid or the database can't be reached
+    pub fn connect(config: &PostgresConfig) -> anyhow::Result<Self> {
+        check_table_name(&config.table)?;
+        let mut client = Client::connect(&config.connection_string, NoTls)
+            .context("Unable to connect to PostgreSQL")?;
+        client.batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                id BIGSERIAL PRIMARY KEY,
+                logged_at TIMESTAMPTZ NOT NULL,
+                level TEXT NOT NULL,
+                target TEXT NOT NULL,
+                message TEXT NOT NULL
+            )",
+            config.table
+        )).with_context(|| format!("Unable to create log table {}", config.table))?;
+
+        let batch = LogBatch::new(config.batch_size.unwrap_or(DEFAULT_POSTGRES_BATCH_SIZE));
+        Ok(PostgresAppender {
+            state: Mutex::new((client, batch)),
+            table: config.table.clone(),
+        })
+    }
+
+    fn insert(client: &mut Client, table: &str, rows: &[LogRow]) -> anyhow::Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let params: Vec<&(dyn ToSql + Sync)> = rows.iter()
+            .flat_map(|row| [
+                &row.logged_at as &(dyn ToSql + Sync),
+                &row.level,
+                &row.target,
+                &row.message,
+            ])
+            .collect();
+        client.execute(&insert_statement(table, rows.len()), &params)
+            .with_context(|| format!("Unable to write {} log rows to {}", rows.len(), table))?;
+        Ok(())
+    }
+
+    fn write_buffered(&self) -> anyhow::Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (client, batch) = &mut *state;
+        let rows = batch.take();
+        Self::insert(client, &self.table, &rows)
+    }
+}
+
+impl fmt::Debug for PostgresAppender {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PostgresAppender").field("table", &self.table).finish()
+    }
+}
+
+impl Append for PostgresAppender {
+    fn append(&self, record: &Record) -> anyhow::Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (client, batch) = &mut *state;
+        match batch.push(LogRow::from_record(record)) {
+            Some(rows) => Self::insert(client, &self.table, &rows),
+            None => Ok(()),
+        }
+    }
+
+    fn flush(&self) {
+        if let Err(e) = self.write_buffered() {
+            eprintln!("log4rs: {:#}", e);
+        }
+    }
+}
+
+impl Drop for PostgresAppender {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(message: &str) -> LogRow {
+        LogRow {
+            logged_at: SystemTime::UNIX_EPOCH,
+            level: "INFO".to_string(),
+            target: "test".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_batch_flushes_at_threshold() {
+        let mut batch = LogBatch::new(3);
+        assert_eq!(batch.push(row("one")), None);
+        assert_eq!(batch.push(row("two")), None);
+        assert_eq!(batch.push(row("three")), Some(vec![row("one"), row("two"), row("three")]));
+        assert!(batch.is_empty());
+
+        // A partial batch waits for flush
+        assert_eq!(batch.push(row("four")), None);
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.take(), vec![row("four")]);
+        assert!(batch.take().is_empty());
+    }
+
+    #[test]
+    fn test_zero_batch_size_writes_every_row() {
+        let mut batch = LogBatch::new(0);
+        assert_eq!(batch.push(row("now")), Some(vec![row("now")]));
+    }
+
+    #[test]
+    fn test_insert_statement_and_table_names() {
+        assert_eq!(insert_statement("logs", 2),
+                   "INSERT INTO logs (logged_at, level, target, message) VALUES ($1, $2, $3, $4), ($5, $6, $7, $8)");
+
+        assert!(check_table_name("steinway_logs").is_ok());
+        assert!(check_table_name("audit.steinway_logs").is_ok());
+        for bad in ["", "logs; DROP TABLE users", "1logs", "logs.", "my-logs"] {
+            assert!(check_table_name(bad).is_err(), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+// Integration test for the Kafka log appender
+// Needs a running broker: set CONWAYS_STEINWAY_TEST_KAFKA_BROKERS (e.g.
+// localhost:9092) to run it; without it the test is skipped.
+#![cfg(feature = "kafka")]
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use config::types::KafkaConfig;
+use kafka::consumer::{Consumer, FetchOffset};
+use log::{Level, Record};
+use log4rs::append::Append;
+use log4rs::encode::pattern::PatternEncoder;
+use logging::kafka_appender::KafkaAppender;
+
+#[test]
+fn test_produced_log_line_can_be_consumed() {
+    let Ok(brokers) = env::var("CONWAYS_STEINWAY_TEST_KAFKA_BROKERS") else {
+        eprintln!("CONWAYS_STEINWAY_TEST_KAFKA_BROKERS not set; skipping the Kafka round trip");
+        return;
+    };
+    let brokers: Vec<String> = brokers.split(',').map(str::to_string).collect();
+    // A fresh topic per run so earlier runs' messages don't match
+    let topic = format!("steinway-test-{}", std::process::id());
+    let message = format!("Step 1: 48 (A4) from run {}", std::process::id());
+
+    let config = KafkaConfig {
+        brokers: brokers.clone(),
+        topic: topic.clone(),
+        client_id: None,
+        compression: Some("gzip".to_string()),
+    };
+    let appender = KafkaAppender::build(&config, Box::new(PatternEncoder::new("{m}{n}"))).unwrap();
+    appender.append(&Record::builder()
+        .args(format_args!("{}", message))
+        .level(Level::Info)
+        .target("conways_steinway")
+        .build()).unwrap();
+    appender.flush();
+    drop(appender);
+
+    let mut consumer = Consumer::from_hosts(brokers)
+        .with_topic(topic)
+        .with_fallback_offset(FetchOffset::Earliest)
+        .create()
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut received = Vec::new();
+    while received.is_empty() && Instant::now() < deadline {
+        for set in consumer.poll().unwrap().iter() {
+            received.extend(set.messages().iter().map(|m| String::from_utf8_lossy(m.value).to_string()));
+        }
+    }
+    assert_eq!(received, vec![message]);
+}
@@ -2,7 +2,6 @@
 // Tests that logging can be initialized and used properly
 
 use log::{info, debug, trace};
-use env_logger;
 
 #[test]
 fn test_logger_initialization() {
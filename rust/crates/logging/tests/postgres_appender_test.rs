@@ -0,0 +1,43 @@
+// Integration test for the PostgreSQL log appender
+// Needs a running database: set CONWAYS_STEINWAY_TEST_POSTGRES_URL (e.g.
+// "host=localhost user=postgres") to run it; without it the test is skipped.
+#![cfg(feature = "postgres")]
+
+use std::env;
+
+use config::types::PostgresConfig;
+use log::{Level, Record};
+use log4rs::append::Append;
+use logging::postgres_appender::PostgresAppender;
+use postgres::{Client, NoTls};
+
+#[test]
+fn test_each_record_becomes_a_row() {
+    let Ok(connection_string) = env::var("CONWAYS_STEINWAY_TEST_POSTGRES_URL") else {
+        eprintln!("CONWAYS_STEINWAY_TEST_POSTGRES_URL not set; skipping the PostgreSQL round trip");
+        return;
+    };
+    // A fresh table per run, and a batch size that leaves a partial batch for drop to write
+    let table = format!("steinway_test_logs_{}", std::process::id());
+    let config = PostgresConfig {
+        connection_string: connection_string.clone(),
+        table: table.clone(),
+        batch_size: Some(4),
+    };
+
+    let appender = PostgresAppender::connect(&config).unwrap();
+    let records = 10;
+    for step in 0..records {
+        appender.append(&Record::builder()
+            .args(format_args!("Step {}", step))
+            .level(Level::Info)
+            .target("conways_steinway")
+            .build()).unwrap();
+    }
+    drop(appender);
+
+    let mut client = Client::connect(&connection_string, NoTls).unwrap();
+    let rows: i64 = client.query_one(&format!("SELECT COUNT(*) FROM {}", table), &[]).unwrap().get(0);
+    client.batch_execute(&format!("DROP TABLE {}", table)).unwrap();
+    assert_eq!(rows, records);
+}
@@ -0,0 +1,137 @@
+// Integration tests for the Redis log appender
+// A small in-process server stands in for Redis: it answers the RESP commands
+// the appender sends and keeps LPUSHed lists and PUBLISHed messages so the
+// test can read them back.
+#![cfg(feature = "redis")]
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use config::types::RedisConfig;
+use log::{Level, Record};
+use log4rs::append::Append;
+use log4rs::encode::pattern::PatternEncoder;
+use logging::redis_appender::RedisAppender;
+
+#[derive(Default)]
+struct MockRedis {
+    lists: HashMap<String, Vec<String>>,
+    published: Vec<(String, String)>,
+    commands: Vec<String>,
+}
+
+// Read one RESP array of bulk strings, or None once the client hangs up
+fn read_command(reader: &mut BufReader<TcpStream>) -> Option<Vec<String>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).ok()? == 0 {
+        return None;
+    }
+    let count: usize = line.trim_end().strip_prefix('*')?.parse().ok()?;
+
+    let mut args = Vec::with_capacity(count);
+    for _ in 0..count {
+        line.clear();
+        reader.read_line(&mut line).ok()?;
+        let len: usize = line.trim_end().strip_prefix('$')?.parse().ok()?;
+        let mut arg = vec![0; len + 2];
+        reader.read_exact(&mut arg).ok()?;
+        arg.truncate(len);
+        args.push(String::from_utf8(arg).ok()?);
+    }
+    Some(args)
+}
+
+// Serve one client on a background thread; returns its address and state
+fn start_mock_redis() -> (String, Arc<Mutex<MockRedis>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let state = Arc::new(Mutex::new(MockRedis::default()));
+
+    let server_state = Arc::clone(&state);
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        while let Some(args) = read_command(&mut reader) {
+            let mut state = server_state.lock().unwrap();
+            state.commands.push(args[0].to_uppercase());
+            let reply = match args[0].to_uppercase().as_str() {
+                "LPUSH" => {
+                    let list = state.lists.entry(args[1].clone()).or_default();
+                    list.insert(0, args[2].clone());
+                    format!(":{}\r\n", list.len())
+                }
+                "PUBLISH" => {
+                    state.published.push((args[1].clone(), args[2].clone()));
+                    ":0\r\n".to_string()
+                }
+                "SELECT" => "+OK\r\n".to_string(),
+                other => format!("-ERR unknown command '{}'\r\n", other),
+            };
+            stream.write_all(reply.as_bytes()).unwrap();
+        }
+    });
+
+    (address, state)
+}
+
+fn log_line(appender: &RedisAppender, message: &str) -> anyhow::Result<()> {
+    appender.append(&Record::builder()
+        .args(format_args!("{}", message))
+        .level(Level::Info)
+        .target("conways_steinway")
+        .build())
+}
+
+#[test]
+fn test_list_mode_pushes_log_lines() {
+    let (address, state) = start_mock_redis();
+    let config = RedisConfig {
+        uri: format!("redis://{}/3", address),
+        key: "steinway:logs".to_string(),
+        mode: "list".to_string(),
+    };
+
+    let appender = RedisAppender::connect(&config, Box::new(PatternEncoder::new("{m}{n}"))).unwrap();
+    log_line(&appender, "Step 1: 48 (A4)").unwrap();
+    log_line(&appender, "Step 2: -").unwrap();
+
+    let state = state.lock().unwrap();
+    assert_eq!(state.commands, vec!["SELECT", "LPUSH", "LPUSH"]);
+    // LPUSH puts the newest line first, without the encoder's newline
+    assert_eq!(state.lists["steinway:logs"], vec!["Step 2: -", "Step 1: 48 (A4)"]);
+}
+
+#[test]
+fn test_pubsub_mode_publishes_log_lines() {
+    let (address, state) = start_mock_redis();
+    let config = RedisConfig {
+        uri: format!("redis://{}", address),
+        key: "steinway".to_string(),
+        mode: "pubsub".to_string(),
+    };
+
+    let appender = RedisAppender::connect(&config, Box::new(PatternEncoder::new("{m}{n}"))).unwrap();
+    log_line(&appender, "Board has settled").unwrap();
+
+    let state = state.lock().unwrap();
+    assert_eq!(state.published, vec![("steinway".to_string(), "Board has settled".to_string())]);
+    assert!(state.lists.is_empty());
+}
+
+#[test]
+fn test_connection_failure_is_an_error() {
+    // Bind and drop a listener to find a port nothing is listening on
+    let address = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let config = RedisConfig {
+        uri: format!("redis://{}", address),
+        key: "steinway".to_string(),
+        mode: "list".to_string(),
+    };
+    assert!(RedisAppender::connect(&config, Box::new(PatternEncoder::new("{m}"))).is_err());
+
+    let bad_mode = RedisConfig { mode: "stream".to_string(), ..config };
+    assert!(RedisAppender::connect(&bad_mode, Box::new(PatternEncoder::new("{m}"))).is_err());
+}
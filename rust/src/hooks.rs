@@ -0,0 +1,160 @@
+// Per-generation work around playing a step, shared by the plain main loop
+// and the terminal UI so both produce the same outputs: the --report tally,
+// --events-out, --serve frames, the melodic interval log, and the extinction
+// and settling checks that can end a run.
+//
+// What is seen trails what is played by --av-sync-ms: each generation's board
+// is queued and only shown (published to --serve, printed, drawn) once the
+// run's clock has moved that far past the notes.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use log::{info, warn};
+
+use audio::timeline::visual_timestamp_ms;
+use audio::{ColumnActivity, IntervalTracker, PlayerPiano, PrincipalNote};
+use config::{Config, GenerationLimit};
+use life::{GameOfLife, StabilityDetector};
+
+use crate::serve::FrameBroadcaster;
+use crate::{advance_with_velocity, EventLog, FrameState};
+
+// A generation as it should be shown: the board after the step and the keys
+// the step played
+pub struct VisualFrame {
+    pub step: u32,
+    pub game: GameOfLife,
+    pub played_keys: Vec<usize>,
+}
+
+pub struct StepHooks {
+    // Milliseconds of the performance waited out so far
+    clock_ms: u64,
+    // Frames waiting for their visual timestamp, oldest first
+    pending: VecDeque<(u64, VisualFrame)>,
+    intervals: IntervalTracker,
+    events: Option<EventLog>,
+    broadcaster: Option<Arc<FrameBroadcaster>>,
+    activity: ColumnActivity,
+    stability: StabilityDetector,
+    extinct: bool,
+}
+
+impl StepHooks {
+    // Hooks for a run starting from `game`. Frames are published to
+    // `broadcaster` when --serve is running one.
+    pub fn new(config: &Config, game: &GameOfLife, broadcaster: Option<Arc<FrameBroadcaster>>) -> Self {
+        StepHooks {
+            clock_ms: 0,
+            pending: VecDeque::new(),
+            intervals: IntervalTracker::new(PrincipalNote::Highest),
+            events: config.events_out.as_ref().map(|_| EventLog::from_config(config)),
+            broadcaster,
+            activity: ColumnActivity::new(),
+            stability: StabilityDetector::default(),
+            // A board that dies out plays nothing until random rows bring it back
+            extinct: game.is_empty(),
+        }
+    }
+
+    // Play the `step`th generation on `piano`, record it, and queue its frame
+    // to be shown once the sync offset has passed
+    pub fn play_step(&mut self, config: &Config, game: &mut GameOfLife, piano: &PlayerPiano, step: u32) {
+        let notes = advance_with_velocity(game, config);
+        piano.play_notes(&notes);
+        let keys: Vec<usize> = notes.iter().map(|&(key, _)| key).collect();
+
+        self.activity.observe(&keys);
+        if let Some(events) = &mut self.events {
+            events.record(game.generation(), &keys);
+        }
+        if let Some(interval) = self.intervals.observe(&keys) {
+            info!("Melodic interval: {:+} semitones", interval);
+        }
+
+        let due = visual_timestamp_ms(self.clock_ms, config.av_sync_ms);
+        self.pending.push_back((due, VisualFrame { step, game: game.clone(), played_keys: keys }));
+    }
+
+    // Wait out the delay after the `step`th generation on the piano's clock,
+    // publishing each queued frame to --serve and handing it to `show` as its
+    // visual timestamp comes up
+    pub fn wait(&mut self, config: &Config, piano: &PlayerPiano, step: u32, show: impl FnMut(&VisualFrame)) {
+        let end = self.clock_ms + step_delay(config, step);
+        self.wait_until(end, piano, show);
+    }
+
+    // Wait for every frame still queued and show it; used when the run ends
+    // with frames still held back by the sync offset
+    pub fn finish(&mut self, piano: &PlayerPiano, show: impl FnMut(&VisualFrame)) {
+        let end = self.pending.back().map_or(self.clock_ms, |&(due, _)| due.max(self.clock_ms));
+        self.wait_until(end, piano, show);
+    }
+
+    fn wait_until(&mut self, end: u64, piano: &PlayerPiano, mut show: impl FnMut(&VisualFrame)) {
+        while let Some(&(due, _)) = self.pending.front() {
+            if due > end {
+                break;
+            }
+            self.advance_clock(due, piano);
+            if let Some((_, visual)) = self.pending.pop_front() {
+                if let Some(broadcaster) = &self.broadcaster {
+                    broadcaster.publish(&FrameState::capture(&visual.game, visual.played_keys.clone()));
+                }
+                show(&visual);
+            }
+        }
+        self.advance_clock(end, piano);
+    }
+
+    fn advance_clock(&mut self, to_ms: u64, piano: &PlayerPiano) {
+        if to_ms > self.clock_ms {
+            piano.wait(to_ms - self.clock_ms);
+            self.clock_ms = to_ms;
+        }
+    }
+
+    // Check the board once the step delay has passed. Returns false when the
+    // run should stop: the board died out with --stop-on-extinction, or an
+    // unlimited run settled into a still life or short cycle.
+    pub fn should_continue(&mut self, config: &Config, game: &GameOfLife) -> bool {
+        if game.is_empty() {
+            if !self.extinct {
+                warn!("Every cell has died out at generation {}", game.generation());
+                if config.stop_on_extinction {
+                    info!("Stopping on extinction");
+                    return false;
+                }
+            }
+            self.extinct = true;
+        } else {
+            self.extinct = false;
+        }
+
+        if matches!(config.generations, GenerationLimit::Unlimited) {
+            if let Some(period) = self.stability.observe(game.fingerprint()) {
+                info!("Board has settled into a cycle of period {}; stopping", period);
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn activity(&self) -> &ColumnActivity {
+        &self.activity
+    }
+
+    pub fn events(&self) -> Option<&EventLog> {
+        self.events.as_ref()
+    }
+}
+
+// Delay after the `step`th generation (counting from 1), following the tempo
+// and any tempo ramp
+pub fn step_delay(config: &Config, step: u32) -> u64 {
+    match config.generations {
+        GenerationLimit::Limited(max) => config.get_effective_delay_at(step.saturating_sub(1), max),
+        GenerationLimit::Unlimited => config.get_effective_delay(),
+    }
+}
@@ -0,0 +1,475 @@
+// Library interface for Conway's Steinway
+// Exposes the deterministic core of the program (board setup and note generation)
+// so full runs can be driven without audio, timing or logging setup
+
+pub mod hooks;
+pub mod serve;
+pub mod tui;
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+
+use audio::key_mapping::transpose_keys;
+use audio::timeline::{build_timeline, timeline_length_ms};
+use audio::{key_to_note_name, AudioRecorder, ChordDetector, ChordQuality, MidiWriter, PlayerPiano, ScaleMapper};
+use config::{BoardType, Config, GenerationLimit, KeyRangePolicy, SonifyRow, VelocityCurve};
+use life::{Cell, GameBoard, GameOfLife, StabilityDetector, BOARD_WIDTH, MAX_VELOCITY, MIN_VELOCITY};
+
+// Seed used by seeded board types when no --seed is given
+pub const DEFAULT_SEED: u64 = 12345;
+
+// Build the initial board for the configured board type
+pub fn create_board(config: &Config) -> GameOfLife {
+    let mut game = match config.board_type {
+        BoardType::Static => {
+            info!("Using complex predefined patterns");
+            GameBoard::create_complex_board()
+        },
+        BoardType::FurElise => {
+            info!("Using Für Elise melody configuration");
+            GameBoard::create_fur_elise_board()
+        },
+        BoardType::Random => match config.seed {
+            Some(seed) => {
+                info!("Using random board configuration (seed {})", seed);
+                random_board(config, seed)
+            },
+            None => {
+                // Log the fresh seed so a run worth keeping can be replayed with --seed
+                let seed = rand::random();
+                info!("Using random board configuration (seed {})", seed);
+                random_board(config, seed)
+            },
+        },
+        BoardType::Soup => {
+            let seed = config.seed.unwrap_or(DEFAULT_SEED);
+            // Center the soup unless a position was configured
+            let row = config.soup_row.unwrap_or(config.board_height.saturating_sub(config.soup_height) / 2);
+            let col = config.soup_col.unwrap_or(BOARD_WIDTH.saturating_sub(config.soup_width) / 2);
+            info!("Using {}x{} soup at ({}, {}) with seed {}",
+                  config.soup_width, config.soup_height, row, col, seed);
+            let mut game = GameOfLife::with_dimensions(config.board_height);
+            GameBoard::place_soup(&mut game, seed, config.alive_probability, row, col,
+                                  config.soup_width, config.soup_height);
+            game
+        },
+        BoardType::Custom(ref path) => match GameOfLife::from_pattern_file(path) {
+            Ok(game) => {
+                info!("Using pattern file {}", path.display());
+                game
+            },
+            Err(e) => {
+                error!("Unable to load pattern file {}: {}; using the random board instead", path.display(), e);
+                random_board(config, config.seed.unwrap_or_else(rand::random))
+            },
+        },
+        BoardType::Named(ref name) => match GameBoard::create_named_board(name) {
+            Some(game) => {
+                info!("Using a centered {}", name);
+                game
+            },
+            None => {
+                error!("Unknown pattern {}; using the random board instead", name);
+                random_board(config, config.seed.unwrap_or_else(rand::random))
+            },
+        },
+        BoardType::Complex | BoardType::Showcase => {
+            // Default to random board for these types
+            info!("Using random board for {:?} type", config.board_type);
+            random_board(config, config.seed.unwrap_or_else(rand::random))
+        }
+    };
+
+    // Fixed patterns are laid out on a default-height board; resizing keeps
+    // them the same distance from the bottom (played) row
+    game.set_height(config.board_height);
+
+    // Let the board evolve on its own once random injection ends
+    game.set_random_injection_generations(config.random_injection_generations);
+    game.set_random_injection(config.random_injection);
+    game.set_decay_states(config.decay_states);
+    game.set_injection_seed(config.seed);
+    game.set_injection_probability(config.alive_probability);
+
+    game
+}
+
+// Seeded random board at the configured alive probability
+pub fn random_board(config: &Config, seed: u64) -> GameOfLife {
+    let mut game = GameOfLife::with_dimensions(config.board_height);
+    GameBoard::fill_random_seeded(&mut game, seed, config.alive_probability);
+    game
+}
+
+// Keys for the sonified rows of the current board: each row's live columns,
+// transposed by that row's offset, merged into one sorted set
+pub fn sonify_rows(game: &GameOfLife, rows: &[SonifyRow], policy: KeyRangePolicy) -> Vec<usize> {
+    let mut keys = BTreeSet::new();
+    for sonified in rows {
+        let Some(row) = (game.height() - 1).checked_sub(sonified.row) else {
+            continue;
+        };
+        let live: Vec<usize> = (0..BOARD_WIDTH)
+            .filter(|&col| game.get_cell(row, col) == Cell::Alive)
+            .collect();
+        keys.extend(transpose_keys(&live, sonified.transpose, policy));
+    }
+    keys.into_iter().collect()
+}
+
+// Play one generation: return its keys and advance the board. With no
+// sonified rows configured this is just the bottom (trigger) row. Keys are
+// snapped onto the configured scale, if any.
+pub fn advance(game: &mut GameOfLife, config: &Config) -> Vec<usize> {
+    let keys = if config.sonify_rows.is_empty() {
+        GameBoard::get_bottom_row_and_advance(game)
+    } else {
+        let keys = sonify_rows(game, &config.sonify_rows, config.out_of_range);
+        GameBoard::get_bottom_row_and_advance(game);
+        keys
+    };
+
+    match ScaleMapper::from_config(config) {
+        Some(mapper) => mapper.map_keys(&keys),
+        None => keys,
+    }
+}
+
+// Like advance, but each key comes with a velocity from how crowded its cell
+// was, shaped by the configured velocity curve. Sonified rows are transposed
+// away from their cells, so they play at full velocity.
+pub fn advance_with_velocity(game: &mut GameOfLife, config: &Config) -> Vec<(usize, u8)> {
+    if !config.sonify_rows.is_empty() {
+        return advance(game, config).into_iter().map(|key| (key, MAX_VELOCITY)).collect();
+    }
+
+    let notes: Vec<(usize, u8)> = GameBoard::get_bottom_row_with_velocity(game)
+        .into_iter()
+        .map(|(key, velocity)| (key, shape_velocity(velocity, config.velocity_curve)))
+        .collect();
+
+    match ScaleMapper::from_config(config) {
+        Some(mapper) => mapper.map_notes(&notes),
+        None => notes,
+    }
+}
+
+// Bend a neighbor-count velocity along a curve, keeping its MIN..MAX range
+pub fn shape_velocity(velocity: u8, curve: VelocityCurve) -> u8 {
+    let span = (MAX_VELOCITY - MIN_VELOCITY) as f64;
+    let density = velocity.saturating_sub(MIN_VELOCITY) as f64 / span;
+    MIN_VELOCITY + (curve.apply(density) * span).round() as u8
+}
+
+// Snapshot of one generation for custom renderers: the board after the step,
+// as (row, col) coordinates of live cells, plus the keys the step played
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameState {
+    pub generation: u32,
+    pub live_cells: Vec<(u16, u16)>,
+    pub played_keys: Vec<usize>,
+    pub population: usize,
+}
+
+impl FrameState {
+    // Capture the current board along with the keys that were just played
+    pub fn capture(game: &GameOfLife, played_keys: Vec<usize>) -> Self {
+        let live_cells: Vec<(u16, u16)> = (0..game.height())
+            .flat_map(|row| (0..BOARD_WIDTH).map(move |col| (row, col)))
+            .filter(|&(row, col)| game.get_cell(row, col) == Cell::Alive)
+            .map(|(row, col)| (row as u16, col as u16))
+            .collect();
+
+        FrameState {
+            generation: game.generation(),
+            population: live_cells.len(),
+            live_cells,
+            played_keys,
+        }
+    }
+}
+
+// One generation of a run as written by --events-out
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationEvent {
+    pub generation: u32,
+    pub keys: Vec<usize>,
+    pub chord: Option<ChordQuality>,
+}
+
+// Records the keys and detected chord of every generation so a run can be
+// handed to a visualizer or other downstream tooling as JSON
+#[derive(Debug, Clone, Default)]
+pub struct EventLog {
+    detector: ChordDetector,
+    events: Vec<GenerationEvent>,
+}
+
+impl EventLog {
+    pub fn new(detector: ChordDetector) -> Self {
+        EventLog {
+            detector,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(ChordDetector::new(config.chord_cluster_size, config.chord_cluster_gap))
+    }
+
+    pub fn record(&mut self, generation: u32, keys: &[usize]) {
+        self.events.push(GenerationEvent {
+            generation,
+            keys: keys.to_vec(),
+            chord: self.detector.classify(keys),
+        });
+    }
+
+    pub fn events(&self) -> &[GenerationEvent] {
+        &self.events
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.events)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+// True when the board has no live cells and nothing will ever be injected,
+// so the run would stay silent forever
+pub fn starts_silent(game: &GameOfLife) -> bool {
+    game.is_empty() && !game.random_injection_active()
+}
+
+// Steps a performance one generation at a time, yielding a FrameState per
+// step and stopping at the configured generation limit. No audio or sleeps.
+pub struct Simulation {
+    game: GameOfLife,
+    config: Config,
+    steps: u32,
+}
+
+impl Simulation {
+    pub fn new(config: &Config) -> Self {
+        Simulation {
+            game: create_board(config),
+            config: config.clone(),
+            steps: 0,
+        }
+    }
+
+    pub fn game(&self) -> &GameOfLife {
+        &self.game
+    }
+}
+
+impl Iterator for Simulation {
+    type Item = FrameState;
+
+    fn next(&mut self) -> Option<FrameState> {
+        if let GenerationLimit::Limited(max) = self.config.generations {
+            if self.steps >= max {
+                return None;
+            }
+        }
+        self.steps += 1;
+
+        let played_keys = advance(&mut self.game, &self.config);
+        Some(FrameState::capture(&self.game, played_keys))
+    }
+}
+
+// Run `limit` generations and return the keys played by each one.
+// No audio, sleeps or logging setup are involved, so this is the testable
+// core of a whole performance.
+pub fn simulate_notes(config: &Config, limit: u32) -> Vec<Vec<usize>> {
+    let mut game = create_board(config);
+
+    (0..limit)
+        .map(|_| advance(&mut game, config))
+        .collect()
+}
+
+// Run the configured number of generations and write them as a MIDI file,
+// one step per generation. Notes last note_duration_ms and detected chords
+// chord_duration_ms, as laid out by the note timeline. No audio device is
+// touched. Returns the note count.
+pub fn export_midi(config: &Config, path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let limit = match config.generations {
+        GenerationLimit::Limited(limit) => limit,
+        GenerationLimit::Unlimited => return Err("MIDI export needs a generation limit (--generations)".into()),
+    };
+
+    let writer = midi_writer(config, &simulate_notes(config, limit));
+    writer.write(path)?;
+    Ok(writer.note_count())
+}
+
+// MIDI writer holding the timeline of the given generations
+fn midi_writer(config: &Config, generations: &[Vec<usize>]) -> MidiWriter {
+    let mut writer = MidiWriter::new(config.get_effective_delay());
+    for note in build_timeline(generations, config) {
+        writer.add_note(note);
+    }
+    writer.extend_to(timeline_length_ms(generations.len(), config));
+    writer
+}
+
+// Separates frames in an exported animation; `less` and most pagers stop at it
+pub const FRAME_SEPARATOR: char = '\x0c';
+
+// The board as printed (its Display output) for `steps` generations, starting
+// with the current one. Only the Life rules are applied between frames, with
+// no scrolling or random rows, so patterns stay in place; see
+// render_frames_with for stepping the board another way.
+pub fn render_frames(game: &mut GameOfLife, steps: usize) -> Vec<String> {
+    render_frames_with(game, steps, GameOfLife::next_generation)
+}
+
+// The board as printed for `steps` generations, starting with the current
+// one and calling `step` to move from each frame to the next
+pub fn render_frames_with(game: &mut GameOfLife, steps: usize,
+                          mut step: impl FnMut(&mut GameOfLife)) -> Vec<String> {
+    (0..steps)
+        .map(|frame| {
+            if frame > 0 {
+                step(game);
+            }
+            game.to_string()
+        })
+        .collect()
+}
+
+// Write the configured number of generations of the board as a text
+// animation, frames separated by form feeds. Returns the frame count. The
+// board is stepped with `advance`, as in a real run, so the frames scroll and
+// show the injected rows.
+pub fn export_frames(config: &Config, path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let limit = match config.generations {
+        GenerationLimit::Limited(limit) => limit,
+        GenerationLimit::Unlimited => return Err("Frame export needs a generation limit (--generations)".into()),
+    };
+
+    let mut game = create_board(config);
+    let frames = render_frames_with(&mut game, limit as usize, |game| {
+        advance(game, config);
+    });
+    fs::write(path, frames.join(&FRAME_SEPARATOR.to_string()))?;
+    Ok(frames.len())
+}
+
+// Keys with their note names for logging, e.g. "39 (C4), 48 (A4)"
+pub fn describe_keys(keys: &[usize]) -> String {
+    if keys.is_empty() {
+        return "-".to_string();
+    }
+    keys.iter()
+        .map(|&key| format!("{} ({})", key, key_to_note_name(key)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Step through a run for --dry-run: each generation is played on a silent
+// piano (NullAudioEngine) with no step delay, and its keys are handed to
+// `on_step` along with the step number. Stops at the generation limit, or for
+// unlimited runs once the board settles. Returns the number of steps run.
+pub fn dry_run<F>(config: &Config, mut on_step: F) -> u32
+where
+    F: FnMut(u32, &[usize]),
+{
+    let mut game = create_board(config);
+    let piano = PlayerPiano::new_silent();
+    let mut stability = StabilityDetector::default();
+    let mut step = 0;
+
+    loop {
+        if let GenerationLimit::Limited(max) = config.generations {
+            if step >= max {
+                break;
+            }
+        }
+        step += 1;
+
+        let notes = advance_with_velocity(&mut game, config);
+        piano.play_notes(&notes);
+        let keys: Vec<usize> = notes.iter().map(|&(key, _)| key).collect();
+        on_step(step, &keys);
+
+        if matches!(config.generations, GenerationLimit::Unlimited) && stability.observe(game.fingerprint()).is_some() {
+            break;
+        }
+    }
+    step
+}
+
+// File name for one batch run, numbered and tagged with the seed it used
+pub fn batch_file_name(run: u32, seed: u64, extension: &str) -> String {
+    format!("run_{:03}_seed_{}.{}", run, seed, extension)
+}
+
+// Play `limit` generations into an offline recording, waiting each step's
+// delay on the recorder's virtual clock. Returns the keys of each generation
+// along with the recording.
+pub fn record_run(config: &Config, limit: u32) -> (Vec<Vec<usize>>, AudioRecorder) {
+    let mut game = create_board(config);
+    let recorder = AudioRecorder::from_config(config);
+    let piano = PlayerPiano::with_recorder(recorder.clone());
+
+    let generations = (0..limit)
+        .map(|step| {
+            let notes = advance_with_velocity(&mut game, config);
+            piano.play_notes(&notes);
+            piano.wait(config.get_effective_delay_at(step, limit));
+            notes.into_iter().map(|(key, _)| key).collect()
+        })
+        .collect();
+    (generations, recorder)
+}
+
+// Render `count` performances with successive seeds (seed, seed+1, ...) into
+// numbered files in the output directory: a WAV recording and a MIDI file per
+// run. No audio device is touched. Each run's summary is logged as it finishes.
+pub fn run_batch(config: &Config, count: u32, output_dir: &Path) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let limit = match config.generations {
+        GenerationLimit::Limited(limit) => limit,
+        GenerationLimit::Unlimited => return Err("Batch mode needs a generation limit (--generations)".into()),
+    };
+
+    fs::create_dir_all(output_dir)?;
+
+    let base_seed = config.seed.unwrap_or(0);
+    let mut outputs = Vec::new();
+
+    for run in 1..=count {
+        let seed = base_seed.wrapping_add(u64::from(run - 1));
+        let run_config = Config {
+            seed: Some(seed),
+            ..config.clone()
+        };
+
+        let (notes, recorder) = record_run(&run_config, limit);
+        let note_count: usize = notes.iter().map(Vec::len).sum();
+        let silent_generations = notes.iter().filter(|keys| keys.is_empty()).count();
+
+        let wav_path = output_dir.join(batch_file_name(run, seed, "wav"));
+        recorder.finalize(&wav_path)?;
+        let midi_path = output_dir.join(batch_file_name(run, seed, "mid"));
+        midi_writer(&run_config, &notes).write(&midi_path)?;
+
+        info!("Batch run {}/{} ({:?} board, seed {}): {} notes and {} silent generations over {} -> {}, {}",
+              run, count, run_config.board_type, seed, note_count, silent_generations, limit,
+              wav_path.display(), midi_path.display());
+        outputs.push(wav_path);
+        outputs.push(midi_path);
+    }
+
+    Ok(outputs)
+}
@@ -1,12 +1,16 @@
-use std::thread;
-use std::time::Duration;
-use log::{info, debug};
+use log::{info, debug, warn, error};
 use std::path::PathBuf;
+use std::sync::Arc;
 
 // Import crate items directly
-use audio::PlayerPiano;
-use config::{Config, BoardType, GenerationLimit};
-use life::GameBoard;
+use audio::{AudioRecorder, ChordDetector, OscPlayer, PlayerPiano, key_to_note_name};
+use config::{Config, GenerationLimit};
+use conways_steinway::hooks::{StepHooks, VisualFrame};
+use conways_steinway::serve::FrameBroadcaster;
+use life::GameOfLife;
+
+// How many of the most-played notes --report lists
+const REPORT_TOP_NOTES: usize = 10;
 
 fn main() {
     // Load configuration first to get log level
@@ -18,6 +22,24 @@ fn main() {
         }
     };
 
+    // --dump-config writes a template of every setting and exits
+    if let Some(path) = &pre_config.dump_config {
+        if let Err(e) = Config::write_default_template(path) {
+            eprintln!("Error writing config template to {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+        println!("Wrote config template to {}", path.display());
+        return;
+    }
+
+    // --list-patterns prints the names --pattern accepts and exits
+    if pre_config.list_patterns {
+        for pattern in life::PATTERNS {
+            println!("{:<16} {}", pattern.name, pattern.kind);
+        }
+        return;
+    }
+
     // Initialize the multi-destination logging system
     if let Err(e) = logging::init_logging(&pre_config) {
         eprintln!("Error initializing logging system: {}", e);
@@ -31,28 +53,21 @@ fn main() {
     // Use the already loaded configuration
     let mut config = pre_config;
 
-    // Apply board-specific configuration - Für Elise gets special treatment
-    match config.board_type {
-        BoardType::FurElise => {
-            // Always use 80 generations for complete musical experience
-            if !matches!(config.generations, GenerationLimit::Limited(80)) {
-                info!("Für Elise always uses 80 generations for complete musical experience (ignoring --generations flag)");
-            }
-            config.generations = GenerationLimit::Limited(80);
-            
-            // Set appropriate musical tempo if not explicitly set
-            if config.tempo_bpm.is_none() {
-                config.tempo_bpm = Some(126.0); // Für Elise typical tempo
-                info!("Setting Für Elise tempo to 126 BPM for authentic musical timing");
-            }
-        },
-        _ => {
-            // Other board types use configured settings
-        }
+    // Without --seed every run gets a fresh seed; log it so a run worth
+    // keeping can be replayed
+    if config.seed.is_none() {
+        let seed = rand::random::<u64>();
+        info!("Using random seed {} (pass --seed {} to replay this run)", seed, seed);
+        config.seed = Some(seed);
     }
 
-    // Print current configuration
-    config.print_config();
+    // Apply board-specific configuration - Für Elise gets special treatment
+    config.apply_board_defaults();
+
+    // Print current configuration unless asked to keep stdout clean
+    if !config.quiet {
+        config.print_config();
+    }
     
     // Save effective configuration to a backup file
     // This ensures we have a record of the actual settings used
@@ -66,43 +81,115 @@ fn main() {
         }
         
         // Save the effective configuration
-        if let Err(e) = config.save_to_file(&backup_path) {
+        if let Err(e) = config.save_backup_to_file(&backup_path) {
             debug!("Unable to save effective configuration: {}", e);
         } else {
             debug!("Saved effective configuration to {}", backup_path.display());
         }
     }
 
+    // Batch mode renders several seeded runs to files and exits without audio
+    if let Some(count) = config.batch_count {
+        match conways_steinway::run_batch(&config, count, &config.batch_output_dir) {
+            Ok(outputs) => info!("Batch complete: {} runs written to {}", outputs.len(), config.batch_output_dir.display()),
+            Err(e) => {
+                error!("Batch mode failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // MIDI export writes the whole run to a file and exits without audio
+    if let Some(path) = &config.midi_out {
+        match conways_steinway::export_midi(&config, path) {
+            Ok(notes) => info!("Wrote {} notes to {}", notes, path.display()),
+            Err(e) => {
+                error!("MIDI export failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Frame export writes the board's evolution as text and exits without audio
+    if let Some(path) = &config.export_frames {
+        match conways_steinway::export_frames(&config, path) {
+            Ok(frames) => info!("Wrote {} frames to {}", frames, path.display()),
+            Err(e) => {
+                error!("Frame export failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Dry runs log the keys each generation would play, without audio or delays
+    if config.dry_run {
+        let steps = conways_steinway::dry_run(&config, |step, keys| {
+            info!("Step {}: {}", step, conways_steinway::describe_keys(keys));
+        });
+        info!("Dry run completed after {} generations", steps);
+        return;
+    }
+
     // Initialize the game board based on configuration
-    let mut game = match config.board_type {
-        BoardType::Static => {
-            info!("Using complex predefined patterns");
-            GameBoard::create_complex_board()
-        },
-        BoardType::FurElise => {
-            info!("Using Für Elise melody configuration");
-            GameBoard::create_fur_elise_board()
-        },
-        BoardType::Random => {
-            info!("Using random board configuration");
-            GameBoard::create_random_board()
-        },
-        BoardType::Complex | BoardType::Showcase => {
-            // Default to random board for these types
-            info!("Using random board for {:?} type", config.board_type);
-            GameBoard::create_random_board()
+    let mut game = conways_steinway::create_board(&config);
+
+    // An empty board with no random injection never plays a note
+    if conways_steinway::starts_silent(&game) {
+        warn!("The initial board has no live cells and random injection is disabled - nothing will ever play");
+        if !config.allow_empty {
+            error!("Refusing to start an empty board; check the board settings or pass --allow-empty");
+            std::process::exit(1);
         }
-    };
+    }
 
     // Initialize audio based on configuration
-    let piano = if config.silent {
-        PlayerPiano::new_silent()
-    } else {
-        PlayerPiano::new()
+    // Recording renders into a WAV file on a virtual clock; keep a handle so
+    // it can be written out once the run is over
+    let recorder = config.record_path.as_ref().map(|path| {
+        if matches!(config.generations, GenerationLimit::Unlimited) {
+            warn!("Recording an unlimited run to {}; it is only written once the board settles", path.display());
+        }
+        AudioRecorder::from_config(&config)
+    });
+    let piano = match (&recorder, config.osc_target) {
+        (Some(recorder), _) => PlayerPiano::with_recorder(recorder.clone()),
+        // --osc-target hands the notes to an external synthesizer
+        (None, Some(target)) => match OscPlayer::connect(target) {
+            Ok(player) => {
+                info!("Sending notes as OSC messages to {}", target);
+                let chord_detector = ChordDetector::new(config.chord_cluster_size, config.chord_cluster_gap);
+                PlayerPiano::with_osc(player).with_chord_detector(chord_detector)
+            }
+            Err(e) => {
+                error!("Unable to send OSC messages to {}: {}", target, e);
+                std::process::exit(1);
+            }
+        },
+        (None, None) => PlayerPiano::from_config(&config),
     };
 
+    // --serve streams every generation to web front ends over Server-Sent Events
+    let broadcaster = config.serve.map(|addr| {
+        let broadcaster = Arc::new(FrameBroadcaster::new());
+        if let Err(e) = conways_steinway::serve::serve(addr, Arc::clone(&broadcaster)) {
+            error!("Unable to serve generations at {}: {}", addr, e);
+            std::process::exit(1);
+        }
+        broadcaster
+    });
+
+    // Everything recorded about each generation: the end-of-run report,
+    // --events-out, --serve, and the checks that end a run early
+    let mut hooks = StepHooks::new(&config, &game, broadcaster);
+
+    // The terminal UI plays the performance itself, redrawing the board in place
+    let tui_steps = run_tui(&config, &mut game, &piano, &mut hooks);
+
     // Run the simulation based on generation limit
-    let mut step = 0;
+    let mut step = tui_steps.unwrap_or(0);
     let should_continue = |current_step: u32| -> bool {
         match config.generations {
             GenerationLimit::Limited(max_generations) => current_step < max_generations,
@@ -110,7 +197,7 @@ fn main() {
         }
     };
 
-    while should_continue(step) {
+    while tui_steps.is_none() && should_continue(step) {
         step += 1;
         
         match config.generations {
@@ -118,20 +205,86 @@ fn main() {
             GenerationLimit::Unlimited => info!("\nStep {} (unlimited)", step),
         }
         
-        let piano_keys = GameBoard::get_bottom_row_and_advance(&mut game);
-        piano.play_keys(&piano_keys);
+        hooks.play_step(&config, &mut game, &piano, step);
         
-        // Use configured delay between steps (respects tempo if set)
-        thread::sleep(Duration::from_millis(config.get_effective_delay()));
-        
-        info!("\n{}", game);
+        // Use configured delay between steps (respects tempo and tempo ramps)
+        hooks.wait(&config, &piano, step, |visual| print_board(&config, visual));
+
+        if !hooks.should_continue(&config, &game) {
+            break;
+        }
 
         // For unlimited generations, allow graceful interruption
-        if matches!(config.generations, GenerationLimit::Unlimited) && step % 100 == 0 {
+        if matches!(config.generations, GenerationLimit::Unlimited) && step.is_multiple_of(100) {
             info!("(Press Ctrl+C to stop after {} steps)", step);
         }
     }
+    if tui_steps.is_none() {
+        hooks.finish(&piano, |visual| print_board(&config, visual));
+    }
     
     info!("\nSimulation completed after {} generations", step);
     info!("Final generation: {}", game.generation());
+
+    if config.report {
+        let top = hooks.activity().top(REPORT_TOP_NOTES);
+        if top.is_empty() {
+            println!("No notes were played");
+        } else {
+            println!("Most-played notes:");
+            for (key, count) in top {
+                println!("  {:<4} (key {:>2}): {} times", key_to_note_name(key), key, count);
+            }
+        }
+    }
+
+    if let (Some(events), Some(path)) = (hooks.events(), &config.events_out) {
+        match events.write(path) {
+            Ok(()) => info!("Wrote {} generation events to {}", events.events().len(), path.display()),
+            Err(e) => error!("Unable to write events to {}: {}", path.display(), e),
+        }
+    }
+
+    if let (Some(recorder), Some(path)) = (&recorder, &config.record_path) {
+        if let Err(e) = recorder.finalize(path) {
+            error!("Unable to write recording to {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+        // A waveform overview, so loud and quiet stretches show without an audio editor
+        match audio::preview::write_preview(path, audio::preview::PREVIEW_BUCKETS) {
+            Ok(sparkline) => info!("Preview: {} (peaks in {})", sparkline, audio::preview::peaks_path(path).display()),
+            Err(e) => warn!("Unable to write a preview of {}: {}", path.display(), e),
+        }
+    }
+}
+
+// Log the board of a generation as it comes up on the visual timeline
+fn print_board(config: &Config, visual: &VisualFrame) {
+    if config.should_print_board(visual.step) {
+        info!("\n{}", visual.game);
+    }
+}
+
+// Play the run in the terminal UI when --tui is set, returning the number of
+// generations it played
+#[cfg(feature = "tui")]
+fn run_tui(config: &Config, game: &mut GameOfLife, piano: &PlayerPiano, hooks: &mut StepHooks) -> Option<u32> {
+    if !config.tui {
+        return None;
+    }
+    match conways_steinway::tui::run(config, game, piano, hooks) {
+        Ok(steps) => Some(steps),
+        Err(e) => {
+            error!("Terminal UI failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+fn run_tui(config: &Config, _game: &mut GameOfLife, _piano: &PlayerPiano, _hooks: &mut StepHooks) -> Option<u32> {
+    if config.tui {
+        warn!("This build has no terminal UI (rebuild with --features tui); printing the board instead");
+    }
+    None
 }
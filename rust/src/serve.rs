@@ -0,0 +1,131 @@
+// Server-Sent Events stream of a performance for web front ends (--serve)
+// Speaks just enough HTTP over std::net for an EventSource: GET /stream holds
+// the connection open and receives one `data: {json}` event per generation,
+// each a serialized FrameState. The simulation loop publishes frames to a
+// FrameBroadcaster; every client has its own thread and bounded queue, so a
+// slow or vanished browser never holds up the music.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use log::{debug, info, warn};
+
+use crate::FrameState;
+
+// Frames a client may fall behind by before it starts missing them
+pub const CLIENT_QUEUE_CAPACITY: usize = 64;
+
+pub const STREAM_PATH: &str = "/stream";
+
+// Fans each published frame out to every connected client
+#[derive(Default)]
+pub struct FrameBroadcaster {
+    subscribers: Mutex<Vec<SyncSender<Arc<str>>>>,
+}
+
+impl FrameBroadcaster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self) -> Receiver<Arc<str>> {
+        let (sender, receiver) = sync_channel(CLIENT_QUEUE_CAPACITY);
+        self.lock().push(sender);
+        receiver
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.lock().len()
+    }
+
+    // Send a frame to every client as JSON. Clients that have disconnected are
+    // dropped; ones whose queue is full skip this frame. Returns how many
+    // clients the frame was queued for.
+    pub fn publish(&self, frame: &FrameState) -> usize {
+        let json: Arc<str> = match serde_json::to_string(frame) {
+            Ok(json) => json.into(),
+            Err(e) => {
+                warn!("Unable to serialize generation {}: {}", frame.generation, e);
+                return 0;
+            }
+        };
+
+        let mut delivered = 0;
+        self.lock().retain(|subscriber| match subscriber.try_send(Arc::clone(&json)) {
+            Ok(()) => {
+                delivered += 1;
+                true
+            }
+            Err(TrySendError::Full(_)) => {
+                debug!("Stream client is behind; skipping generation {}", frame.generation);
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+        delivered
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<SyncSender<Arc<str>>>> {
+        self.subscribers.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// Start serving /stream on `addr` from a background thread and return the
+// bound address (useful with port 0)
+pub fn serve(addr: impl ToSocketAddrs, broadcaster: Arc<FrameBroadcaster>) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    info!("Streaming generations at http://{}{}", local_addr, STREAM_PATH);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let broadcaster = Arc::clone(&broadcaster);
+                    thread::spawn(move || {
+                        if let Err(e) = handle_client(stream, &broadcaster) {
+                            debug!("Stream client disconnected: {}", e);
+                        }
+                    });
+                }
+                Err(e) => warn!("Unable to accept stream connection: {}", e),
+            }
+        }
+    });
+
+    Ok(local_addr)
+}
+
+fn handle_client(mut stream: TcpStream, broadcaster: &FrameBroadcaster) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Skip the headers; nothing in them changes the response
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    if method != "GET" || path.split('?').next() != Some(STREAM_PATH) {
+        return stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    }
+
+    stream.write_all(b"HTTP/1.1 200 OK\r\n\
+                       Content-Type: text/event-stream\r\n\
+                       Cache-Control: no-cache\r\n\
+                       Connection: keep-alive\r\n\
+                       Access-Control-Allow-Origin: *\r\n\r\n")?;
+    stream.flush()?;
+
+    for frame in broadcaster.subscribe() {
+        write!(stream, "data: {}\n\n", frame)?;
+        stream.flush()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,176 @@
+// Terminal UI for Conway's Steinway
+// Animates the board in place instead of scrolling Display dumps. Turning a
+// board into styled cells is plain data, so it can be tested without a
+// terminal; drawing it with ratatui needs the `tui` feature.
+
+use life::{Cell, GameOfLife, BOARD_WIDTH};
+
+use crate::describe_keys;
+
+// How a cell is drawn; the terminal front-end picks the colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellStyle {
+    Dead,
+    Alive,
+    Dying,  // "Generations" mode cell that is decaying
+    Key,    // Keyboard row: a key that didn't play this step
+    Played, // Keyboard row: a key that just played
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StyledCell {
+    pub symbol: char,
+    pub style: CellStyle,
+}
+
+impl StyledCell {
+    fn for_cell(cell: Cell) -> Self {
+        match cell {
+            Cell::Dead => StyledCell { symbol: ' ', style: CellStyle::Dead },
+            Cell::Alive => StyledCell { symbol: '█', style: CellStyle::Alive },
+            Cell::Dying(_) => StyledCell { symbol: '▒', style: CellStyle::Dying },
+        }
+    }
+
+    fn for_key(played: bool) -> Self {
+        if played {
+            StyledCell { symbol: '▀', style: CellStyle::Played }
+        } else {
+            StyledCell { symbol: '─', style: CellStyle::Key }
+        }
+    }
+}
+
+// The board as rows of styled cells, followed by a keyboard row under it that
+// marks the keys the last step played
+pub fn render_cells(game: &GameOfLife, played_keys: &[usize]) -> Vec<Vec<StyledCell>> {
+    let mut rows: Vec<Vec<StyledCell>> = (0..game.height())
+        .map(|row| (0..BOARD_WIDTH).map(|col| StyledCell::for_cell(game.get_cell(row, col))).collect())
+        .collect();
+    rows.push((0..BOARD_WIDTH).map(|key| StyledCell::for_key(played_keys.contains(&key))).collect());
+    rows
+}
+
+// One-line summary shown above the board
+pub fn status_line(game: &GameOfLife, played_keys: &[usize]) -> String {
+    format!("Generation {}  Population {}  Keys: {}",
+            game.generation(), game.population(), describe_keys(played_keys))
+}
+
+#[cfg(feature = "tui")]
+pub use terminal::run;
+
+#[cfg(feature = "tui")]
+mod terminal {
+    use std::io;
+    use std::time::Duration;
+
+    use audio::PlayerPiano;
+    use config::{Config, GenerationLimit};
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+    use logging::filters::mute_console;
+    use life::GameOfLife;
+    use ratatui::layout::{Constraint, Layout};
+    use ratatui::style::{Color, Style};
+    use ratatui::text::{Line, Span};
+    use ratatui::widgets::{Block, Paragraph};
+    use ratatui::{DefaultTerminal, Frame};
+
+    use super::{render_cells, status_line, CellStyle};
+    use crate::hooks::{StepHooks, VisualFrame};
+
+    // Play the performance in the terminal, redrawing the board after every
+    // step. Stops at the generation limit, when `hooks` end the run or when q
+    // (or Esc) is pressed, and always restores the terminal. Console logging
+    // is muted meanwhile so it can't draw over the board. Returns the number
+    // of generations played.
+    pub fn run(config: &Config, game: &mut GameOfLife, piano: &PlayerPiano,
+               hooks: &mut StepHooks) -> io::Result<u32> {
+        let mut terminal = ratatui::init();
+        mute_console(true);
+        let result = run_loop(&mut terminal, config, game, piano, hooks);
+        mute_console(false);
+        ratatui::restore();
+        result
+    }
+
+    fn run_loop(terminal: &mut DefaultTerminal, config: &Config, game: &mut GameOfLife,
+                piano: &PlayerPiano, hooks: &mut StepHooks) -> io::Result<u32> {
+        let mut step = 0;
+        terminal.draw(|frame| draw(frame, game, &[]))?;
+
+        loop {
+            if let GenerationLimit::Limited(max) = config.generations {
+                if step >= max {
+                    break;
+                }
+            }
+            step += 1;
+
+            hooks.play_step(config, game, piano, step);
+
+            // The piano keeps time, so a recording runs on its own clock. The
+            // board is redrawn as each generation comes up on the visual
+            // timeline, trailing the notes by --av-sync-ms.
+            let mut drawn = Ok(());
+            hooks.wait(config, piano, step, |visual| redraw(terminal, visual, &mut drawn));
+            drawn?;
+
+            if quit_requested()? {
+                return Ok(step);
+            }
+            if !hooks.should_continue(config, game) {
+                break;
+            }
+        }
+
+        let mut drawn = Ok(());
+        hooks.finish(piano, |visual| redraw(terminal, visual, &mut drawn));
+        drawn.map(|_| step)
+    }
+
+    // Draw a queued frame, keeping the first error for the caller
+    fn redraw(terminal: &mut DefaultTerminal, visual: &VisualFrame, drawn: &mut io::Result<()>) {
+        if drawn.is_ok() {
+            *drawn = terminal.draw(|frame| draw(frame, &visual.game, &visual.played_keys)).map(|_| ());
+        }
+    }
+
+    // Whether q or Esc was pressed since the last check, without blocking
+    fn quit_requested() -> io::Result<bool> {
+        let mut quit = false;
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(key) = event::read()? {
+                quit |= key.kind == KeyEventKind::Press && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc);
+            }
+        }
+        Ok(quit)
+    }
+
+    fn draw(frame: &mut Frame, game: &GameOfLife, played_keys: &[usize]) {
+        let [status_area, board_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)])
+            .areas(frame.area());
+
+        let lines: Vec<Line> = render_cells(game, played_keys).into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|cell| Span::styled(cell.symbol.to_string(), style_for(cell.style)))
+                    .collect::<Line>()
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(status_line(game, played_keys)), status_area);
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Conway's Steinway - q to quit ")),
+                            board_area);
+    }
+
+    fn style_for(style: CellStyle) -> Style {
+        match style {
+            CellStyle::Dead => Style::default(),
+            CellStyle::Alive => Style::default().fg(Color::Green),
+            CellStyle::Dying => Style::default().fg(Color::DarkGray),
+            CellStyle::Key => Style::default().fg(Color::Gray),
+            CellStyle::Played => Style::default().fg(Color::Yellow),
+        }
+    }
+}
@@ -8,11 +8,13 @@ use audio::PlayerPiano;
 #[test]
 fn test_end_to_end_flow() {
     // Create a minimal config for testing
-    let mut config = Config::default();
-    config.board_type = BoardType::Random;
-    config.silent = true; // Silent mode for testing
-    config.generations = GenerationLimit::Limited(5); // Just a few generations
-    config.step_delay_ms = 0; // No delay for tests
+    let config = Config {
+        board_type: BoardType::Random,
+        silent: true, // Silent mode for testing
+        generations: GenerationLimit::Limited(5), // Just a few generations
+        step_delay_ms: 0, // No delay for tests
+        ..Config::default()
+    };
     
     // Print the config in debug mode
     println!("Test configuration: {:?}", config);
@@ -21,7 +23,7 @@ fn test_end_to_end_flow() {
     let mut game = match config.board_type {
         BoardType::Static => GameBoard::create_complex_board(),
         BoardType::FurElise => GameBoard::create_fur_elise_board(),
-        BoardType::Random | _ => GameBoard::create_random_board(),
+        _ => GameBoard::create_random_board(config.alive_probability),
     };
     
     // Create a silent piano
@@ -71,16 +73,18 @@ fn test_config_loading_and_application() {
     // Test that configuration values are correctly applied to the game
     
     // Create a custom config
-    let mut config = Config::default();
-    config.board_type = BoardType::Static;
-    config.silent = true;
-    config.generations = GenerationLimit::Limited(1);
+    let config = Config {
+        board_type: BoardType::Static,
+        silent: true,
+        generations: GenerationLimit::Limited(1),
+        ..Config::default()
+    };
     
     // Initialize based on this config
     let board = match config.board_type {
         BoardType::Static => GameBoard::create_complex_board(),
         BoardType::FurElise => GameBoard::create_fur_elise_board(),
-        BoardType::Random | _ => GameBoard::create_random_board(),
+        _ => GameBoard::create_random_board(config.alive_probability),
     };
     
     // Board should have been created using the complex pattern method
@@ -0,0 +1,64 @@
+// Integration tests for batch mode
+// Renders several seeded runs headlessly and checks the files produced
+
+use std::fs;
+
+use config::{BoardType, Config, GenerationLimit};
+use conways_steinway::{batch_file_name, run_batch};
+use hound::WavReader;
+use tempfile::tempdir;
+
+#[test]
+fn test_batch_mode_writes_one_recording_per_seed() {
+    let dir = tempdir().unwrap();
+    let config = Config {
+        board_type: BoardType::Random,
+        generations: GenerationLimit::Limited(3),
+        step_delay_ms: 100,
+        seed: Some(40),
+        // Retuning every key is slow in debug builds and not what's under test
+        pitch_shift: false,
+        ..Config::default()
+    };
+
+    let outputs = run_batch(&config, 3, dir.path()).unwrap();
+
+    let expected: Vec<_> = [(1, 40), (2, 41), (3, 42)].iter()
+        .flat_map(|&(run, seed)| ["wav", "mid"].map(|ext| dir.path().join(batch_file_name(run, seed, ext))))
+        .collect();
+    assert_eq!(outputs, expected);
+    assert_eq!(batch_file_name(1, 40, "wav"), "run_001_seed_40.wav");
+
+    for path in outputs.iter().filter(|path| path.extension().unwrap() == "wav") {
+        // Stereo 16-bit audio lasting at least the three 100 ms steps
+        let reader = WavReader::open(path).unwrap();
+        let spec = reader.spec();
+        assert_eq!((spec.channels, spec.bits_per_sample), (2, 16));
+        assert!(reader.duration() as u64 * 1000 >= 300 * spec.sample_rate as u64);
+    }
+
+    for path in outputs.iter().filter(|path| path.extension().unwrap() == "mid") {
+        // A format 0 Standard MIDI File whose single track fills the rest of the file
+        let bytes = fs::read(path).unwrap();
+        assert_eq!(&bytes[0..4], b"MThd");
+        assert_eq!(u16::from_be_bytes([bytes[8], bytes[9]]), 0);
+        assert_eq!(&bytes[14..18], b"MTrk");
+        let length = u32::from_be_bytes(bytes[18..22].try_into().unwrap()) as usize;
+        assert_eq!(bytes.len(), 22 + length);
+        assert!(bytes.ends_with(&[0xFF, 0x2F, 0x00]));
+    }
+
+    // Same seeds, same music
+    let again = tempdir().unwrap();
+    let repeated = run_batch(&config, 3, again.path()).unwrap();
+    for (first, second) in outputs.iter().zip(&repeated) {
+        assert_eq!(fs::read(first).unwrap(), fs::read(second).unwrap());
+    }
+}
+
+#[test]
+fn test_batch_mode_needs_generation_limit() {
+    let dir = tempdir().unwrap();
+    let config = Config::default();
+    assert!(run_batch(&config, 2, dir.path()).is_err());
+}
@@ -0,0 +1,30 @@
+// Integration tests for the empty-board start guard
+
+use conways_steinway::starts_silent;
+use life::{Cell, GameOfLife};
+
+#[test]
+fn test_empty_board_without_injection_starts_silent() {
+    let mut game = GameOfLife::new();
+    game.set_random_injection(false);
+
+    assert_eq!(game.population(), 0);
+    assert!(starts_silent(&game));
+}
+
+#[test]
+fn test_guard_considers_seeding_and_injection() {
+    let mut seeded = GameOfLife::new();
+    seeded.set_random_injection(false);
+    seeded.set_cell(10, 20, Cell::Alive);
+    assert!(!starts_silent(&seeded));
+
+    // Random rows will eventually arrive, so an empty board is fine
+    let injected = GameOfLife::new();
+    assert!(!starts_silent(&injected));
+
+    // Injection that stops before the first generation is as good as none
+    let mut expired = GameOfLife::new();
+    expired.set_random_injection_generations(Some(0));
+    assert!(starts_silent(&expired));
+}
@@ -0,0 +1,48 @@
+// Integration tests for the per-generation JSON event log
+
+use audio::ChordDetector;
+use config::{Config, GenerationLimit};
+use conways_steinway::{simulate_notes, EventLog};
+
+#[test]
+fn test_short_run_writes_one_event_per_generation() {
+    let config = Config {
+        generations: GenerationLimit::Limited(5),
+        seed: Some(7),
+        ..Config::default()
+    };
+    let generations = simulate_notes(&config, 5);
+
+    let mut log = EventLog::from_config(&config);
+    for (step, keys) in generations.iter().enumerate() {
+        log.record(step as u32 + 1, keys);
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.json");
+    log.write(&path).unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 5);
+
+    for (step, (entry, keys)) in entries.iter().zip(&generations).enumerate() {
+        assert_eq!(entry["generation"], step as u64 + 1);
+        let logged: Vec<usize> = entry["keys"].as_array().unwrap()
+            .iter()
+            .map(|key| key.as_u64().unwrap() as usize)
+            .collect();
+        assert_eq!(&logged, keys);
+    }
+}
+
+#[test]
+fn test_events_include_chord_quality() {
+    let mut log = EventLog::new(ChordDetector::default());
+    log.record(1, &[27, 31, 34]);
+    log.record(2, &[40]);
+
+    let json: serde_json::Value = serde_json::from_str(&log.to_json().unwrap()).unwrap();
+    assert_eq!(json[0]["chord"], "major");
+    assert!(json[1]["chord"].is_null());
+}
@@ -0,0 +1,79 @@
+// Integration tests for exporting a run as a text animation
+
+use config::{Config, GenerationLimit};
+use conways_steinway::{advance, create_board, export_frames, render_frames, FRAME_SEPARATOR};
+use life::{Cell, GameOfLife, BOARD_HEIGHT};
+
+fn blinker() -> GameOfLife {
+    let mut game = GameOfLife::new();
+    for col in 10..13 {
+        game.set_cell(10, col, Cell::Alive);
+    }
+    game
+}
+
+#[test]
+fn test_blinker_frames_alternate() {
+    let mut game = blinker();
+    let frames = render_frames(&mut game, 5);
+    assert_eq!(frames.len(), 5);
+
+    // Header lines, the rows and the two borders
+    for frame in &frames {
+        assert_eq!(frame.lines().count(), BOARD_HEIGHT + 4);
+    }
+
+    // Generation numbers differ, so compare the boards below the headers
+    let board = |frame: &String| frame.lines().skip(2).collect::<Vec<_>>().join("\n");
+    assert_ne!(board(&frames[0]), board(&frames[1]));
+    assert_eq!(board(&frames[0]), board(&frames[2]));
+    assert_eq!(board(&frames[1]), board(&frames[3]));
+    assert_eq!(board(&frames[0]), board(&frames[4]));
+
+    // The first frame is the board as it was handed in
+    assert_eq!(frames[0], blinker().to_string());
+    assert_eq!(game.generation(), 4);
+}
+
+#[test]
+fn test_export_frames_writes_separated_frames() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("run.txt");
+    let config = Config {
+        generations: GenerationLimit::Limited(3),
+        ..Config::default()
+    };
+
+    assert_eq!(export_frames(&config, &path).unwrap(), 3);
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(text.split(FRAME_SEPARATOR).count(), 3);
+
+    let unlimited = Config { generations: GenerationLimit::Unlimited, ..Config::default() };
+    assert!(export_frames(&unlimited, &path).is_err());
+}
+
+#[test]
+fn test_exported_frames_follow_the_played_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("run.txt");
+    let config = Config {
+        generations: GenerationLimit::Limited(4),
+        seed: Some(11),
+        ..Config::default()
+    };
+    export_frames(&config, &path).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    let frames: Vec<&str> = text.split(FRAME_SEPARATOR).collect();
+
+    // Each frame is the board a real run shows, scrolled and with its injected row
+    let mut game = create_board(&config);
+    for frame in frames {
+        assert_eq!(frame, game.to_string());
+        advance(&mut game, &config);
+    }
+
+    // Plain Life steps would not have scrolled the board
+    let mut still = create_board(&config);
+    let unscrolled = render_frames(&mut still, 2);
+    assert_ne!(text.split(FRAME_SEPARATOR).nth(1).unwrap(), unscrolled[1]);
+}
@@ -0,0 +1,43 @@
+// Integration tests for the headless frame-state API
+
+use config::{BoardType, Config, GenerationLimit};
+use conways_steinway::{simulate_notes, FrameState, Simulation};
+use life::{Cell, GameOfLife};
+
+#[test]
+fn test_frame_state_for_known_board() {
+    let mut game = GameOfLife::new();
+    game.set_cell(0, 1, Cell::Alive);
+    game.set_cell(2, 3, Cell::Alive);
+    game.set_cell(39, 87, Cell::Alive);
+
+    let frame = FrameState::capture(&game, vec![4, 9]);
+
+    assert_eq!(frame.generation, 0);
+    assert_eq!(frame.live_cells, vec![(0, 1), (2, 3), (39, 87)]);
+    assert_eq!(frame.population, 3);
+    assert_eq!(frame.played_keys, vec![4, 9]);
+}
+
+#[test]
+fn test_simulation_yields_one_frame_per_generation() {
+    let config = Config {
+        board_type: BoardType::Static,
+        generations: GenerationLimit::Limited(5),
+        ..Config::default()
+    };
+
+    let frames: Vec<FrameState> = Simulation::new(&config).collect();
+    assert_eq!(frames.len(), 5, "The iterator stops at the generation limit");
+
+    let generations: Vec<u32> = frames.iter().map(|f| f.generation).collect();
+    assert_eq!(generations, vec![1, 2, 3, 4, 5]);
+
+    // Frames play the same notes as the notes-only driver
+    let played: Vec<Vec<usize>> = frames.iter().map(|f| f.played_keys.clone()).collect();
+    assert_eq!(played, simulate_notes(&config, 5));
+
+    for frame in &frames {
+        assert_eq!(frame.population, frame.live_cells.len());
+    }
+}
@@ -0,0 +1,98 @@
+// Integration tests for exporting a run as a Standard MIDI File
+
+use audio::midi::MIDI_NOTE_OFFSET;
+use audio::timeline::build_timeline;
+use config::{BoardType, Config, GenerationLimit};
+use conways_steinway::{export_midi, simulate_notes};
+use tempfile::tempdir;
+
+// (start, midi note, length) of every note in a format 0 file, in milliseconds
+// (the exporter writes one tick per millisecond)
+fn decode_notes(bytes: &[u8]) -> Vec<(u64, u8, u64)> {
+    assert_eq!(&bytes[0..4], b"MThd");
+    assert_eq!(&bytes[14..18], b"MTrk");
+    let track = &bytes[22..];
+
+    let mut sounding = std::collections::HashMap::new();
+    let mut notes = Vec::new();
+    let (mut pos, mut tick) = (0, 0u64);
+    loop {
+        let mut delta = 0u64;
+        loop {
+            let byte = track[pos];
+            pos += 1;
+            delta = (delta << 7) | (byte & 0x7F) as u64;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        tick += delta;
+        match track[pos] {
+            0xFF => {
+                let kind = track[pos + 1];
+                pos += 3 + track[pos + 2] as usize;
+                if kind == 0x2F {
+                    assert!(sounding.is_empty(), "every note must be released");
+                    notes.sort();
+                    return notes;
+                }
+            }
+            0x90 => {
+                assert!(sounding.insert(track[pos + 1], tick).is_none(), "note struck twice");
+                pos += 3;
+            }
+            0x80 => {
+                let start = sounding.remove(&track[pos + 1]).expect("note-off without note-on");
+                notes.push((start, track[pos + 1], tick - start));
+                pos += 3;
+            }
+            status => panic!("unexpected status {:#x}", status),
+        }
+    }
+}
+
+#[test]
+fn test_exported_notes_use_configured_durations() {
+    let config = Config {
+        board_type: BoardType::Static,
+        generations: GenerationLimit::Limited(40),
+        step_delay_ms: 1000,
+        note_duration_ms: 120,
+        chord_duration_ms: 450,
+        gap_ms: 5,
+        seed: Some(11),
+        ..Config::default()
+    };
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("run.mid");
+    let note_count = export_midi(&config, &path).unwrap();
+    let notes = decode_notes(&std::fs::read(&path).unwrap());
+    assert_eq!(notes.len(), note_count);
+
+    // The file holds exactly the timeline: staggered single notes and held chords
+    let generations = simulate_notes(&config, 40);
+    let mut expected: Vec<(u64, u8, u64)> = build_timeline(&generations, &config)
+        .iter()
+        .map(|note| (note.start_ms, MIDI_NOTE_OFFSET + note.key as u8, note.duration_ms))
+        .collect();
+    expected.sort();
+    assert_eq!(notes, expected);
+
+    assert!(notes.iter().any(|&(_, _, length)| length == 120), "no single notes were exported");
+    assert!(notes.iter().all(|&(_, _, length)| length == 120 || length == 450));
+}
+
+#[test]
+fn test_chords_are_held_for_chord_duration() {
+    let config = Config {
+        step_delay_ms: 1000,
+        note_duration_ms: 120,
+        chord_duration_ms: 450,
+        ..Config::default()
+    };
+
+    // A C major triad is detected as a chord and held together
+    let timeline = build_timeline(&[vec![39, 43, 46]], &config);
+    assert!(timeline.iter().all(|note| note.start_ms == 0 && note.duration_ms == 450));
+}
@@ -0,0 +1,85 @@
+// Integration tests for the single-pattern board types
+
+use config::{BoardType, Config};
+use conways_steinway::create_board;
+use life::{Cell, GameOfLife, BOARD_HEIGHT, BOARD_WIDTH, GLIDER_CELLS};
+
+fn board_for(board_type: &str) -> GameOfLife {
+    let config = Config::from_args(["conways-steinway", "--board-type", board_type]).unwrap();
+    create_board(&config)
+}
+
+#[test]
+fn test_gun_board_has_its_blocks() {
+    let game = board_for("gosper_gun");
+
+    // The gun's 36x9 bounding box is centered
+    let row = (BOARD_HEIGHT - 9) / 2;
+    let col = (BOARD_WIDTH - 36) / 2;
+    for (block_row, block_col) in [(row + 6, col), (row + 4, col + 34)] {
+        for (r, c) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            assert_eq!(game.get_cell(block_row + r, block_col + c), Cell::Alive,
+                       "block cell ({}, {})", block_row + r, block_col + c);
+        }
+    }
+    assert_eq!(game.population(), 36);
+}
+
+#[test]
+fn test_methuselah_boards_hold_one_pattern() {
+    for (name, population) in [("acorn", 7), ("diehard", 7), ("r_pentomino", 5)] {
+        assert_eq!(board_for(name).population(), population, "{}", name);
+    }
+
+    // A pattern given as the board type is the same board as --pattern
+    let config = Config::from_args(["conways-steinway", "--board-type", "R_Pentomino"]).unwrap();
+    assert!(matches!(config.board_type, BoardType::Named(ref name) if name == "r_pentomino"));
+    let pattern = Config::from_args(["conways-steinway", "--pattern", "r_pentomino"]).unwrap();
+    assert_eq!(create_board(&config).population(), create_board(&pattern).population());
+
+    // Any registry pattern works, and nothing else does
+    assert_eq!(board_for("pulsar").population(), 48);
+    assert!(Config::from_args(["conways-steinway", "--board-type", "rpentomino"]).is_err());
+}
+
+#[test]
+fn test_boards_use_the_configured_height() {
+    let tall = |board_type: &str| {
+        let config = Config::from_args(["conways-steinway", "--board-type", board_type, "--height", "60", "--seed", "3"])
+            .unwrap();
+        create_board(&config)
+    };
+
+    // Patterns keep their distance from the bottom row
+    let acorn = tall("acorn");
+    assert_eq!(acorn.height(), 60);
+    let rows_from_bottom = |game: &GameOfLife| {
+        (0..game.height()).rev().find(|&row| (0..BOARD_WIDTH).any(|col| game.get_cell(row, col) == Cell::Alive))
+            .map(|row| game.height() - row)
+    };
+    assert_eq!(rows_from_bottom(&acorn), rows_from_bottom(&board_for("acorn")));
+
+    // Random boards fill every row
+    let random = tall("random");
+    assert_eq!(random.height(), 60);
+    assert!((0..BOARD_WIDTH).any(|col| random.get_cell(0, col) == Cell::Alive));
+    assert!(random.population() > BOARD_WIDTH * BOARD_HEIGHT / 10);
+}
+
+#[test]
+fn test_named_pattern_seeds_a_glider() {
+    let config = Config::from_args(["conways-steinway", "--pattern", "glider"]).unwrap();
+    assert!(matches!(config.board_type, BoardType::Named(ref name) if name == "glider"));
+
+    // The glider's 3x3 bounding box is centered on the board
+    let game = create_board(&config);
+    let row = (BOARD_HEIGHT - 3) / 2;
+    let col = (BOARD_WIDTH - 3) / 2;
+    assert_eq!(game.population(), 5);
+    for &(r, c) in GLIDER_CELLS {
+        assert_eq!(game.get_cell(row + r, col + c), Cell::Alive, "glider cell ({}, {})", r, c);
+    }
+
+    let errors = Config::from_args(["conways-steinway", "--pattern", "spaceship"]).unwrap_err();
+    assert!(errors.to_string().contains("Unknown pattern 'spaceship'"), "{}", errors);
+}
@@ -0,0 +1,89 @@
+// Integration tests for the Server-Sent Events stream of generations
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use config::{Config, GenerationLimit};
+use conways_steinway::serve::{serve, FrameBroadcaster};
+use conways_steinway::{FrameState, Simulation};
+
+fn request(addr: std::net::SocketAddr, path: &str) -> TcpStream {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\nAccept: text/event-stream\r\n\r\n", path).unwrap();
+    stream
+}
+
+#[test]
+fn test_stream_sends_json_frames() {
+    let broadcaster = Arc::new(FrameBroadcaster::new());
+    let addr = serve("127.0.0.1:0", Arc::clone(&broadcaster)).unwrap();
+    let mut reader = BufReader::new(request(addr, "/stream"));
+
+    // Wait for the client to be registered before the simulation starts publishing
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while broadcaster.subscriber_count() == 0 {
+        assert!(Instant::now() < deadline, "client never subscribed");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let config = Config {
+        generations: GenerationLimit::Limited(3),
+        seed: Some(5),
+        ..Config::default()
+    };
+    let publisher = Arc::clone(&broadcaster);
+    let expected: Vec<FrameState> = Simulation::new(&config).collect();
+    let frames = expected.clone();
+    thread::spawn(move || {
+        for frame in &frames {
+            publisher.publish(frame);
+        }
+    });
+
+    let mut status = String::new();
+    reader.read_line(&mut status).unwrap();
+    assert!(status.starts_with("HTTP/1.1 200"), "{}", status);
+
+    let mut received = Vec::new();
+    let mut line = String::new();
+    while received.len() < expected.len() {
+        line.clear();
+        assert!(reader.read_line(&mut line).unwrap() > 0, "stream closed early");
+        if line.to_lowercase().starts_with("content-type:") {
+            assert_eq!(line.trim_end(), "Content-Type: text/event-stream");
+        }
+        if let Some(json) = line.strip_prefix("data: ") {
+            received.push(serde_json::from_str::<FrameState>(json.trim_end()).unwrap());
+        }
+    }
+    assert_eq!(received, expected);
+}
+
+#[test]
+fn test_other_paths_are_not_found() {
+    let broadcaster = Arc::new(FrameBroadcaster::new());
+    let addr = serve("127.0.0.1:0", Arc::clone(&broadcaster)).unwrap();
+
+    let mut response = String::new();
+    request(addr, "/").read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 404"), "{}", response);
+    assert_eq!(broadcaster.subscriber_count(), 0);
+}
+
+#[test]
+fn test_disconnected_clients_are_dropped() {
+    let broadcaster = FrameBroadcaster::new();
+    let frame = Simulation::new(&Config::default()).next().unwrap();
+
+    let receiver = broadcaster.subscribe();
+    assert_eq!(broadcaster.publish(&frame), 1);
+    assert!(receiver.recv().unwrap().contains("\"generation\""));
+
+    drop(receiver);
+    assert_eq!(broadcaster.publish(&frame), 0);
+    assert_eq!(broadcaster.subscriber_count(), 0);
+}
@@ -0,0 +1,101 @@
+// Integration tests for the notes-only simulation driver
+// Runs whole performances without audio, timing or logging
+
+use audio::note_name_to_key;
+use config::{BoardType, Config, GenerationLimit, Scale, VelocityCurve};
+use conways_steinway::{describe_keys, dry_run, shape_velocity, simulate_notes};
+use life::{MAX_VELOCITY, MIN_VELOCITY};
+
+#[test]
+fn test_simulate_notes_is_stable() {
+    // A seeded random board and its injected rows replay exactly
+    let config = Config {
+        board_type: BoardType::Random,
+        seed: Some(12345),
+        ..Config::default()
+    };
+
+    let notes = simulate_notes(&config, 6);
+
+    assert_eq!(notes.len(), 6, "One entry per generation");
+    assert!(notes.iter().any(|keys| !keys.is_empty()));
+
+    // Running again gives exactly the same performance
+    assert_eq!(simulate_notes(&config, 6), notes);
+}
+
+#[test]
+fn test_simulate_notes_respects_limit() {
+    let config = Config {
+        board_type: BoardType::FurElise,
+        ..Config::default()
+    };
+
+    assert!(simulate_notes(&config, 0).is_empty());
+    assert_eq!(simulate_notes(&config, 12).len(), 12);
+}
+
+#[test]
+fn test_velocity_curves_keep_their_range() {
+    for curve in [VelocityCurve::Linear, VelocityCurve::Exponential, VelocityCurve::Logarithmic] {
+        assert_eq!(shape_velocity(MIN_VELOCITY, curve), MIN_VELOCITY);
+        assert_eq!(shape_velocity(MAX_VELOCITY, curve), MAX_VELOCITY);
+    }
+
+    // Halfway up the range, exponential stays quieter and logarithmic louder
+    let middle = MIN_VELOCITY + (MAX_VELOCITY - MIN_VELOCITY) / 2;
+    assert_eq!(shape_velocity(middle, VelocityCurve::Linear), middle);
+    assert!(shape_velocity(middle, VelocityCurve::Exponential) < middle);
+    assert!(shape_velocity(middle, VelocityCurve::Logarithmic) > middle);
+}
+
+#[test]
+fn test_scale_keeps_a_performance_in_key() {
+    // G major pentatonic: G A B D E
+    let config = Config {
+        board_type: BoardType::Random,
+        scale: Scale::Pentatonic,
+        scale_root: "G".to_string(),
+        ..Config::default()
+    };
+    let allowed: Vec<usize> = ["G1", "A1", "B1", "D1", "E1"].iter()
+        .map(|name| note_name_to_key(name).unwrap() % 12)
+        .collect();
+
+    let notes = simulate_notes(&config, 10);
+    assert!(notes.iter().any(|keys| !keys.is_empty()));
+    for keys in &notes {
+        assert!(keys.iter().all(|key| allowed.contains(&(key % 12))), "{:?}", keys);
+    }
+}
+
+#[test]
+fn test_dry_run_is_deterministic() {
+    let config = Config {
+        board_type: BoardType::Random,
+        seed: Some(42),
+        generations: GenerationLimit::Limited(8),
+        ..Config::default()
+    };
+
+    let record = |config: &Config| {
+        let mut steps = Vec::new();
+        let count = dry_run(config, |step, keys| steps.push((step, keys.to_vec())));
+        (count, steps)
+    };
+
+    let (count, steps) = record(&config);
+    assert_eq!(count, 8, "Dry run respects the generation limit");
+    assert_eq!(steps.iter().map(|(step, _)| *step).collect::<Vec<_>>(), (1..=8).collect::<Vec<_>>());
+    assert_eq!(record(&config), (count, steps.clone()), "Same seed, same keys");
+
+    // The keys are the ones a real run would play
+    let played: Vec<Vec<usize>> = steps.into_iter().map(|(_, keys)| keys).collect();
+    assert_eq!(played, simulate_notes(&config, 8));
+}
+
+#[test]
+fn test_describe_keys_names_each_key() {
+    assert_eq!(describe_keys(&[39, 48, 87]), "39 (C4), 48 (A4), 87 (C8)");
+    assert_eq!(describe_keys(&[]), "-");
+}
@@ -0,0 +1,37 @@
+// Integration tests for playing several board rows at once
+
+use config::{KeyRangePolicy, SonifyRow};
+use conways_steinway::sonify_rows;
+use life::{Cell, GameOfLife, BOARD_HEIGHT};
+
+#[test]
+fn test_two_rows_play_the_union_of_their_keys() {
+    let mut game = GameOfLife::new();
+    let bottom = BOARD_HEIGHT - 1;
+    game.set_cell(bottom, 3, Cell::Alive);
+    game.set_cell(bottom, 5, Cell::Alive);
+    game.set_cell(bottom - 1, 5, Cell::Alive);
+    game.set_cell(bottom - 1, 10, Cell::Alive);
+
+    // Both rows untransposed: the shared column only sounds once
+    let rows = SonifyRow::parse_list("0,1").unwrap();
+    assert_eq!(sonify_rows(&game, &rows, KeyRangePolicy::Drop), vec![3, 5, 10]);
+
+    // The second row an octave up
+    let rows = SonifyRow::parse_list("0,1:+12").unwrap();
+    assert_eq!(sonify_rows(&game, &rows, KeyRangePolicy::Drop), vec![3, 5, 17, 22]);
+}
+
+#[test]
+fn test_parse_sonify_rows() {
+    let rows = SonifyRow::parse_list("0, 1:-12,2:+7").unwrap();
+    assert_eq!(rows, vec![
+        SonifyRow { row: 0, transpose: 0 },
+        SonifyRow { row: 1, transpose: -12 },
+        SonifyRow { row: 2, transpose: 7 },
+    ]);
+    assert_eq!(SonifyRow::format_list(&rows), "0,1:-12,2:+7");
+
+    assert!(SonifyRow::parse_list("0,bass").is_none());
+    assert!(SonifyRow::parse_list("1:up").is_none());
+}
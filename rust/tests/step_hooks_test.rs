@@ -0,0 +1,67 @@
+// Integration tests for the per-generation hooks shared by the main loop and the TUI
+
+use audio::PlayerPiano;
+use config::{Config, GenerationLimit};
+use conways_steinway::hooks::StepHooks;
+use life::GameOfLife;
+
+fn config_with_sync(av_sync_ms: u64) -> Config {
+    Config {
+        generations: GenerationLimit::Limited(3),
+        step_delay_ms: 10,
+        av_sync_ms,
+        seed: Some(5),
+        ..Config::default()
+    }
+}
+
+// Play `steps` generations, returning the steps shown during each wait and
+// those left for the end of the run
+fn shown_steps(config: &Config, steps: u32) -> (Vec<Vec<u32>>, Vec<u32>) {
+    let mut game = GameOfLife::new();
+    let piano = PlayerPiano::new_silent();
+    let mut hooks = StepHooks::new(config, &game, None);
+
+    let mut during = Vec::new();
+    for step in 1..=steps {
+        hooks.play_step(config, &mut game, &piano, step);
+        let mut shown = Vec::new();
+        hooks.wait(config, &piano, step, |visual| shown.push(visual.step));
+        during.push(shown);
+    }
+    let mut at_end = Vec::new();
+    hooks.finish(&piano, |visual| at_end.push(visual.step));
+    (during, at_end)
+}
+
+#[test]
+fn test_frames_show_with_their_step_without_sync_offset() {
+    let (during, at_end) = shown_steps(&config_with_sync(0), 3);
+    assert_eq!(during, vec![vec![1], vec![2], vec![3]]);
+    assert!(at_end.is_empty());
+}
+
+#[test]
+fn test_sync_offset_holds_frames_back() {
+    // Two step delays behind the notes
+    let (during, at_end) = shown_steps(&config_with_sync(20), 3);
+    assert_eq!(during, vec![vec![], vec![1], vec![2]]);
+    assert_eq!(at_end, vec![3]);
+}
+
+#[test]
+fn test_shown_frame_is_the_board_after_its_step() {
+    let config = config_with_sync(15);
+    let mut game = GameOfLife::new();
+    let piano = PlayerPiano::new_silent();
+    let mut hooks = StepHooks::new(&config, &game, None);
+
+    hooks.play_step(&config, &mut game, &piano, 1);
+    let after_first = game.to_string();
+    hooks.wait(&config, &piano, 1, |_| panic!("shown before the sync offset"));
+    hooks.play_step(&config, &mut game, &piano, 2);
+
+    let mut shown = Vec::new();
+    hooks.wait(&config, &piano, 2, |visual| shown.push(visual.game.to_string()));
+    assert_eq!(shown, vec![after_first]);
+}
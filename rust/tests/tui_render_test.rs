@@ -0,0 +1,37 @@
+// Tests for the terminal UI's board rendering, without touching a terminal
+
+use conways_steinway::tui::{render_cells, status_line, CellStyle};
+use life::{Cell, GameOfLife, BOARD_HEIGHT, BOARD_WIDTH};
+
+#[test]
+fn test_board_renders_to_styled_cells() {
+    let mut game = GameOfLife::new();
+    game.set_cell(0, 0, Cell::Alive);
+    game.set_cell(5, 40, Cell::Dying(1));
+
+    let rows = render_cells(&game, &[3, 87]);
+
+    // Every board row plus the keyboard row, each a full keyboard wide
+    assert_eq!(rows.len(), BOARD_HEIGHT + 1);
+    assert!(rows.iter().all(|row| row.len() == BOARD_WIDTH));
+
+    assert_eq!(rows[0][0].style, CellStyle::Alive);
+    assert_eq!(rows[0][1].style, CellStyle::Dead);
+    assert_eq!(rows[5][40].style, CellStyle::Dying);
+    assert_ne!(rows[0][0].symbol, rows[0][1].symbol);
+
+    let keyboard = &rows[BOARD_HEIGHT];
+    let played: Vec<usize> = (0..BOARD_WIDTH).filter(|&key| keyboard[key].style == CellStyle::Played).collect();
+    assert_eq!(played, vec![3, 87]);
+    assert_eq!(keyboard[4].style, CellStyle::Key);
+}
+
+#[test]
+fn test_status_line_summarizes_the_step() {
+    let mut game = GameOfLife::new();
+    game.set_cell(1, 1, Cell::Alive);
+    game.set_cell(1, 2, Cell::Alive);
+
+    assert_eq!(status_line(&game, &[48]), "Generation 0  Population 2  Keys: 48 (A4)");
+    assert_eq!(status_line(&game, &[]), "Generation 0  Population 2  Keys: -");
+}